@@ -2,7 +2,10 @@
 //!
 //! A Domain is a container for items that all participate in the same abstraction.
 //! Currently that means a model.
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use log;
 use nut::{
@@ -27,9 +30,15 @@ use crate::{
             types::{Attribute, Context as SarzakContext, Object, Relationship, Subtype, Type},
         },
     },
-    v2::domain::Domain as DomainV2,
+    v2::{domain::Domain as DomainV2, merlin::store::ObjectStore as MerlinStore},
 };
 
+#[cfg(feature = "sarzak-rwlock")]
+use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+
+#[cfg(feature = "sarzak-single")]
+use crate::v2::sarzak_single::store::ObjectStore as SarzakStore;
+
 /// Domain Builder
 ///
 /// Loading a domain isn't that complicated really: there's currently only a
@@ -87,15 +96,23 @@ use crate::{
 /// the system with instances that should exist at start-up. It's also a place
 /// where initialization events may be created and sent.
 ///
+/// # Version 2
+///
+/// [`DomainBuilder::build_v2`] doesn't go through `nut` or extrusion at all.
+/// The cuckoo model is read directly into the v2 sarzak and merlin stores. As
+/// such the pre- and post-load functions above don't apply. Use
+/// [`DomainBuilder::post_load_v2`] instead.
+///
 /// [os]: crate::sarzak::ObjectStore
 /// [s]: crate::sarzak::State
 /// [e]: crate::sarzak::Event
 pub struct DomainBuilder {
-    nut_model: Option<(PathBuf, FromModel)>,
+    cuckoo_model: Option<PathBuf>,
     pre_load: Option<
         Box<dyn Fn(&FromSarzakStore, &FromDrawingStore, &mut SarzakV1Store, &mut DrawingV1Store)>,
     >,
     post_load: Option<Box<dyn Fn(&mut SarzakV1Store, &mut DrawingV1Store)>>,
+    post_load_v2: Option<Box<dyn Fn(&mut SarzakStore, &mut MerlinStore)>>,
 }
 
 impl DomainBuilder {
@@ -103,21 +120,24 @@ impl DomainBuilder {
     ///
     pub fn new() -> Self {
         Self {
-            nut_model: None,
+            cuckoo_model: None,
             pre_load: None,
             post_load: None,
+            post_load_v2: None,
         }
     }
 
     /// Specify an input model
     ///
-    /// Currently the only input we know how to process is a cuckoo model.
+    /// Currently the only input we know how to process is a cuckoo model. The
+    /// model isn't parsed until the domain is built, but we make sure that we
+    /// can open it here.
     pub fn cuckoo_model<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
-        let nut_model = FromModel::load_cuckoo_model(&path).context(FileOpenSnafu {
+        fs::File::open(&path).context(FileOpenSnafu {
             path: path.as_ref(),
         })?;
 
-        self.nut_model = Some((path.as_ref().to_path_buf(), nut_model));
+        self.cuckoo_model = Some(path.as_ref().to_path_buf());
         Ok(self)
     }
 
@@ -151,8 +171,22 @@ impl DomainBuilder {
         self
     }
 
-    fn _build_v1(self) -> DomainV1 {
-        let (path, model) = self.nut_model.unwrap();
+    /// Version 2 post-load function
+    ///
+    /// This is the same as [`DomainBuilder::post_load`], but it's run by
+    /// [`DomainBuilder::build_v2`] against the v2 stores.
+    pub fn post_load_v2<F>(mut self, post_load: F) -> Self
+    where
+        F: Fn(&mut SarzakStore, &mut MerlinStore) + 'static,
+    {
+        self.post_load_v2 = Some(Box::new(post_load));
+
+        self
+    }
+
+    fn _build_v1(self) -> Result<DomainV1> {
+        let path = self.cuckoo_model.unwrap();
+        let model = FromModel::load_cuckoo_model(&path).context(FileOpenSnafu { path: &path })?;
 
         let mut sarzak = SarzakV1Store::new();
         let mut drawing = DrawingV1Store::new();
@@ -173,7 +207,7 @@ impl DomainBuilder {
             func(&mut sarzak, &mut drawing);
         }
 
-        DomainV1::new(path, model, sarzak, drawing)
+        Ok(DomainV1::new(path, model, sarzak, drawing))
     }
 
     /// The final step
@@ -181,27 +215,69 @@ impl DomainBuilder {
     /// Return the newly packaged domain
     pub fn build_v1(self) -> Result<DomainV1> {
         ensure!(
-            self.nut_model.is_some(),
+            self.cuckoo_model.is_some(),
             DomainBuilderSnafu {
                 message: "you must specify a cuckoo model using `DomainBuilder::cuckoo_model`"
             }
         );
 
-        Ok(self._build_v1())
+        self._build_v1()
     }
 
     /// The final step
     ///
     /// Return the newly packaged domain
+    #[cfg(feature = "sarzak-rwlock")]
     pub fn build_v2(self) -> Result<DomainV2> {
         ensure!(
-            self.nut_model.is_some(),
+            self.cuckoo_model.is_some(),
+            DomainBuilderSnafu {
+                message: "you must specify a cuckoo model using `DomainBuilder::cuckoo_model`"
+            }
+        );
+        ensure!(
+            self.pre_load.is_none() && self.post_load.is_none(),
+            DomainBuilderSnafu {
+                message: "v1 load functions don't apply to v2, use `DomainBuilder::post_load_v2`"
+            }
+        );
+
+        let mut domain = DomainV2::from_cuckoo(self.cuckoo_model.unwrap())?;
+
+        if let Some(ref func) = self.post_load_v2 {
+            log::debug!("executing postload function");
+            let (sarzak, merlin) = domain.stores_mut();
+            func(sarzak, merlin);
+        }
+
+        Ok(domain)
+    }
+
+    /// The final step
+    ///
+    /// Return the newly packaged domain, by way of a version 1 domain.
+    #[cfg(feature = "sarzak-single")]
+    pub fn build_v2(mut self) -> Result<DomainV2> {
+        ensure!(
+            self.cuckoo_model.is_some(),
             DomainBuilderSnafu {
                 message: "you must specify a cuckoo model using `DomainBuilder::cuckoo_model`"
             }
         );
 
-        Ok(self._build_v1().into())
+        // 🚧 Read the cuckoo model directly, as above. That waits on merlin,
+        // which the loader fills in, and which only builds against the
+        // `sarzak-rwlock` store.
+        let post_load_v2 = self.post_load_v2.take();
+        let mut domain: DomainV2 = self._build_v1()?.into();
+
+        if let Some(ref func) = post_load_v2 {
+            log::debug!("executing postload function");
+            let (sarzak, merlin) = domain.stores_mut();
+            func(sarzak, merlin);
+        }
+
+        Ok(domain)
     }
 }
 
@@ -362,4 +438,28 @@ mod tests {
             .build_v1();
         assert!(ok.is_ok());
     }
+
+    #[cfg(feature = "sarzak-rwlock")]
+    #[test]
+    fn test_build_v2() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let err = DomainBuilder::new().build_v2();
+        assert!(err.is_err());
+
+        let sarzak = DomainBuilder::new()
+            .cuckoo_model("models/sarzak.json")
+            .unwrap()
+            .post_load_v2(|sarzak, _merlin| {
+                crate::v2::sarzak::types::Object::new(
+                    "A test object".to_owned(),
+                    "TEST".to_owned(),
+                    "Test".to_owned(),
+                    sarzak,
+                );
+            })
+            .build_v2()
+            .unwrap();
+        assert!(sarzak.sarzak().exhume_object_id_by_name("Test").is_some());
+    }
 }
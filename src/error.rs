@@ -19,4 +19,18 @@ pub(crate) enum SarzakError {
     },
     #[snafu(display("error building domain: {}", message))]
     DomainBuilder { message: String },
+    #[snafu(display("error parsing cuckoo model {}", path.display()))]
+    CuckooParse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("error loading cuckoo model: {}", message))]
+    CuckooModel { message: String },
+    #[snafu(display("error writing cuckoo model {}", path.display()))]
+    CuckooWrite {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("error exporting cuckoo model: {}", message))]
+    CuckooExport { message: String },
 }
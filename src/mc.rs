@@ -11,6 +11,19 @@ use snafu::{prelude::*, Backtrace, Location};
 
 use crate::v2::domain::Domain;
 
+pub mod config;
+pub mod diagnostic;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod entry;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod operation;
+pub mod output;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod registry;
+
+use diagnostic::Diagnostic;
+use output::Output;
+
 pub type Result<T, E = ModelCompilerError> = std::result::Result<T, E>;
 
 #[derive(Debug, Snafu)]
@@ -43,6 +56,21 @@ pub enum ModelCompilerError {
         location: Location,
         description: String,
     },
+    /// Problems with the model, that the user can fix
+    #[snafu(display("{}", diagnostics.iter().map(|d| format!("\n{d}")).collect::<String>()))]
+    Diagnostics { diagnostics: Vec<Diagnostic> },
+}
+
+impl ModelCompilerError {
+    /// The diagnostics carried by the error
+    ///
+    /// Only [`ModelCompilerError::Diagnostics`] has any.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            Self::Diagnostics { diagnostics } => diagnostics,
+            _ => &[],
+        }
+    }
 }
 
 pub trait ModelCompilerOptions: std::fmt::Debug {
@@ -60,4 +88,25 @@ pub trait SarzakModelCompiler {
         test: bool,
         verbosity: u8,
     ) -> Result<usize, ModelCompilerError>;
+
+    /// Compile the domain, writing the files through `output`
+    ///
+    /// Paths given to `output` are relative to what would have been
+    /// `src_path`. Compilers that write straight to disk don't implement
+    /// this, and can't be dry run.
+    fn compile_to(
+        &self,
+        domain: Domain,
+        package: &str,
+        module: &str,
+        output: &mut dyn Output,
+        options: Box<&dyn ModelCompilerOptions>,
+        test: bool,
+        verbosity: u8,
+    ) -> Result<usize, ModelCompilerError> {
+        CompilerSnafu {
+            description: format!("{module}: this model compiler can only write to disk"),
+        }
+        .fail()
+    }
 }
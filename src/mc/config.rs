@@ -0,0 +1,369 @@
+//! `sarzak.toml`
+//!
+//! A crate that contains generated code has a `sarzak.toml` at its root. It
+//! lists the modules to generate, the model that each is generated from, and
+//! the model compilers to run, along with their options.
+//!
+//! ```toml
+//! [modules."v2/merlin"]
+//! model = "models/merlin.json"
+//!
+//! [[modules."v2/merlin".compiler]]
+//! compiler = "grace"
+//! derive = ["Clone", "Debug", "Deserialize", "PartialEq", "Serialize"]
+//! imported_domains = ["crate/v2/sarzak"]
+//!
+//! [modules."v2/merlin".compiler.target]
+//! target = "domain"
+//! optimization_level = "None"
+//! uber_store = "StdRwLock"
+//! persist = true
+//! ```
+//!
+//! [`Config::load`] parses and validates the file. Each [`CompilerConfig`] is
+//! a [`ModelCompilerOptions`], and is handed to the model compiler as is.
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use super::ModelCompilerOptions;
+
+/// The name of the file
+pub const CONFIG_FILE: &str = "sarzak.toml";
+
+/// Imported domains that start with this are modules in the same crate
+const CRATE_PREFIX: &str = "crate/";
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum ConfigError {
+    #[snafu(display("error reading {}: {source}", path.display()))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("error parsing {}: {source}", path.display()))]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[snafu(display("{} is invalid:\n{}", path.display(), problems.iter().map(|p| format!("  {p}")).collect::<Vec<_>>().join("\n")))]
+    Invalid {
+        path: PathBuf,
+        problems: Vec<Problem>,
+    },
+}
+
+/// Something wrong with a config that parsed
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// There's no model file at `path`
+    MissingModel { module: String, path: PathBuf },
+    /// The module has no `[[compiler]]` sections
+    NoCompiler { module: String },
+    /// An import of a module in this crate, that isn't in the config
+    UnresolvedImport { module: String, import: String },
+    /// `persist_timestamps` without `persist`
+    TimestampsWithoutPersist { module: String },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingModel { module, path } => {
+                write!(f, "{module}: model {} does not exist", path.display())
+            }
+            Self::NoCompiler { module } => write!(f, "{module}: no compiler is configured"),
+            Self::UnresolvedImport { module, import } => {
+                write!(f, "{module}: imported domain {import} is not a module")
+            }
+            Self::TimestampsWithoutPersist { module } => {
+                write!(f, "{module}: persist_timestamps requires persist")
+            }
+        }
+    }
+}
+
+/// The whole of `sarzak.toml`
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Config {
+    /// Modules to generate, by path relative to `src`, e.g. `v2/merlin`
+    pub modules: BTreeMap<String, ModuleConfig>,
+    /// The directory that the config was loaded from
+    ///
+    /// Paths in the config are relative to this.
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+/// A generated module
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ModuleConfig {
+    /// The model that the module is generated from
+    pub model: PathBuf,
+    /// The model compilers to run, in order
+    #[serde(default)]
+    pub compiler: Vec<CompilerConfig>,
+}
+
+/// A model compiler invocation, and its options
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CompilerConfig {
+    /// The name of the model compiler, e.g. `grace`
+    pub compiler: String,
+    /// Generate the code even if the model hasn't changed
+    #[serde(default)]
+    pub always_process: bool,
+    /// Traits to derive on the generated types
+    #[serde(default)]
+    pub derive: Vec<String>,
+    /// Domains whose types are used by this one, e.g. `crate/v2/sarzak`
+    #[serde(default)]
+    pub imported_domains: Vec<String>,
+    /// Extra `use` statements for the generated code
+    #[serde(default)]
+    pub use_paths: Vec<String>,
+    /// Instrument the generated code for tracy
+    #[serde(default)]
+    pub tracy: bool,
+    pub target: TargetConfig,
+}
+
+impl ModelCompilerOptions for CompilerConfig {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// What sort of code to generate
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "target", rename_all = "lowercase")]
+pub enum TargetConfig {
+    /// A domain: types, and a store to keep them in
+    Domain(DomainConfig),
+}
+
+/// Options for a [`TargetConfig::Domain`]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DomainConfig {
+    #[serde(default)]
+    pub optimization_level: OptimizationLevel,
+    #[serde(default)]
+    pub uber_store: UberStore,
+    /// Generate `persist` and `load` methods for the store
+    #[serde(default)]
+    pub persist: bool,
+    /// Keep a timestamp with each instance in the store
+    #[serde(default)]
+    pub persist_timestamps: bool,
+    #[serde(default)]
+    pub is_meta_model: bool,
+    #[serde(default)]
+    pub is_sarzak: bool,
+    /// A module to generate `From` implementations from, e.g. `v1::sarzak`
+    pub from_module: Option<String>,
+    /// The model that `from_module` was generated from
+    pub from_path: Option<PathBuf>,
+}
+
+/// How instances are kept in the store
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum OptimizationLevel {
+    /// In hash maps, keyed by id
+    #[default]
+    None,
+    /// In vectors, indexed by id
+    Vec,
+}
+
+/// How a store is shared between threads
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum UberStore {
+    /// Instances are plain values
+    #[default]
+    Disabled,
+    /// `Rc<RefCell<T>>`
+    Single,
+    /// `Arc<std::sync::RwLock<T>>`
+    StdRwLock,
+    /// `Arc<std::sync::Mutex<T>>`
+    StdMutex,
+    /// `Arc<parking_lot::RwLock<T>>`
+    ParkingLotRwLock,
+    /// `Arc<parking_lot::Mutex<T>>`
+    ParkingLotMutex,
+    /// `Arc<no_deadlocks::RwLock<T>>`
+    NDRwLock,
+    /// `Arc<async_std::sync::RwLock<T>>`
+    AsyncRwLock,
+}
+
+impl Config {
+    /// Load, and validate, the config in `dir`
+    ///
+    /// `dir` is usually the root of a crate.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self, ConfigError> {
+        let dir = dir.as_ref();
+        let path = dir.join(CONFIG_FILE);
+        let text = fs::read_to_string(&path).context(ReadSnafu { path: &path })?;
+
+        let config = Self::parse(&text, dir).context(ParseSnafu { path: &path })?;
+        let problems = config.validate();
+        ensure!(problems.is_empty(), InvalidSnafu { path, problems });
+
+        Ok(config)
+    }
+
+    /// Parse a config, without validating it
+    ///
+    /// Paths are relative to `root`.
+    pub fn parse<P: AsRef<Path>>(text: &str, root: P) -> Result<Self, toml::de::Error> {
+        let mut config: Self = toml::from_str(text)?;
+        config.root = root.as_ref().to_path_buf();
+
+        Ok(config)
+    }
+
+    /// Check the things that the parser can't
+    ///
+    /// Returns everything that's wrong, rather than stopping at the first
+    /// problem.
+    pub fn validate(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+
+        for (name, module) in &self.modules {
+            let model = self.root.join(&module.model);
+            if !model.exists() {
+                problems.push(Problem::MissingModel {
+                    module: name.clone(),
+                    path: model,
+                });
+            }
+
+            if module.compiler.is_empty() {
+                problems.push(Problem::NoCompiler {
+                    module: name.clone(),
+                });
+            }
+
+            for compiler in &module.compiler {
+                for import in &compiler.imported_domains {
+                    if let Some(imported) = import.strip_prefix(CRATE_PREFIX) {
+                        if !self.modules.contains_key(imported) {
+                            problems.push(Problem::UnresolvedImport {
+                                module: name.clone(),
+                                import: import.clone(),
+                            });
+                        }
+                    }
+                }
+
+                let TargetConfig::Domain(target) = &compiler.target;
+                if let Some(from_path) = &target.from_path {
+                    let from_path = self.root.join(from_path);
+                    if !from_path.exists() {
+                        problems.push(Problem::MissingModel {
+                            module: name.clone(),
+                            path: from_path,
+                        });
+                    }
+                }
+                if target.persist_timestamps && !target.persist {
+                    problems.push(Problem::TimestampsWithoutPersist {
+                        module: name.clone(),
+                    });
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// The modules that are generated from `model`
+    ///
+    pub fn modules_for_model<'a, P: AsRef<Path> + 'a>(
+        &'a self,
+        model: P,
+    ) -> impl Iterator<Item = (&'a String, &'a ModuleConfig)> + 'a {
+        self.modules
+            .iter()
+            .filter(move |(_, module)| module.model == model.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERLIN: &str = r#"
+[modules."v2/merlin"]
+model = "models/merlin.json"
+
+[[modules."v2/merlin".compiler]]
+compiler = "grace"
+imported_domains = ["crate/v2/sarzak", "other_crate/v2/thing"]
+
+[modules."v2/merlin".compiler.target]
+target = "domain"
+uber_store = "StdRwLock"
+persist = true
+"#;
+
+    #[test]
+    fn test_load_crate_config() {
+        let config = Config::load(".").unwrap();
+        assert_eq!(config.modules.len(), 15);
+
+        let sarzak = &config.modules["v2/sarzak"];
+        assert_eq!(sarzak.model, Path::new("models/sarzak.json"));
+        let TargetConfig::Domain(target) = &sarzak.compiler[0].target;
+        assert_eq!(target.uber_store, UberStore::StdRwLock);
+        assert!(target.persist_timestamps);
+        assert_eq!(target.from_module.as_deref(), Some("v1::sarzak"));
+
+        assert_eq!(config.modules_for_model("models/lu_dog.json").count(), 9);
+
+        // The model compiler gets its options back out
+        let options: Box<&dyn ModelCompilerOptions> = Box::new(&sarzak.compiler[0]);
+        let options = options.as_any().downcast_ref::<CompilerConfig>().unwrap();
+        assert_eq!(options.compiler, "grace");
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = Config::parse(MERLIN, ".").unwrap();
+        assert_eq!(
+            config.validate(),
+            [Problem::UnresolvedImport {
+                module: "v2/merlin".to_owned(),
+                import: "crate/v2/sarzak".to_owned()
+            }]
+        );
+
+        let text = MERLIN
+            .replace("models/merlin.json", "models/nope.json")
+            .replace("persist = true", "persist_timestamps = true");
+        let config = Config::parse(&text, ".").unwrap();
+        let problems = config.validate();
+        assert!(problems.contains(&Problem::MissingModel {
+            module: "v2/merlin".to_owned(),
+            path: Path::new(".").join("models/nope.json"),
+        }));
+        assert!(problems.contains(&Problem::TimestampsWithoutPersist {
+            module: "v2/merlin".to_owned(),
+        }));
+
+        let text = MERLIN.replace("StdRwLock", "GlobalLock");
+        let error = Config::parse(&text, ".").unwrap_err();
+        assert!(error.to_string().contains("unknown variant `GlobalLock`"));
+
+        let text = MERLIN.replace(r#"target = "domain""#, r#"target = "app""#);
+        assert!(Config::parse(&text, ".").is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Model Diagnostics
+//!
+//! When a model compiler finds something wrong with a model, the user needs to
+//! know where in the model to look, not where in the compiler the problem was
+//! noticed. A [`Diagnostic`] points at a [`ModelElement`], by id and by its
+//! path in the model, e.g. `sarzak::Object::name`. It may carry notes that
+//! point at other elements.
+//!
+//! ```ignore
+//! let attribute = ModelElement::find(&domain, &attr.id).unwrap();
+//! let object = ModelElement::find(&domain, &attr.obj_id).unwrap();
+//! return DiagnosticsSnafu {
+//!     diagnostics: vec![Diagnostic::error("attribute has an unknown type")
+//!         .at(attribute)
+//!         .note("declared on", Some(object))],
+//! }
+//! .fail();
+//! ```
+//!
+//! Compilers return them in [`ModelCompilerError::Diagnostics`].
+//!
+//! [`ModelCompilerError::Diagnostics`]: super::ModelCompilerError::Diagnostics
+use std::fmt;
+
+use ansi_term::Colour;
+use uuid::Uuid;
+
+/// How bad it is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Note => write!(f, "{}", Colour::Cyan.bold().paint("note")),
+            Self::Warning => write!(f, "{}", Colour::Yellow.bold().paint("warning")),
+            Self::Error => write!(f, "{}", Colour::Red.bold().paint("error")),
+        }
+    }
+}
+
+/// The kinds of model element that a diagnostic may point at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementKind {
+    Object,
+    Attribute,
+    Relationship,
+    State,
+    Event,
+    Operation,
+}
+
+impl fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Object => write!(f, "object"),
+            Self::Attribute => write!(f, "attribute"),
+            Self::Relationship => write!(f, "relationship"),
+            Self::State => write!(f, "state"),
+            Self::Event => write!(f, "event"),
+            Self::Operation => write!(f, "operation"),
+        }
+    }
+}
+
+/// Something in a model
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelElement {
+    pub kind: ElementKind,
+    pub id: Uuid,
+    /// The element's own name, e.g. `name`, or `R1`
+    pub name: String,
+    /// Where the element is in the model, e.g. `sarzak::Object::name`
+    pub path: String,
+}
+
+impl fmt::Display for ModelElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} {})", self.path, self.kind, self.id)
+    }
+}
+
+/// Something else that the user should know about a [`Diagnostic`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Note {
+    pub message: String,
+    pub element: Option<ModelElement>,
+}
+
+/// A problem with a model
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The element that has the problem
+    pub element: Option<ModelElement>,
+    pub notes: Vec<Note>,
+}
+
+impl Diagnostic {
+    pub fn new<S: Into<String>>(severity: Severity, message: S) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            element: None,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Point the diagnostic at an element
+    pub fn at(mut self, element: ModelElement) -> Self {
+        self.element = Some(element);
+        self
+    }
+
+    /// Add a note, optionally pointing at another element
+    pub fn note<S: Into<String>>(mut self, message: S, element: Option<ModelElement>) -> Self {
+        self.notes.push(Note {
+            message: message.into(),
+            element,
+        });
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+        if let Some(element) = &self.element {
+            write!(f, "\n  --> {element}")?;
+        }
+        for note in &self.notes {
+            write!(f, "\n   = {}: {}", Severity::Note, note.message)?;
+            if let Some(element) = &note.element {
+                write!(f, " {element}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sarzak-rwlock")]
+mod sarzak {
+    use super::*;
+    use crate::v2::{
+        domain::Domain,
+        sarzak::{lint, store::ObjectStore},
+    };
+
+    impl ModelElement {
+        /// Find the element with `id` in the domain
+        ///
+        /// Objects, attributes, relationships, states, events and operations
+        /// are looked for, in that order.
+        pub fn find(domain: &Domain, id: &Uuid) -> Option<Self> {
+            let sarzak = domain.sarzak();
+            let path = |parts: &[&str]| {
+                let mut path = vec![domain.name()];
+                path.extend(parts);
+                path.join("::")
+            };
+            let object_name = |obj_id: &Uuid| {
+                sarzak
+                    .exhume_object(obj_id)
+                    .map(|o| o.read().unwrap().name.clone())
+                    .unwrap_or_else(|| obj_id.to_string())
+            };
+            let element = |kind, name: &str, path: String| Self {
+                kind,
+                id: *id,
+                name: name.to_owned(),
+                path,
+            };
+
+            if let Some(object) = sarzak.exhume_object(id) {
+                let object = object.read().unwrap();
+                return Some(element(
+                    ElementKind::Object,
+                    &object.name,
+                    path(&[&object.name]),
+                ));
+            }
+            if let Some(attribute) = sarzak.exhume_attribute(id) {
+                let attribute = attribute.read().unwrap();
+                let object = object_name(&attribute.obj_id);
+                return Some(element(
+                    ElementKind::Attribute,
+                    &attribute.name,
+                    path(&[&object, &attribute.name]),
+                ));
+            }
+            if let Some(number) = relationship_number(sarzak, id) {
+                let name = format!("R{number}");
+                return Some(element(ElementKind::Relationship, &name, path(&[&name])));
+            }
+            if let Some(state) = sarzak.exhume_state(id) {
+                let state = state.read().unwrap();
+                let object = object_name(&state.obj_id);
+                return Some(element(
+                    ElementKind::State,
+                    &state.name,
+                    path(&[&object, &state.name]),
+                ));
+            }
+            if let Some(event) = sarzak.exhume_event(id) {
+                let event = event.read().unwrap();
+                let object = object_name(&event.obj_id);
+                return Some(element(
+                    ElementKind::Event,
+                    &event.name,
+                    path(&[&object, &event.name]),
+                ));
+            }
+            if let Some(operation) = sarzak.exhume_operation(id) {
+                let operation = operation.read().unwrap();
+                let object = object_name(&operation.obj_id);
+                return Some(element(
+                    ElementKind::Operation,
+                    &operation.name,
+                    path(&[&object, &operation.name]),
+                ));
+            }
+
+            None
+        }
+    }
+
+    impl Diagnostic {
+        /// Turn a lint finding into a diagnostic that points into the model
+        ///
+        pub fn from_lint(domain: &Domain, lint: &lint::Diagnostic) -> Self {
+            let diagnostic = Self::error(lint.to_string());
+            match ModelElement::find(domain, &lint.id()) {
+                Some(element) => diagnostic.at(element),
+                None => diagnostic,
+            }
+        }
+    }
+
+    /// The number of the relationship with `id`
+    ///
+    /// The id may be that of the `Relationship`, or of the `Binary`, `Isa` or
+    /// `Associative` that it is.
+    fn relationship_number(sarzak: &ObjectStore, id: &Uuid) -> Option<i64> {
+        sarzak
+            .exhume_binary(id)
+            .map(|b| b.read().unwrap().number)
+            .or_else(|| sarzak.exhume_isa(id).map(|i| i.read().unwrap().number))
+            .or_else(|| {
+                sarzak
+                    .exhume_associative(id)
+                    .map(|a| a.read().unwrap().number)
+            })
+    }
+}
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use super::*;
+    use crate::{
+        mc::{DiagnosticsSnafu, ModelCompilerError},
+        v2::{domain::Domain, sarzak::lint},
+    };
+
+    #[test]
+    fn test_find() {
+        let domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak();
+
+        let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+        let element = ModelElement::find(&domain, &object).unwrap();
+        assert_eq!(element.kind, ElementKind::Object);
+        assert_eq!(element.path, "sarzak::Object");
+
+        let attribute = sarzak
+            .exhume_attribute_by_obj_id(&object)
+            .into_iter()
+            .find(|a| a.read().unwrap().name == "description")
+            .unwrap();
+        let id = attribute.read().unwrap().id;
+        let element = ModelElement::find(&domain, &id).unwrap();
+        assert_eq!(element.kind, ElementKind::Attribute);
+        assert_eq!(element.path, "sarzak::Object::description");
+
+        let binary = sarzak.iter_binary().find(|b| b.read().unwrap().number == 1);
+        let id = binary.unwrap().read().unwrap().id;
+        let element = ModelElement::find(&domain, &id).unwrap();
+        assert_eq!(element.kind, ElementKind::Relationship);
+        assert_eq!(element.path, "sarzak::R1");
+
+        assert!(ModelElement::find(&domain, &Uuid::nil()).is_none());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let object = domain.sarzak().exhume_object_id_by_name("Object").unwrap();
+        let object = ModelElement::find(&domain, &object).unwrap();
+        let r1 = domain
+            .sarzak()
+            .iter_binary()
+            .find(|b| b.read().unwrap().number == 1)
+            .unwrap()
+            .read()
+            .unwrap()
+            .id;
+        let r1 = ModelElement::find(&domain, &r1).unwrap();
+
+        let error: ModelCompilerError = DiagnosticsSnafu {
+            diagnostics: vec![
+                Diagnostic::error("object has no identifier")
+                    .at(object.clone())
+                    .note("formalized by", Some(r1)),
+                Diagnostic::warning("unused object").at(object),
+            ],
+        }
+        .build();
+        assert_eq!(error.diagnostics().len(), 2);
+        assert!(error.diagnostics()[0].is_error());
+
+        let rendered = error.to_string();
+        assert!(rendered.contains("object has no identifier\n  --> sarzak::Object (object "));
+        assert!(rendered.contains(": formalized by sarzak::R1 (relationship "));
+        assert!(rendered.contains("unused object"));
+
+        // Lint findings point into the model too
+        let obj_id = domain.sarzak().exhume_object_id_by_name("Object").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let attribute = sarzak.exhume_attribute_by_obj_id(&obj_id)[0].clone();
+        let mut dangling = attribute.read().unwrap().clone();
+        dangling.id = Uuid::new_v4();
+        dangling.name = "dangling".to_owned();
+        dangling.ty = Uuid::new_v4();
+        let id = dangling.id;
+        sarzak.inter_attribute(std::sync::Arc::new(std::sync::RwLock::new(dangling)));
+
+        let lint = lint::lint(domain.sarzak())
+            .into_iter()
+            .find(|d| d.id() == id)
+            .unwrap();
+        let diagnostic = Diagnostic::from_lint(&domain, &lint);
+        assert_eq!(diagnostic.element.unwrap().path, "sarzak::Object::dangling");
+    }
+}
@@ -0,0 +1,189 @@
+//! State Entry Signatures
+//!
+//! The data that an event carries, its [`Parameter`]s, is handed to the state
+//! that the event moves an instance to. Generated state entry code takes them
+//! as typed arguments, in parameter order:
+//!
+//! ```ignore
+//! for argument in entry_arguments(&domain, &state.id)? {
+//!     write!(buffer, "{argument}, ")?; // e.g. `name: String`
+//! }
+//! ```
+//!
+//! Every event that enters a state has to carry the same data, by name and by
+//! type. If they don't the state can't have a signature, and that's reported as
+//! a [`Diagnostic`] that points at the state, and at the events that disagree.
+//!
+//! [`Parameter`]: crate::v2::sarzak::types::Parameter
+use std::fmt;
+
+use heck::ToUpperCamelCase;
+use uuid::Uuid;
+
+use super::{
+    diagnostic::{Diagnostic, ModelElement},
+    DiagnosticsSnafu, Result,
+};
+use crate::v2::{
+    domain::Domain,
+    sarzak::{store::ObjectStore as SarzakStore, types::Ty},
+};
+
+/// An argument to a state's entry code, or to an operation
+#[derive(Clone, Debug, PartialEq)]
+pub struct Argument {
+    pub name: String,
+    pub ty: Ty,
+    /// The Rust type of the argument, e.g. `i64`, or `Object`
+    pub type_name: String,
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.type_name)
+    }
+}
+
+/// The arguments of the state with id `state`
+///
+/// A state that no transition enters, e.g. one that instances are created in,
+/// has no arguments.
+pub fn entry_arguments(domain: &Domain, state: &Uuid) -> Result<Vec<Argument>> {
+    let sarzak = domain.sarzak();
+
+    let mut events: Vec<Uuid> = sarzak
+        .exhume_state(state)
+        .map(|s| s.read().unwrap().r38_next_state(sarzak))
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|n| n.read().unwrap().r30_transition(sarzak))
+        .map(|t| t.read().unwrap().event)
+        .collect();
+    events.sort();
+    events.dedup();
+
+    let mut signature: Option<(Uuid, Vec<Argument>)> = None;
+    for event in events {
+        let arguments = event_arguments(sarzak, &event);
+        match &signature {
+            None => signature = Some((event, arguments)),
+            Some((first, expected)) if *expected != arguments => {
+                let mut diagnostic =
+                    Diagnostic::error("events that enter this state carry different data")
+                        .note("carries one set", ModelElement::find(domain, first))
+                        .note("carries another", ModelElement::find(domain, &event));
+                if let Some(state) = ModelElement::find(domain, state) {
+                    diagnostic = diagnostic.at(state);
+                }
+                return DiagnosticsSnafu {
+                    diagnostics: vec![diagnostic],
+                }
+                .fail();
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(signature
+        .map(|(_, arguments)| arguments)
+        .unwrap_or_default())
+}
+
+fn event_arguments(sarzak: &SarzakStore, event: &Uuid) -> Vec<Argument> {
+    let event = match sarzak.exhume_event(event) {
+        Some(event) => event,
+        None => return Vec::new(),
+    };
+    let event = event.read().unwrap();
+
+    event
+        .parameters(sarzak)
+        .into_iter()
+        .map(|param| {
+            let param = param.read().unwrap();
+            let ty = sarzak
+                .exhume_ty(&param.ty)
+                .map(|ty| *ty.read().unwrap())
+                .unwrap_or(Ty::ZUuid(param.ty));
+            Argument {
+                name: param.name.clone(),
+                type_name: type_name(sarzak, &ty),
+                ty,
+            }
+        })
+        .collect()
+}
+
+/// The Rust type of `ty`
+pub(super) fn type_name(sarzak: &SarzakStore, ty: &Ty) -> String {
+    match ty {
+        Ty::Boolean(_) => "bool".to_owned(),
+        Ty::Float(_) => "f64".to_owned(),
+        Ty::Integer(_) => "i64".to_owned(),
+        Ty::ZString(_) => "String".to_owned(),
+        Ty::ZUuid(_) => "Uuid".to_owned(),
+        Ty::Object(id) => sarzak
+            .exhume_object(id)
+            .map(|o| o.read().unwrap().name.to_upper_camel_case())
+            .unwrap_or_else(|| "Uuid".to_owned()),
+        Ty::Enumeration(id) => sarzak
+            .exhume_enumeration(id)
+            .map(|e| e.read().unwrap().name.to_upper_camel_case())
+            .unwrap_or_else(|| "Uuid".to_owned()),
+        Ty::External(id) => sarzak
+            .exhume_external(id)
+            .map(|e| e.read().unwrap().name.clone())
+            .unwrap_or_else(|| "Uuid".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::types::{
+        Event, NextState, Object, Parameter, State, Transition, INTEGER, Z_STRING,
+    };
+
+    #[test]
+    fn test_entry_arguments() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let oven = Object::new(
+            "".to_owned(),
+            "OVEN".to_owned(),
+            "microwave oven".to_owned(),
+            sarzak,
+        );
+        let idle = State::new("idle".to_owned(), &oven, sarzak);
+        let cooking = State::new("cooking".to_owned(), &oven, sarzak);
+        let start = Event::new("start".to_owned(), &oven, sarzak);
+        let more = Event::new("more".to_owned(), &oven, sarzak);
+        let integer = sarzak.exhume_ty(&INTEGER).unwrap();
+        let string = sarzak.exhume_ty(&Z_STRING).unwrap();
+        let ty = Ty::new_object(&oven, sarzak);
+        Parameter::new("seconds".to_owned(), 0, &start, &integer, sarzak);
+        Parameter::new("by".to_owned(), 1, &start, &ty, sarzak);
+        Parameter::new("by".to_owned(), 1, &more, &ty, sarzak);
+        let seconds = Parameter::new("seconds".to_owned(), 0, &more, &integer, sarzak);
+        Transition::new_next_state(&NextState::new(&cooking, sarzak), &start, &idle, sarzak);
+        Transition::new_next_state(&NextState::new(&cooking, sarzak), &more, &cooking, sarzak);
+
+        let idle = idle.read().unwrap().id;
+        let cooking = cooking.read().unwrap().id;
+        assert!(entry_arguments(&domain, &idle).unwrap().is_empty());
+        let arguments = entry_arguments(&domain, &cooking).unwrap();
+        let rendered: Vec<_> = arguments.iter().map(|a| a.to_string()).collect();
+        assert_eq!(rendered, ["seconds: i64", "by: MicrowaveOven"]);
+
+        // Now they disagree
+        seconds.write().unwrap().ty = string.read().unwrap().id();
+        domain.sarzak_mut().inter_parameter(seconds);
+        let error = entry_arguments(&domain, &cooking).unwrap_err();
+        let diagnostic = &error.diagnostics()[0];
+        assert_eq!(
+            diagnostic.element.as_ref().unwrap().path,
+            "sarzak::microwave oven::cooking"
+        );
+        assert_eq!(diagnostic.notes.len(), 2);
+    }
+}
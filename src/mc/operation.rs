@@ -0,0 +1,137 @@
+//! Operation Signatures
+//!
+//! An [`Operation`] on an object becomes a method on the generated type. The
+//! [`Signature`] of the method comes from the model, the body is left to the
+//! compiler:
+//!
+//! ```ignore
+//! for op in object.r33_operation(domain.sarzak()) {
+//!     let signature = signature(&domain, &op.read().unwrap().id)?;
+//!     writeln!(buffer, "{signature} {{")?; // e.g. `pub fn baz(&self) -> bool`
+//!     writeln!(buffer, "    todo!()\n}}")?;
+//! }
+//! ```
+//!
+//! [`Operation`]: crate::v2::sarzak::types::Operation
+use std::fmt;
+
+use heck::ToSnakeCase;
+use snafu::prelude::*;
+use uuid::Uuid;
+
+use super::{
+    entry::{type_name, Argument},
+    ModelSnafu, Result,
+};
+use crate::v2::{domain::Domain, sarzak::types::Ty};
+
+/// The signature of the method that implements an operation
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    /// The method name, in snake case
+    pub name: String,
+    /// A class-based method doesn't take `self`.
+    pub class_based: bool,
+    pub arguments: Vec<Argument>,
+    /// The Rust type of the return value, if there is one
+    pub returns: Option<String>,
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut arguments: Vec<String> = self.arguments.iter().map(|a| a.to_string()).collect();
+        if !self.class_based {
+            arguments.insert(0, "&self".to_owned());
+        }
+        write!(f, "pub fn {}({})", self.name, arguments.join(", "))?;
+        if let Some(returns) = &self.returns {
+            write!(f, " -> {returns}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The signature of the operation with id `operation`
+pub fn signature(domain: &Domain, operation: &Uuid) -> Result<Signature> {
+    let sarzak = domain.sarzak();
+    let operation = sarzak.exhume_operation(operation).context(ModelSnafu {
+        description: format!("no operation with id {operation}"),
+    })?;
+    let operation = operation.read().unwrap();
+
+    let ty = |id: &Uuid| {
+        sarzak
+            .exhume_ty(id)
+            .map(|ty| *ty.read().unwrap())
+            .unwrap_or(Ty::ZUuid(*id))
+    };
+
+    let arguments = operation
+        .arguments(sarzak)
+        .into_iter()
+        .map(|arg| {
+            let arg = arg.read().unwrap();
+            let ty = ty(&arg.ty);
+            Argument {
+                name: arg.name.to_snake_case(),
+                type_name: type_name(sarzak, &ty),
+                ty,
+            }
+        })
+        .collect();
+
+    Ok(Signature {
+        name: operation.name.to_snake_case(),
+        class_based: operation.class_based,
+        arguments,
+        returns: operation.ret.map(|ret| type_name(sarzak, &ty(&ret))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::types::{Argument, Object, Operation, BOOLEAN, Z_STRING};
+
+    #[test]
+    fn test_signature() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let foo = Object::new("".to_owned(), "F".to_owned(), "Foo".to_owned(), sarzak);
+        let ty = Ty::new_object(&foo, sarzak);
+        let boolean = sarzak.exhume_ty(&BOOLEAN).unwrap();
+        let string = sarzak.exhume_ty(&Z_STRING).unwrap();
+        let baz = Operation::new(
+            "baz".to_owned(),
+            "".to_owned(),
+            false,
+            "".to_owned(),
+            false,
+            &foo,
+            Some(&boolean),
+            sarzak,
+        );
+        let make = Operation::new(
+            "Make Foo".to_owned(),
+            "".to_owned(),
+            true,
+            "".to_owned(),
+            false,
+            &foo,
+            Some(&ty),
+            sarzak,
+        );
+        Argument::new("bar".to_owned(), 0, &make, &string, sarzak);
+        Argument::new("other".to_owned(), 1, &make, &ty, sarzak);
+
+        let baz = signature(&domain, &baz.read().unwrap().id).unwrap();
+        assert_eq!(baz.to_string(), "pub fn baz(&self) -> bool");
+        let make = signature(&domain, &make.read().unwrap().id).unwrap();
+        assert_eq!(
+            make.to_string(),
+            "pub fn make_foo(bar: String, other: Foo) -> Foo"
+        );
+
+        assert!(signature(&domain, &Uuid::nil()).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Model Compiler Output
+//!
+//! A model compiler writes its files through an [`Output`], rather than
+//! straight to `src_path`. [`DiskOutput`] writes to disk as it goes.
+//! [`MemoryOutput`] keeps everything in memory, on top of what's on disk, so
+//! that:
+//!
+//!  - tests can look at the generated files without a temporary directory,
+//!  - a dry run can report which files would change, with
+//!    [`MemoryOutput::changes`], and
+//!  - the whole set of files can be written at once, with
+//!    [`MemoryOutput::commit`], so that a compiler that fails half way through
+//!    doesn't leave half a module behind.
+//!
+//! Paths are relative to the root of the output, which is usually `src`.
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Somewhere for a model compiler to put its files
+pub trait Output {
+    /// The contents of the file at `path`, if there is one
+    ///
+    /// Compilers that preserve hand edits read the file before they write it.
+    fn read(&self, path: &Path) -> io::Result<Option<String>>;
+
+    /// Write the file at `path`, creating directories as needed
+    fn write(&mut self, path: &Path, contents: &str) -> io::Result<()>;
+
+    /// Remove the file at `path`, if there is one
+    fn remove(&mut self, path: &Path) -> io::Result<()>;
+}
+
+/// Output that goes straight to disk
+#[derive(Clone, Debug)]
+pub struct DiskOutput {
+    root: PathBuf,
+}
+
+impl DiskOutput {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl Output for DiskOutput {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        read_file(&self.root.join(path))
+    }
+
+    fn write(&mut self, path: &Path, contents: &str) -> io::Result<()> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+
+    fn remove(&mut self, path: &Path) -> io::Result<()> {
+        remove_file(&self.root.join(path))
+    }
+}
+
+/// What would happen to a file if a [`MemoryOutput`] were committed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileChange {
+    Created,
+    Modified,
+    Removed,
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created => write!(f, "created"),
+            Self::Modified => write!(f, "modified"),
+            Self::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+/// Output that's kept in memory
+///
+/// Reads see what's been written, and fall back to the files under `root`.
+/// Nothing is written to `root` until [`MemoryOutput::commit`].
+#[derive(Clone, Debug)]
+pub struct MemoryOutput {
+    root: PathBuf,
+    /// `None` means the file was removed
+    files: BTreeMap<PathBuf, Option<String>>,
+}
+
+impl MemoryOutput {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// A file that has been written
+    ///
+    /// This doesn't look on disk.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&str> {
+        self.files.get(path.as_ref())?.as_deref()
+    }
+
+    /// The files that have been written, in path order
+    ///
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.files
+            .iter()
+            .filter_map(|(path, contents)| Some((path.as_path(), contents.as_deref()?)))
+    }
+
+    /// How the files on disk would change if the output were committed
+    ///
+    /// Files that were written with what's already on disk aren't included.
+    pub fn changes(&self) -> io::Result<Vec<(PathBuf, FileChange)>> {
+        let mut changes = Vec::new();
+        for (path, contents) in &self.files {
+            let on_disk = read_file(&self.root.join(path))?;
+            let change = match (on_disk, contents) {
+                (None, Some(_)) => FileChange::Created,
+                (Some(_), None) => FileChange::Removed,
+                (Some(old), Some(new)) if old != *new => FileChange::Modified,
+                _ => continue,
+            };
+            changes.push((path.clone(), change));
+        }
+
+        Ok(changes)
+    }
+
+    /// Write the changes to disk
+    ///
+    /// Every new file is written next to its destination first, and only once
+    /// they have all been written are they renamed into place, and removed
+    /// files removed. If writing fails, the temporary files are cleaned up and
+    /// nothing on disk has changed.
+    ///
+    /// Returns the changes that were made.
+    pub fn commit(self) -> io::Result<Vec<(PathBuf, FileChange)>> {
+        let changes = self.changes()?;
+
+        let mut staged = Vec::new();
+        for (path, _) in &changes {
+            let contents = match &self.files[path] {
+                Some(contents) => contents,
+                None => continue,
+            };
+            let path = self.root.join(path);
+            let temp = temp_path(&path);
+
+            let written = match path.parent() {
+                Some(parent) => fs::create_dir_all(parent),
+                None => Ok(()),
+            }
+            .and_then(|_| fs::write(&temp, contents));
+
+            if let Err(e) = written {
+                let _ = fs::remove_file(&temp);
+                for (temp, _) in &staged {
+                    let _ = fs::remove_file(temp);
+                }
+                return Err(e);
+            }
+            staged.push((temp, path));
+        }
+
+        for (temp, path) in staged {
+            fs::rename(temp, path)?;
+        }
+        for (path, change) in &changes {
+            if *change == FileChange::Removed {
+                remove_file(&self.root.join(path))?;
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+impl Output for MemoryOutput {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        match self.files.get(path) {
+            Some(contents) => Ok(contents.clone()),
+            None => read_file(&self.root.join(path)),
+        }
+    }
+
+    fn write(&mut self, path: &Path, contents: &str) -> io::Result<()> {
+        self.files
+            .insert(path.to_path_buf(), Some(contents.to_owned()));
+        Ok(())
+    }
+
+    fn remove(&mut self, path: &Path) -> io::Result<()> {
+        self.files.insert(path.to_path_buf(), None);
+        Ok(())
+    }
+}
+
+fn read_file(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(".sarzak-tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use super::*;
+    use crate::{
+        mc::{ModelCompilerError, ModelCompilerOptions, SarzakModelCompiler},
+        v2::domain::{migrate::scratch_dir, Domain},
+    };
+
+    /// Writes a file per object
+    struct Objects;
+
+    impl SarzakModelCompiler for Objects {
+        fn compile<P: AsRef<Path>>(
+            &self,
+            domain: Domain,
+            package: &str,
+            module: &str,
+            src_path: P,
+            options: Box<&dyn ModelCompilerOptions>,
+            test: bool,
+            verbosity: u8,
+        ) -> Result<usize, ModelCompilerError> {
+            let mut output = DiskOutput::new(src_path);
+            self.compile_to(
+                domain,
+                package,
+                module,
+                &mut output,
+                options,
+                test,
+                verbosity,
+            )
+        }
+
+        fn compile_to(
+            &self,
+            domain: Domain,
+            _package: &str,
+            module: &str,
+            output: &mut dyn Output,
+            _options: Box<&dyn ModelCompilerOptions>,
+            _test: bool,
+            _verbosity: u8,
+        ) -> Result<usize, ModelCompilerError> {
+            let mut count = 0;
+            for object in domain.sarzak().iter_object() {
+                let object = object.read().unwrap();
+                let path = Path::new(module).join(format!("{}.rs", object.name));
+                output
+                    .write(&path, &format!("pub struct {};\n", object.name))
+                    .unwrap();
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+
+    #[derive(Debug)]
+    struct NoOptions;
+
+    impl ModelCompilerOptions for NoOptions {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn compile(output: &mut dyn Output) -> usize {
+        let domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        Objects
+            .compile_to(
+                domain,
+                "sarzak",
+                "v2",
+                output,
+                Box::new(&NoOptions),
+                false,
+                0,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_memory_output() {
+        let scratch = scratch_dir("output").unwrap();
+        let root = scratch.path().join("memory");
+
+        let mut output = MemoryOutput::new(&root);
+        let count = compile(&mut output);
+        assert_eq!(output.files().count(), count);
+        assert_eq!(output.get("v2/Object.rs"), Some("pub struct Object;\n"));
+        assert!(!root.exists());
+
+        // Dry run
+        let changes = output.changes().unwrap();
+        assert_eq!(changes.len(), count);
+        assert!(changes.iter().all(|(_, c)| *c == FileChange::Created));
+
+        assert_eq!(output.commit().unwrap().len(), count);
+        assert_eq!(
+            fs::read_to_string(root.join("v2/Object.rs")).unwrap(),
+            "pub struct Object;\n"
+        );
+        assert!(!root.join("v2/.Object.rs.sarzak-tmp").exists());
+
+        // Nothing changes the second time around, except what we touch
+        let mut output = MemoryOutput::new(&root);
+        compile(&mut output);
+        output
+            .write(Path::new("v2/Object.rs"), "// edited\n")
+            .unwrap();
+        output.remove(Path::new("v2/State.rs")).unwrap();
+        assert_eq!(
+            output.read(Path::new("v2/Object.rs")).unwrap().as_deref(),
+            Some("// edited\n")
+        );
+        assert_eq!(
+            output.changes().unwrap(),
+            [
+                (PathBuf::from("v2/Object.rs"), FileChange::Modified),
+                (PathBuf::from("v2/State.rs"), FileChange::Removed)
+            ]
+        );
+        output.commit().unwrap();
+        assert!(!root.join("v2/State.rs").exists());
+
+        // Straight to disk
+        let disk = scratch.path().join("disk");
+        assert_eq!(compile(&mut DiskOutput::new(&disk)), count);
+        assert!(disk.join("v2/State.rs").exists());
+    }
+}
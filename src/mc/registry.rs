@@ -0,0 +1,382 @@
+//! Model Compiler Registry
+//!
+//! Model compilers register here by name, and the names are what go in the
+//! `compiler` field of `sarzak.toml`. [`Registry::run`] then runs each module's
+//! `[[compiler]]` entries, in order, against the module's model.
+//!
+//! ```ignore
+//! let mut registry = Registry::new();
+//! registry.register("grace", ModelCompiler::default());
+//!
+//! let config = Config::load(".")?;
+//! for report in registry.run(&config, "sarzak", "src", false, 1) {
+//!     println!("{report}");
+//! }
+//! ```
+use std::{collections::HashMap, fmt, path::Path};
+
+use super::{
+    config::{CompilerConfig, Config},
+    output::Output,
+    CompilerSnafu, ModelCompilerError, ModelCompilerOptions, ModelSnafu, Result,
+    SarzakModelCompiler,
+};
+use crate::v2::domain::Domain;
+
+/// Model compilers, by name
+#[derive(Default)]
+pub struct Registry {
+    compilers: HashMap<String, Box<dyn Registered>>,
+}
+
+/// Where the generated files go
+enum Destination<'a> {
+    Disk(&'a Path),
+    Output(&'a mut dyn Output),
+}
+
+/// A registered compiler
+///
+/// [`SarzakModelCompiler::compile`] is generic over `src_path`, which won't go
+/// in a `Box`. This pins it down.
+trait Registered: Send + Sync {
+    fn run(
+        &self,
+        domain: Domain,
+        package: &str,
+        module: &str,
+        destination: &mut Destination,
+        options: &CompilerConfig,
+        test: bool,
+        verbosity: u8,
+    ) -> Result<usize>;
+}
+
+impl<C: SarzakModelCompiler + Send + Sync> Registered for C {
+    fn run(
+        &self,
+        domain: Domain,
+        package: &str,
+        module: &str,
+        destination: &mut Destination,
+        options: &CompilerConfig,
+        test: bool,
+        verbosity: u8,
+    ) -> Result<usize> {
+        let options = Box::new(options as &dyn ModelCompilerOptions);
+        match destination {
+            Destination::Disk(src_path) => {
+                self.compile(domain, package, module, src_path, options, test, verbosity)
+            }
+            Destination::Output(output) => {
+                self.compile_to(domain, package, module, *output, options, test, verbosity)
+            }
+        }
+    }
+}
+
+/// What happened to a module
+#[derive(Debug)]
+pub struct ModuleReport {
+    pub module: String,
+    /// The count returned by each compiler that ran, in order
+    pub counts: Vec<(String, usize)>,
+    /// The error that stopped the pipeline, if there was one
+    ///
+    /// The compilers after the one that failed are not run.
+    pub error: Option<ModelCompilerError>,
+}
+
+impl ModuleReport {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl fmt::Display for ModuleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.module)?;
+        for (compiler, count) in &self.counts {
+            write!(f, " {compiler} ({count})")?;
+        }
+        if let Some(error) = &self.error {
+            write!(f, " failed: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a model compiler under `name`
+    ///
+    /// Returns `true` if a compiler was already registered under that name.
+    /// It's replaced.
+    pub fn register<N, C>(&mut self, name: N, compiler: C) -> bool
+    where
+        N: Into<String>,
+        C: SarzakModelCompiler + Send + Sync + 'static,
+    {
+        self.compilers
+            .insert(name.into(), Box::new(compiler))
+            .is_some()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.compilers.contains_key(name)
+    }
+
+    /// The names of the registered compilers, sorted
+    ///
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.compilers.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Run the pipeline for every module in `config`
+    ///
+    /// Modules are run in the order that they appear in the config's module
+    /// map. Each module's model is loaded once, and every compiler in the
+    /// pipeline gets a clone of it. The clones share their stores, so a pass
+    /// that adds to the model is seen by the passes after it.
+    ///
+    /// A module that fails doesn't stop the others.
+    pub fn run<P: AsRef<Path>>(
+        &self,
+        config: &Config,
+        package: &str,
+        src_path: P,
+        test: bool,
+        verbosity: u8,
+    ) -> Vec<ModuleReport> {
+        let mut destination = Destination::Disk(src_path.as_ref());
+        self.pipeline(config, package, &mut destination, test, verbosity)
+    }
+
+    /// Run the pipeline for every module, writing through `output`
+    ///
+    /// See [`Registry::run`]. With a [`MemoryOutput`](super::output::MemoryOutput)
+    /// this is a dry run of the whole config.
+    pub fn run_to(
+        &self,
+        config: &Config,
+        package: &str,
+        output: &mut dyn Output,
+        test: bool,
+        verbosity: u8,
+    ) -> Vec<ModuleReport> {
+        let mut destination = Destination::Output(output);
+        self.pipeline(config, package, &mut destination, test, verbosity)
+    }
+
+    fn pipeline(
+        &self,
+        config: &Config,
+        package: &str,
+        destination: &mut Destination,
+        test: bool,
+        verbosity: u8,
+    ) -> Vec<ModuleReport> {
+        config
+            .modules
+            .iter()
+            .map(|(module, module_config)| {
+                let mut report = ModuleReport {
+                    module: module.clone(),
+                    counts: Vec::new(),
+                    error: None,
+                };
+
+                let model = config.root.join(&module_config.model);
+                let domain = match load_model(&model) {
+                    Ok(domain) => domain,
+                    Err(error) => {
+                        report.error = Some(error);
+                        return report;
+                    }
+                };
+
+                for options in &module_config.compiler {
+                    let result = match self.compilers.get(&options.compiler) {
+                        Some(compiler) => compiler.run(
+                            domain.clone(),
+                            package,
+                            module,
+                            destination,
+                            options,
+                            test,
+                            verbosity,
+                        ),
+                        None => CompilerSnafu {
+                            description: format!(
+                                "{module}: there is no model compiler called {}",
+                                options.compiler
+                            ),
+                        }
+                        .fail(),
+                    };
+
+                    match result {
+                        Ok(count) => report.counts.push((options.compiler.clone(), count)),
+                        Err(error) => {
+                            report.error = Some(error);
+                            break;
+                        }
+                    }
+                }
+
+                report
+            })
+            .collect()
+    }
+}
+
+fn load_model(path: &Path) -> Result<Domain> {
+    Domain::from_cuckoo(path).map_err(|e| {
+        ModelSnafu {
+            description: format!("{}: {e}", path.display()),
+        }
+        .build()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Counts objects, and remembers what it was asked to do
+    #[derive(Default)]
+    struct Counter(Arc<Mutex<Vec<String>>>);
+
+    impl SarzakModelCompiler for Counter {
+        fn compile<P: AsRef<Path>>(
+            &self,
+            domain: Domain,
+            _package: &str,
+            module: &str,
+            _src_path: P,
+            options: Box<&dyn ModelCompilerOptions>,
+            _test: bool,
+            _verbosity: u8,
+        ) -> Result<usize, ModelCompilerError> {
+            let options = options.as_any().downcast_ref::<CompilerConfig>().unwrap();
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{module} {}", options.compiler));
+
+            Ok(domain.sarzak().iter_object().count())
+        }
+    }
+
+    struct Broken;
+
+    impl SarzakModelCompiler for Broken {
+        fn compile<P: AsRef<Path>>(
+            &self,
+            _domain: Domain,
+            _package: &str,
+            _module: &str,
+            _src_path: P,
+            _options: Box<&dyn ModelCompilerOptions>,
+            _test: bool,
+            _verbosity: u8,
+        ) -> Result<usize, ModelCompilerError> {
+            CompilerSnafu {
+                description: "broken",
+            }
+            .fail()
+        }
+    }
+
+    const CONFIG: &str = r#"
+[modules."v2/merlin"]
+model = "models/merlin.json"
+
+[[modules."v2/merlin".compiler]]
+compiler = "first"
+target = { target = "domain" }
+
+[[modules."v2/merlin".compiler]]
+compiler = "second"
+target = { target = "domain" }
+
+[modules."v2/sarzak"]
+model = "models/sarzak.json"
+
+[[modules."v2/sarzak".compiler]]
+compiler = "broken"
+target = { target = "domain" }
+
+[[modules."v2/sarzak".compiler]]
+compiler = "first"
+target = { target = "domain" }
+
+[modules."v2/woog"]
+model = "models/woog.json"
+
+[[modules."v2/woog".compiler]]
+compiler = "missing"
+target = { target = "domain" }
+"#;
+
+    #[test]
+    fn test_pipeline() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = Registry::new();
+        assert!(!registry.register("first", Counter(seen.clone())));
+        assert!(!registry.register("second", Counter(seen.clone())));
+        assert!(!registry.register("broken", Broken));
+        assert!(registry.register("broken", Broken));
+        assert_eq!(registry.names(), ["broken", "first", "second"]);
+
+        let config = Config::parse(CONFIG, ".").unwrap();
+        let reports = registry.run(&config, "sarzak", "src", false, 0);
+        assert_eq!(reports.len(), 3);
+
+        let merlin = &reports[0];
+        assert!(merlin.is_ok());
+        let objects = Domain::from_cuckoo("models/merlin.json")
+            .unwrap()
+            .sarzak()
+            .iter_object()
+            .count();
+        assert_eq!(
+            merlin.counts,
+            [
+                ("first".to_owned(), objects),
+                ("second".to_owned(), objects)
+            ]
+        );
+
+        // The pipeline stops at the first error
+        let sarzak = &reports[1];
+        assert!(!sarzak.is_ok());
+        assert!(sarzak.counts.is_empty());
+
+        let woog = &reports[2];
+        assert!(woog
+            .error
+            .as_ref()
+            .unwrap()
+            .to_string()
+            .contains("no model compiler called missing"));
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["v2/merlin first", "v2/merlin second"]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod domain;
 pub mod drawing;
+pub mod id;
+pub(crate) mod index;
 #[cfg(feature = "lu-dog-rc")]
 pub mod lu_dog;
 #[cfg(feature = "lu-dog-async-vec")]
@@ -17,10 +19,12 @@ pub mod lu_dog_vec;
 #[cfg(feature = "lu-dog-vec-tracy")]
 pub mod lu_dog_vec_tracy;
 pub mod merlin;
+pub mod notify;
 #[cfg(feature = "sarzak-rwlock")]
 pub mod sarzak;
 #[cfg(feature = "sarzak-single")]
 pub mod sarzak_single;
+pub mod transaction;
 #[cfg(feature = "woog-rwlock")]
 pub mod woog;
 #[cfg(feature = "woog-single")]
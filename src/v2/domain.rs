@@ -1,8 +1,13 @@
 //! Version 2 Sarzak Domain
 //!
 use std::path::PathBuf;
-use std::{fs, io, path::Path};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
 
+use bincode::Options;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -19,6 +24,55 @@ use crate::v2::sarzak_single::store::ObjectStore as SarzakStore;
 
 use crate::VERSION;
 
+use migrate::{crate_version, parse_version, Migrations};
+
+#[cfg(feature = "sarzak-rwlock")]
+mod cuckoo;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod delete;
+#[cfg(feature = "sarzak-rwlock")]
+mod diagram;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod diff;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod git;
+#[cfg(feature = "sarzak-rwlock")]
+pub mod merge;
+pub mod migrate;
+#[cfg(feature = "sarzak-rwlock")]
+mod svg;
+
+/// Magic number at the start of a domain bincode file
+const BINCODE_MAGIC: [u8; 4] = *b"SZKD";
+
+/// Version of the domain bincode layout
+///
+/// Bump this when the layout of [`BincodeHeader`] or [`Domain`] changes in a
+/// way that old files can't be read.
+const BINCODE_FORMAT: u32 = 8;
+
+/// Domain bincode file header
+///
+/// This is written in front of the domain by [`Domain::persist_bincode`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct BincodeHeader {
+    magic: [u8; 4],
+    format: u32,
+    /// The version of sarzak that wrote the file
+    version: String,
+}
+
+/// Options for reading and writing the header
+///
+/// These are the same as plain `bincode::serialize`, with a size limit so that
+/// reading garbage fails quickly rather than trying to allocate a huge string.
+fn header_options() -> impl bincode::Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(1024)
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct MetaData {
     version: String,
@@ -28,6 +82,8 @@ struct MetaData {
     extents: [u16; 2],
     view: [i32; 2],
     path: PathBuf,
+    #[serde(default)]
+    domain_ns: Uuid,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -100,19 +156,70 @@ impl Domain {
         &mut self.merlin
     }
 
+    /// Return mutable references to both stores at once
+    ///
+    pub(crate) fn stores_mut(&mut self) -> (&mut SarzakStore, &mut MerlinStore) {
+        (&mut self.sarzak, &mut self.merlin)
+    }
+
     pub fn path(&self) -> &PathBuf {
         &self.meta.path
     }
 
+    /// Load a domain from a directory written by [`Domain::persist`]
+    ///
+    /// Domains written by older versions of sarzak are migrated with the
+    /// default [`Migrations`]. Domains written by newer versions are an error.
     pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::load_with_migrations(path, &Migrations::default())
+    }
+
+    /// Load a domain from a directory, using the given migrations
+    ///
+    /// The migrations are run against a temporary copy of the directory.
+    pub fn load_with_migrations<P: AsRef<Path>>(
+        path: P,
+        migrations: &Migrations,
+    ) -> io::Result<Self> {
         let path = path.as_ref();
 
+        let written = Self::load_meta(path)?.version;
+        let written = parse_version(&written).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "domain at {} has an invalid version: {written}",
+                    path.display()
+                ),
+            )
+        })?;
+
+        if written > crate_version() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "domain at {} was written by sarzak {}.{}.{}, which is newer than this version ({VERSION})",
+                    path.display(),
+                    written[0],
+                    written[1],
+                    written[2]
+                ),
+            ));
+        }
+
+        let scratch;
+        let path = if migrations.pending(written).next().is_some() {
+            scratch = migrate::scratch_copy(path)?;
+            migrations.run(written, scratch.path())?;
+            scratch.path()
+        } else {
+            path
+        };
+
         let sarzak = SarzakStore::load(path)?;
         let merlin = MerlinStore::load(path)?;
-
-        let file = fs::File::open(path.join("metadata.json"))?;
-        let reader = io::BufReader::new(file);
-        let meta: MetaData = serde_json::from_reader(reader)?;
+        let mut meta = Self::load_meta(path)?;
+        meta.version = VERSION.to_owned();
 
         let domain = Domain {
             meta,
@@ -123,6 +230,13 @@ impl Domain {
         Ok(domain)
     }
 
+    fn load_meta(path: &Path) -> io::Result<MetaData> {
+        let file = fs::File::open(path.join("metadata.json"))?;
+        let reader = io::BufReader::new(file);
+
+        Ok(serde_json::from_reader(reader)?)
+    }
+
     pub fn persist<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
         let path = path.as_ref();
         fs::create_dir_all(path.parent().unwrap())?;
@@ -138,13 +252,80 @@ impl Domain {
         Ok(())
     }
 
+    /// Persist the domain as a single bincode file
+    ///
+    /// Everything goes into the file: the sarzak and merlin stores, and the
+    /// metadata. The file starts with a [`BincodeHeader`] so that we can tell
+    /// what we are looking at when it's loaded.
     pub fn persist_bincode<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
         let path = path.as_ref();
-        fs::create_dir_all(path.parent().unwrap())?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
 
-        self.sarzak.persist_bincode(path)?;
+        let file = fs::File::create(path)?;
+        let mut writer = io::BufWriter::new(file);
 
-        Ok(())
+        let header = BincodeHeader {
+            magic: BINCODE_MAGIC,
+            format: BINCODE_FORMAT,
+            version: VERSION.to_owned(),
+        };
+        header_options()
+            .serialize_into(&mut writer, &header)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        bincode::serialize_into(&mut writer, &self)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        writer.flush()
+    }
+
+    /// Load a domain from a bincode file
+    ///
+    /// The file is expected to have been written by [`Domain::persist_bincode`].
+    pub fn load_bincode<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        let reader = io::BufReader::new(file);
+
+        Self::read_bincode(reader)
+    }
+
+    /// Load a domain from bincode bytes
+    ///
+    /// This is for domains that are compiled in with `include_bytes!`.
+    pub fn from_bincode(code: &[u8]) -> io::Result<Self> {
+        Self::read_bincode(code)
+    }
+
+    fn read_bincode<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        let header: BincodeHeader = header_options()
+            .deserialize_from(&mut reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if header.magic != BINCODE_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a sarzak domain bincode file",
+            ));
+        }
+        if header.format != BINCODE_FORMAT {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "unsupported domain bincode format {}, expected {} (written by sarzak {})",
+                    header.format, BINCODE_FORMAT, header.version
+                ),
+            ));
+        }
+        if header.version != VERSION {
+            log::debug!(
+                "loading domain written by sarzak {}, this is {}",
+                header.version,
+                VERSION
+            );
+        }
+
+        bincode::deserialize_from(reader).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 }
 
@@ -163,6 +344,7 @@ impl From<DomainV1> for Domain {
                 extents: domain.extents().to_owned(),
                 view: domain.view().to_owned(),
                 path: domain.path().to_owned(),
+                domain_ns: Uuid::nil(),
             },
             sarzak,
             merlin,
@@ -171,3 +353,148 @@ impl From<DomainV1> for Domain {
         domain
     }
 }
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bincode_round_trip() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let domain = Domain::from_cuckoo("models/merlin.json").unwrap();
+        let scratch = migrate::scratch_dir("bincode").unwrap();
+        let path = scratch.path().join("merlin-domain.bin");
+        domain.persist_bincode(&path).unwrap();
+
+        let loaded = Domain::load_bincode(&path).unwrap();
+        assert_eq!(loaded.name(), domain.name());
+        assert_eq!(loaded.id(), domain.id());
+        assert_eq!(loaded.extents(), domain.extents());
+        assert_eq!(
+            loaded.sarzak().iter_object().count(),
+            domain.sarzak().iter_object().count()
+        );
+        assert_eq!(
+            loaded.merlin().iter_anchor().count(),
+            domain.merlin().iter_anchor().count()
+        );
+
+        let bytes = fs::read(&path).unwrap();
+        assert!(Domain::from_bincode(&bytes).is_ok());
+        assert!(Domain::from_bincode(&bytes[4..]).is_err());
+    }
+
+    #[test]
+    fn test_migrations() {
+        use migrate::{rewrite_instances, Migration};
+
+        let scratch = migrate::scratch_dir("migrations").unwrap();
+        let path = scratch.path().join("sarzak");
+        let domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        domain.persist(&path).unwrap();
+
+        // Pretend that attributes used to call their type `type`, and that the
+        // domain was written back then.
+        let set_version = |version: &str| {
+            let mut meta = Domain::load_meta(&path).unwrap();
+            meta.version = version.to_owned();
+            fs::write(
+                path.join("metadata.json"),
+                serde_json::to_vec_pretty(&meta).unwrap(),
+            )
+            .unwrap();
+        };
+        set_version("1.0.0");
+        rewrite_instances(&path, "sarzak.json", "attribute", |attr| {
+            let ty = attr[0].as_object_mut().unwrap().remove("ty").unwrap();
+            attr[0]["type"] = ty;
+        })
+        .unwrap();
+
+        assert!(Domain::load(&path).is_err());
+
+        fn rename_type(path: &Path) -> io::Result<()> {
+            rewrite_instances(path, "sarzak.json", "attribute", |attr| {
+                let ty = attr[0].as_object_mut().unwrap().remove("type").unwrap();
+                attr[0]["ty"] = ty;
+            })
+        }
+        let mut migrations = Migrations::new();
+        migrations.register(Migration {
+            version: [1, 1, 0],
+            description: "rename Attribute::type to Attribute::ty",
+            migrate: rename_type,
+        });
+
+        let loaded = Domain::load_with_migrations(&path, &migrations).unwrap();
+        assert_eq!(
+            loaded.sarzak().iter_attribute().count(),
+            domain.sarzak().iter_attribute().count()
+        );
+        assert_eq!(loaded.meta.version, VERSION);
+        // The original is untouched
+        assert!(Domain::load(&path).is_err());
+
+        set_version("999.0.0");
+        let error = Domain::load(&path).unwrap_err();
+        assert!(error.to_string().contains("newer than this version"));
+    }
+
+    #[test]
+    fn test_incremental_persist() {
+        use crate::v2::sarzak::types::Object;
+
+        let scratch = migrate::scratch_dir("incremental").unwrap();
+        let path = scratch.path().join("sarzak");
+        Domain::from_cuckoo("models/sarzak.json")
+            .unwrap()
+            .persist(&path)
+            .unwrap();
+        let mut domain = Domain::load(&path).unwrap();
+        let tables = path.join("sarzak.json");
+
+        // Anything that hasn't changed is left alone, so scribbling on a file
+        // shows us that it wasn't written.
+        let isa = domain.sarzak().iter_isa().next().unwrap();
+        let untouched = tables
+            .join("isa")
+            .join(format!("{}.json", isa.read().unwrap().id));
+        fs::write(&untouched, "scribble").unwrap();
+
+        let sarzak = domain.sarzak_mut();
+        let state = sarzak.exhume_object_id_by_name("State").unwrap();
+        let attributes = sarzak.exhume_attribute_by_obj_id(&state);
+        let renamed = attributes[0].clone();
+        renamed.write().unwrap().name = "renamed".to_owned();
+        sarzak.inter_attribute(renamed.clone());
+        let removed = attributes[1].read().unwrap().id;
+        sarzak.exorcise_attribute(&removed);
+        let object = Object::new("".to_owned(), "NEW".to_owned(), "New".to_owned(), sarzak);
+        // Changed in place, and never inter'd again, so it's not written
+        let isa = sarzak.exhume_object_id_by_name("Isa").unwrap();
+        let edited = sarzak.exhume_attribute_by_obj_id(&isa)[0].clone();
+        edited.write().unwrap().name = "edited".to_owned();
+
+        domain.persist(&path).unwrap();
+
+        let file = |table: &str, id: Uuid| tables.join(table).join(format!("{id}.json"));
+        let renamed = file("attribute", renamed.read().unwrap().id);
+        assert!(fs::read_to_string(renamed).unwrap().contains("\"renamed\""));
+        assert!(!file("attribute", removed).exists());
+        assert!(file("object", object.read().unwrap().id).exists());
+        let edited = file("attribute", edited.read().unwrap().id);
+        assert!(!fs::read_to_string(edited).unwrap().contains("\"edited\""));
+        assert_eq!(fs::read_to_string(&untouched).unwrap(), "scribble");
+
+        // Persisting somewhere else writes everything
+        let elsewhere = scratch.path().join("elsewhere");
+        domain.persist(&elsewhere).unwrap();
+        assert_eq!(
+            fs::read_dir(elsewhere.join("sarzak.json").join("isa"))
+                .unwrap()
+                .count(),
+            domain.sarzak().iter_isa().count()
+        );
+    }
+}
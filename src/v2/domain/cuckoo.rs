@@ -0,0 +1,1985 @@
+//! Cuckoo Model Loader and Writer
+//!
+//! Cuckoo is the model editor. It saves models as a single JSON file with three
+//! tables: `paper`, `objects`, and `relationships`. The `paper` table holds the
+//! drawing, and the other two hold the model proper.
+//!
+//! This module reads that file directly into the v2 sarzak and merlin stores.
+//! There used to be a detour through `nut` and the v1 domains, which cost us
+//! two conversions and a handful of details along the way. It also writes the
+//! stores back out, so that a model that's been edited programmatically may be
+//! opened in cuckoo.
+//!
+//! The cuckoo format doesn't store ids for things like [`Referrer`] and
+//! [`Subtype`]. We derive those as v5 UUIDs from the id of the relationship that
+//! owns them, so that loading the same model twice yields the same store.
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    error::{
+        CuckooExportSnafu, CuckooModelSnafu, CuckooParseSnafu, CuckooWriteSnafu, FileOpenSnafu,
+        Result,
+    },
+    v2::{
+        domain::{Domain, MetaData},
+        merlin::{
+            store::ObjectStore as MerlinStore,
+            types::{
+                Anchor, Bisection, Edge, Glyph, GlyphEnum, Line, LineSegment, LineSegmentPoint,
+                Point, PointEnum, RelationshipName, RelationshipPhrase, XBox, BOTTOM, LEFT, RIGHT,
+                TOP,
+            },
+        },
+        sarzak::{
+            store::ObjectStore as SarzakStore,
+            types::{
+                AnAssociativeReferent, Argument, Associative, AssociativeReferent,
+                AssociativeReferrer, Attribute, Binary, Enumeration, Enumerator, Event, Isa,
+                Object, Operation, Parameter, Referent, Referrer, Relationship, Subtype, Supertype,
+                Ty, BOOLEAN, CONDITIONAL, FLOAT, INTEGER, MANY, ONE, UNCONDITIONAL, Z_STRING,
+                Z_UUID,
+            },
+            UUID_NS,
+        },
+    },
+    VERSION,
+};
+
+/// A cuckoo table
+///
+/// Every table in the file has the same shape: a list of ids, and a map from
+/// id to entity.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Table<T> {
+    pub(crate) ids: Vec<Uuid>,
+    pub(crate) entities: BTreeMap<Uuid, T>,
+}
+
+impl<T> Table<T> {
+    /// Iterate over the entities in the order given by `ids`.
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.ids.iter().filter_map(|id| self.entities.get(id))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+impl<T> Default for Table<T> {
+    fn default() -> Self {
+        Self {
+            ids: Vec::new(),
+            entities: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooModel {
+    pub(crate) paper: Table<Paper>,
+    pub(crate) objects: Table<CuckooObject>,
+    pub(crate) relationships: Table<CuckooRelationship>,
+    /// Enumerations aren't drawn, and older models don't have them.
+    #[serde(default, skip_serializing_if = "Table::is_empty")]
+    pub(crate) enumerations: Table<CuckooEnumeration>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Paper {
+    pub(crate) id: Uuid,
+    pub(crate) description: String,
+    pub(crate) domain_name: String,
+    pub(crate) domain_ns: Uuid,
+    pub(crate) width: i64,
+    pub(crate) height: i64,
+    pub(crate) offset: Offset,
+    pub(crate) objects: BTreeMap<Uuid, ObjectUi>,
+    pub(crate) relationships: BTreeMap<Uuid, RelationshipUi>,
+    #[serde(default)]
+    pub(crate) config: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub(crate) struct Offset {
+    pub(crate) x: i64,
+    pub(crate) y: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct ObjectUi {
+    /// Older versions of cuckoo didn't write the id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) id: Option<Uuid>,
+    pub(crate) x: i64,
+    pub(crate) y: i64,
+    pub(crate) width: i64,
+    pub(crate) height: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) enum RelationshipUi {
+    #[serde(rename = "BinaryUI")]
+    Binary { from: UiEnd, to: UiEnd },
+    #[serde(rename = "IsaUI")]
+    Isa { from: UiEnd, to: Vec<UiEnd> },
+    #[serde(rename = "AssociativeUI")]
+    Associative {
+        from: Offset,
+        middle: UiEnd,
+        one: UiEnd,
+        other: UiEnd,
+    },
+}
+
+/// One end of a relationship line
+///
+/// The `id` is the id of the object to which the end is attached, and `offset`
+/// is where the relationship phrase is drawn, relative to the end.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct UiEnd {
+    pub(crate) id: Uuid,
+    pub(crate) dir: Direction,
+    pub(crate) x: i64,
+    pub(crate) y: i64,
+    pub(crate) offset: Offset,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooObject {
+    pub(crate) id: Uuid,
+    pub(crate) key_letter: String,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) attributes: BTreeMap<Uuid, CuckooAttribute>,
+    /// Events aren't drawn, but they are kept with their object so that they
+    /// survive a round trip.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) events: BTreeMap<Uuid, CuckooEvent>,
+    /// Operations aren't drawn either.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) operations: BTreeMap<Uuid, CuckooOperation>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooAttribute {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) ty: AttributeType,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) enum AttributeType {
+    Boolean,
+    Float,
+    Integer,
+    String,
+    Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooEvent {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    /// In order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) parameters: Vec<CuckooParameter>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooParameter {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) ty: ParameterType,
+}
+
+/// An attribute type, or a reference to an instance of an object
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) enum ParameterType {
+    Boolean,
+    Float,
+    Integer,
+    String,
+    Uuid,
+    Object(Uuid),
+    Enumeration(Uuid),
+}
+
+impl ParameterType {
+    /// The id of the sarzak type
+    ///
+    /// An object's type has the object's id.
+    fn ty(self) -> Uuid {
+        match self {
+            Self::Boolean => BOOLEAN,
+            Self::Float => FLOAT,
+            Self::Integer => INTEGER,
+            Self::String => Z_STRING,
+            Self::Uuid => Z_UUID,
+            Self::Object(id) | Self::Enumeration(id) => id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooOperation {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: String,
+    #[serde(default)]
+    pub(crate) class_based: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) body: Option<String>,
+    /// In order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) arguments: Vec<CuckooParameter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) returns: Option<ParameterType>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooEnumeration {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: String,
+    /// In order
+    pub(crate) enumerators: Vec<CuckooEnumerator>,
+    /// The attributes that the enumeration types
+    ///
+    /// Cuckoo only knows the primitive types, so these attributes are `Uuid`
+    /// as far as it's concerned, and the file still opens in cuckoo.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) attributes: Vec<Uuid>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooEnumerator {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) enum CuckooRelationship {
+    Binary(CuckooBinary),
+    Isa(CuckooIsa),
+    Associative(CuckooAssociative),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooBinary {
+    pub(crate) id: Uuid,
+    pub(crate) number: i64,
+    pub(crate) from: CuckooEnd,
+    pub(crate) to: CuckooEnd,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooIsa {
+    pub(crate) id: Uuid,
+    pub(crate) number: i64,
+    pub(crate) obj_id: Uuid,
+    pub(crate) subtypes: Vec<Uuid>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooAssociative {
+    pub(crate) id: Uuid,
+    pub(crate) number: i64,
+    pub(crate) from: CuckooAssociativeFrom,
+    pub(crate) one: CuckooEnd,
+    pub(crate) other: CuckooEnd,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooAssociativeFrom {
+    pub(crate) id: Uuid,
+    pub(crate) obj_id: Uuid,
+    pub(crate) cardinality: CuckooCardinality,
+    pub(crate) one_referential_attribute: String,
+    pub(crate) other_referential_attribute: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CuckooEnd {
+    pub(crate) obj_id: Uuid,
+    pub(crate) description: String,
+    pub(crate) cardinality: CuckooCardinality,
+    pub(crate) conditionality: CuckooConditionality,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) formalizing_attribute_name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) enum CuckooCardinality {
+    One,
+    Many,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) enum CuckooConditionality {
+    Conditional,
+    Unconditional,
+}
+
+impl CuckooModel {
+    /// Read a cuckoo model from disk
+    ///
+    pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = fs::File::open(path).context(FileOpenSnafu { path })?;
+        let reader = io::BufReader::new(file);
+
+        Ok(serde_json::from_reader(reader).context(CuckooParseSnafu { path })?)
+    }
+
+    fn paper(&self) -> Result<&Paper> {
+        Ok(self.paper.iter().next().context(CuckooModelSnafu {
+            message: "the model has no paper",
+        })?)
+    }
+}
+
+impl Domain {
+    /// Load a cuckoo model
+    ///
+    /// The JSON file written by cuckoo is read straight into the sarzak and
+    /// merlin stores.
+    pub fn from_cuckoo<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let model = CuckooModel::load(path)?;
+        let paper = model.paper()?;
+
+        let mut sarzak = SarzakStore::new();
+        let mut typed = load_enumerations(&model, &mut sarzak);
+        load_objects(&model, &paper.domain_ns, &mut typed, &mut sarzak)?;
+        load_events(&model, &mut sarzak)?;
+        load_operations(&model, &mut sarzak)?;
+        for rel in model.relationships.iter() {
+            load_relationship(rel, &mut sarzak)?;
+        }
+
+        let mut merlin = MerlinStore::new();
+        load_paper(paper, &sarzak, &mut merlin)?;
+
+        log::debug!("loaded cuckoo domain: {}", paper.domain_name);
+
+        Ok(Domain {
+            meta: MetaData {
+                version: VERSION.to_owned(),
+                domain: paper.domain_name.clone(),
+                id: paper.id,
+                description: paper.description.clone(),
+                extents: [paper.width as u16, paper.height as u16],
+                view: [paper.offset.x as i32, paper.offset.y as i32],
+                path: path.to_path_buf(),
+                domain_ns: paper.domain_ns,
+            },
+            sarzak,
+            merlin,
+        })
+    }
+}
+
+/// Load the enumerations, and return the enumeration that types each
+/// attribute, by attribute id
+fn load_enumerations(model: &CuckooModel, sarzak: &mut SarzakStore) -> HashMap<Uuid, Uuid> {
+    let mut typed = HashMap::default();
+    for enumeration in model.enumerations.iter() {
+        let new = Arc::new(RwLock::new(Enumeration {
+            id: enumeration.id,
+            name: enumeration.name.clone(),
+            description: enumeration.description.clone(),
+        }));
+        sarzak.inter_enumeration(new.clone());
+        Ty::new_enumeration(&new, sarzak);
+
+        for (position, enumerator) in enumeration.enumerators.iter().enumerate() {
+            sarzak.inter_enumerator(Arc::new(RwLock::new(Enumerator {
+                id: enumerator.id,
+                name: enumerator.name.clone(),
+                position: position as i64,
+                enumeration: enumeration.id,
+            })));
+        }
+
+        for attr in &enumeration.attributes {
+            typed.insert(*attr, enumeration.id);
+        }
+    }
+
+    typed
+}
+
+/// Load the objects
+///
+/// `typed` is what [`load_enumerations`] returned. Each entry is removed as its
+/// attribute is loaded, and any that are left over are an error.
+fn load_objects(
+    model: &CuckooModel,
+    domain_ns: &Uuid,
+    typed: &mut HashMap<Uuid, Uuid>,
+    sarzak: &mut SarzakStore,
+) -> Result<()> {
+    for obj in model.objects.iter() {
+        let object = Arc::new(RwLock::new(Object {
+            description: obj.description.clone(),
+            id: obj.id,
+            key_letters: obj.key_letter.clone(),
+            name: obj.name.clone(),
+        }));
+        sarzak.inter_object(object.clone());
+
+        // Every object is also a type.
+        Ty::new_object(&object, sarzak);
+
+        for attr in obj.attributes.values() {
+            // An enumeration's type has the enumeration's id.
+            let ty = match (typed.remove(&attr.id), attr.ty) {
+                (Some(enumeration), _) => enumeration,
+                (None, AttributeType::Boolean) => BOOLEAN,
+                (None, AttributeType::Float) => FLOAT,
+                (None, AttributeType::Integer) => INTEGER,
+                (None, AttributeType::String) => Z_STRING,
+                (None, AttributeType::Uuid) => Z_UUID,
+            };
+
+            // Older versions of cuckoo reused attribute ids across objects. We
+            // give the duplicates the id that cuckoo would generate today.
+            let id = if sarzak.exhume_attribute(&attr.id).is_some() {
+                Uuid::new_v5(domain_ns, format!("{}::{}", obj.name, attr.name).as_bytes())
+            } else {
+                attr.id
+            };
+
+            sarzak.inter_attribute(Arc::new(RwLock::new(Attribute {
+                id,
+                name: attr.name.clone(),
+                obj_id: obj.id,
+                ty,
+            })));
+        }
+    }
+
+    if let Some((attr, enumeration)) = typed.iter().next() {
+        let enumeration = sarzak.exhume_enumeration(enumeration).unwrap();
+        CuckooModelSnafu {
+            message: format!(
+                "enumeration {} types attribute {attr}, which isn't in the model",
+                enumeration.read().unwrap().name
+            ),
+        }
+        .fail()?;
+    }
+
+    Ok(())
+}
+
+/// Load the events, and their parameters
+///
+/// A parameter may refer to any object, so this has to wait until they are all
+/// loaded.
+fn load_events(model: &CuckooModel, sarzak: &mut SarzakStore) -> Result<()> {
+    for obj in model.objects.iter() {
+        for event in obj.events.values() {
+            sarzak.inter_event(Arc::new(RwLock::new(Event {
+                id: event.id,
+                name: event.name.clone(),
+                obj_id: obj.id,
+            })));
+
+            for (position, param) in event.parameters.iter().enumerate() {
+                let ty = load_type(param.ty, sarzak, || {
+                    format!("{}::{}::{}", obj.name, event.name, param.name)
+                })?;
+                sarzak.inter_parameter(Arc::new(RwLock::new(Parameter {
+                    id: param.id,
+                    name: param.name.clone(),
+                    position: position as i64,
+                    event: event.id,
+                    ty,
+                })));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Load the operations, and their arguments
+///
+/// Like parameters, these may refer to any object.
+fn load_operations(model: &CuckooModel, sarzak: &mut SarzakStore) -> Result<()> {
+    for obj in model.objects.iter() {
+        for op in obj.operations.values() {
+            let ret = op
+                .returns
+                .map(|ret| load_type(ret, sarzak, || format!("{}::{}", obj.name, op.name)))
+                .transpose()?;
+            sarzak.inter_operation(Arc::new(RwLock::new(Operation {
+                id: op.id,
+                name: op.name.clone(),
+                description: op.description.clone(),
+                class_based: op.class_based,
+                body: op.body.clone().unwrap_or_default(),
+                has_body: op.body.is_some(),
+                obj_id: obj.id,
+                ret,
+            })));
+
+            for (position, arg) in op.arguments.iter().enumerate() {
+                let ty = load_type(arg.ty, sarzak, || {
+                    format!("{}::{}::{}", obj.name, op.name, arg.name)
+                })?;
+                sarzak.inter_argument(Arc::new(RwLock::new(Argument {
+                    id: arg.id,
+                    name: arg.name.clone(),
+                    position: position as i64,
+                    operation: op.id,
+                    ty,
+                })));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The id of the sarzak type for `ty`, which must have been loaded
+///
+/// `what` names the thing that has the type, for the error. This is the
+/// inverse of [`parameter_type`].
+fn load_type<F>(ty: ParameterType, sarzak: &SarzakStore, what: F) -> Result<Uuid>
+where
+    F: FnOnce() -> String,
+{
+    let id = ty.ty();
+    let loaded = sarzak.exhume_ty(&id).map(|ty| *ty.read().unwrap());
+    let loaded = match ty {
+        ParameterType::Object(_) => matches!(loaded, Some(Ty::Object(_))),
+        ParameterType::Enumeration(_) => matches!(loaded, Some(Ty::Enumeration(_))),
+        _ => true,
+    };
+    ensure!(
+        loaded,
+        CuckooModelSnafu {
+            message: format!("{} refers to a missing type: {id}", what()),
+        }
+    );
+
+    Ok(id)
+}
+
+fn load_relationship(rel: &CuckooRelationship, sarzak: &mut SarzakStore) -> Result<()> {
+    match rel {
+        CuckooRelationship::Binary(binary) => {
+            let number = binary.number;
+            check_object(&binary.from.obj_id, number, sarzak)?;
+            check_object(&binary.to.obj_id, number, sarzak)?;
+
+            let referential_attribute =
+                binary
+                    .from
+                    .formalizing_attribute_name
+                    .clone()
+                    .context(CuckooModelSnafu {
+                        message: format!("R{number} is missing a formalizing attribute"),
+                    })?;
+
+            let referrer = Referrer {
+                description: binary.from.description.clone(),
+                id: derived_id(&binary.id, "referrer"),
+                referential_attribute,
+                cardinality: cardinality(binary.from.cardinality),
+                conditionality: conditionality(binary.from.conditionality),
+                obj_id: binary.from.obj_id,
+            };
+            let referent = Referent {
+                description: binary.to.description.clone(),
+                id: derived_id(&binary.id, "referent"),
+                cardinality: cardinality(binary.to.cardinality),
+                conditionality: conditionality(binary.to.conditionality),
+                obj_id: binary.to.obj_id,
+            };
+            let new = Arc::new(RwLock::new(Binary {
+                id: binary.id,
+                number,
+                from: referrer.id,
+                to: referent.id,
+            }));
+
+            sarzak.inter_referrer(Arc::new(RwLock::new(referrer)));
+            sarzak.inter_referent(Arc::new(RwLock::new(referent)));
+            sarzak.inter_binary(new.clone());
+            Relationship::new_binary(&new, sarzak);
+        }
+        CuckooRelationship::Isa(isa) => {
+            check_object(&isa.obj_id, isa.number, sarzak)?;
+
+            let supertype = Supertype {
+                id: derived_id(&isa.id, "supertype"),
+                obj_id: isa.obj_id,
+            };
+            let new = Arc::new(RwLock::new(Isa {
+                id: isa.id,
+                number: isa.number,
+                supertype: supertype.id,
+            }));
+
+            sarzak.inter_supertype(Arc::new(RwLock::new(supertype)));
+            sarzak.inter_isa(new.clone());
+            Relationship::new_isa(&new, sarzak);
+
+            for obj_id in &isa.subtypes {
+                check_object(obj_id, isa.number, sarzak)?;
+                sarzak.inter_subtype(Arc::new(RwLock::new(Subtype {
+                    id: derived_id(&isa.id, &format!("subtype::{obj_id}")),
+                    isa: isa.id,
+                    obj_id: *obj_id,
+                })));
+            }
+        }
+        CuckooRelationship::Associative(assoc) => {
+            let number = assoc.number;
+            check_object(&assoc.from.obj_id, number, sarzak)?;
+            check_object(&assoc.one.obj_id, number, sarzak)?;
+            check_object(&assoc.other.obj_id, number, sarzak)?;
+
+            let referrer = AssociativeReferrer {
+                id: assoc.from.id,
+                cardinality: cardinality(assoc.from.cardinality),
+                obj_id: assoc.from.obj_id,
+            };
+            let new = Associative {
+                id: assoc.id,
+                number,
+                from: referrer.id,
+            };
+
+            sarzak.inter_associative_referrer(Arc::new(RwLock::new(referrer)));
+            let new = Arc::new(RwLock::new(new));
+            sarzak.inter_associative(new.clone());
+            Relationship::new_associative(&new, sarzak);
+
+            for (side, end, referential_attribute) in [
+                ("one", &assoc.one, &assoc.from.one_referential_attribute),
+                (
+                    "other",
+                    &assoc.other,
+                    &assoc.from.other_referential_attribute,
+                ),
+            ] {
+                let referent = AssociativeReferent {
+                    cardinality: cardinality(end.cardinality),
+                    conditionality: conditionality(end.conditionality),
+                    description: end.description.clone(),
+                    id: derived_id(&assoc.id, &format!("{side}::referent")),
+                    obj_id: end.obj_id,
+                };
+                let an_id = derived_id(&assoc.id, &format!("{side}::an_associative_referent"));
+                sarzak.inter_an_associative_referent(Arc::new(RwLock::new(
+                    AnAssociativeReferent {
+                        id: an_id,
+                        referential_attribute: referential_attribute.clone(),
+                        associative: assoc.id,
+                        referent: referent.id,
+                    },
+                )));
+                sarzak.inter_associative_referent(Arc::new(RwLock::new(referent)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Derive an id for an instance that cuckoo doesn't store
+///
+fn derived_id(owner: &Uuid, what: &str) -> Uuid {
+    Uuid::new_v5(&UUID_NS, format!("{owner}::{what}").as_bytes())
+}
+
+fn check_object(id: &Uuid, number: i64, sarzak: &SarzakStore) -> Result<()> {
+    ensure!(
+        sarzak.exhume_object(id).is_some(),
+        CuckooModelSnafu {
+            message: format!("R{number} refers to a missing object: {id}"),
+        }
+    );
+
+    Ok(())
+}
+
+fn cardinality(card: CuckooCardinality) -> Uuid {
+    match card {
+        CuckooCardinality::One => ONE,
+        CuckooCardinality::Many => MANY,
+    }
+}
+
+fn conditionality(cond: CuckooConditionality) -> Uuid {
+    match cond {
+        CuckooConditionality::Conditional => CONDITIONAL,
+        CuckooConditionality::Unconditional => UNCONDITIONAL,
+    }
+}
+
+/// Load the drawing
+///
+/// Each object on the paper becomes an [`XBox`], and each relationship becomes
+/// a [`Line`] with a single [`LineSegment`]. The ends of the line are
+/// [`Anchor`]s on the boxes, with a [`Glyph`] that depends on the kind of
+/// relationship and the cardinality of the end.
+fn load_paper(paper: &Paper, sarzak: &SarzakStore, merlin: &mut MerlinStore) -> Result<()> {
+    let mut boxes = HashMap::default();
+    for (obj_id, oui) in &paper.objects {
+        let object = sarzak.exhume_object(obj_id).context(CuckooModelSnafu {
+            message: format!("the paper has a box for a missing object: {obj_id}"),
+        })?;
+        let x_box = XBox::new(
+            oui.height,
+            oui.width,
+            oui.x,
+            oui.y,
+            &object.read().unwrap(),
+            merlin,
+        );
+        boxes.insert(*obj_id, x_box);
+    }
+
+    for (rel_id, rui) in &paper.relationships {
+        let rel = sarzak
+            .exhume_relationship(rel_id)
+            .context(CuckooModelSnafu {
+                message: format!("the paper has a line for a missing relationship: {rel_id}"),
+            })?;
+        let rel = *rel.read().unwrap();
+
+        let line = Line::new(&rel, merlin);
+        let segment = LineSegment::new(&line, merlin);
+
+        match (rui, rel) {
+            (RelationshipUi::Binary { from, to }, Relationship::Binary(ref id)) => {
+                let binary = sarzak.exhume_binary(id).unwrap();
+                let binary = binary.read().unwrap();
+                let referrer = sarzak.exhume_referrer(&binary.from).unwrap();
+                let referrer = referrer.read().unwrap();
+                let referent = sarzak.exhume_referent(&binary.to).unwrap();
+                let referent = referent.read().unwrap();
+
+                name_line(binary.number, &line, &segment, merlin);
+
+                let glyph = cardinality_glyph(&referrer.cardinality, &line, merlin);
+                let anchor = load_anchor(from, &glyph, &line, &segment, &boxes, merlin)?;
+                phrase(&referrer.description, from, &line, &anchor, merlin);
+
+                let glyph = cardinality_glyph(&referent.cardinality, &line, merlin);
+                let anchor = load_anchor(to, &glyph, &line, &segment, &boxes, merlin)?;
+                phrase(&referent.description, to, &line, &anchor, merlin);
+            }
+            (RelationshipUi::Isa { from, to }, Relationship::Isa(ref id)) => {
+                let isa = sarzak.exhume_isa(id).unwrap();
+                name_line(isa.read().unwrap().number, &line, &segment, merlin);
+
+                let glyph = Glyph::new_z_super(&line, merlin);
+                load_anchor(from, &glyph, &line, &segment, &boxes, merlin)?;
+
+                for to in to {
+                    let glyph = Glyph::new_sub(&line, merlin);
+                    load_anchor(to, &glyph, &line, &segment, &boxes, merlin)?;
+                }
+            }
+            (
+                RelationshipUi::Associative {
+                    from,
+                    middle,
+                    one,
+                    other,
+                },
+                Relationship::Associative(ref id),
+            ) => {
+                let assoc = sarzak.exhume_associative(id).unwrap();
+                let assoc = assoc.read().unwrap();
+                let referrer = sarzak.exhume_associative_referrer(&assoc.from).unwrap();
+
+                name_line(assoc.number, &line, &segment, merlin);
+
+                // This is where the line from the associative object meets the
+                // line between the other two.
+                let point = Point::new_inflection(from.x, from.y, merlin);
+                LineSegmentPoint::new(&segment, &point, merlin);
+
+                let glyph = cardinality_glyph(&referrer.read().unwrap().cardinality, &line, merlin);
+                load_anchor(middle, &glyph, &line, &segment, &boxes, merlin)?;
+
+                for end in [one, other] {
+                    let referent = assoc
+                        .r22_an_associative_referent(sarzak)
+                        .iter()
+                        .map(|an| an.read().unwrap().r22_associative_referent(sarzak)[0].clone())
+                        .find(|referent| referent.read().unwrap().obj_id == end.id)
+                        .context(CuckooModelSnafu {
+                            message: format!(
+                                "R{} is drawn to an object that it does not relate: {}",
+                                assoc.number, end.id
+                            ),
+                        })?;
+                    let referent = referent.read().unwrap();
+
+                    let glyph = cardinality_glyph(&referent.cardinality, &line, merlin);
+                    let anchor = load_anchor(end, &glyph, &line, &segment, &boxes, merlin)?;
+                    phrase(&referent.description, end, &line, &anchor, merlin);
+                }
+            }
+            _ => CuckooModelSnafu {
+                message: format!("the line for {rel_id} does not match its relationship"),
+            }
+            .fail()?,
+        }
+    }
+
+    Ok(())
+}
+
+fn name_line(
+    number: i64,
+    line: &Arc<RwLock<Line>>,
+    segment: &Arc<RwLock<LineSegment>>,
+    merlin: &mut MerlinStore,
+) {
+    // Default to putting the relationship at the midpoint of the line,
+    let bisection = Bisection::new(0.5, segment, merlin);
+    RelationshipName::new(format!("R{number}"), 0, 0, line, &bisection, merlin);
+}
+
+fn cardinality_glyph(
+    card: &Uuid,
+    line: &Arc<RwLock<Line>>,
+    merlin: &mut MerlinStore,
+) -> Arc<RwLock<Glyph>> {
+    if card == &ONE {
+        Glyph::new_one(line, merlin)
+    } else {
+        Glyph::new_many(line, merlin)
+    }
+}
+
+fn phrase(
+    text: &str,
+    end: &UiEnd,
+    line: &Arc<RwLock<Line>>,
+    anchor: &Arc<RwLock<Anchor>>,
+    merlin: &mut MerlinStore,
+) {
+    RelationshipPhrase::new(
+        text.to_owned(),
+        end.offset.x,
+        end.offset.y,
+        line,
+        anchor,
+        merlin,
+    );
+}
+
+fn load_anchor(
+    end: &UiEnd,
+    glyph: &Arc<RwLock<Glyph>>,
+    line: &Arc<RwLock<Line>>,
+    segment: &Arc<RwLock<LineSegment>>,
+    boxes: &HashMap<Uuid, Arc<RwLock<XBox>>>,
+    merlin: &mut MerlinStore,
+) -> Result<Arc<RwLock<Anchor>>> {
+    let x_box = boxes.get(&end.id).context(CuckooModelSnafu {
+        message: format!("a line is attached to a missing box: {}", end.id),
+    })?;
+
+    let edge = match end.dir {
+        Direction::North => TOP,
+        Direction::South => BOTTOM,
+        Direction::East => RIGHT,
+        Direction::West => LEFT,
+    };
+    let edge = merlin.exhume_edge(&edge).unwrap();
+
+    // How far along the edge the line is attached, as a fraction of its length.
+    let offset = {
+        let x_box = x_box.read().unwrap();
+        match *edge.read().unwrap() {
+            Edge::Top(_) | Edge::Bottom(_) => (end.x - x_box.x) as f64 / x_box.width as f64,
+            Edge::Left(_) | Edge::Right(_) => (end.y - x_box.y) as f64 / x_box.height as f64,
+        }
+    };
+
+    let anchor = Anchor::new(
+        offset,
+        end.offset.x,
+        end.offset.y,
+        &edge,
+        glyph,
+        x_box,
+        line,
+        merlin,
+    );
+    let point = Point::new_anchor(end.x, end.y, &anchor, merlin);
+    LineSegmentPoint::new(segment, &point, merlin);
+
+    Ok(anchor)
+}
+
+impl Domain {
+    /// Save the domain as a cuckoo model
+    ///
+    /// The sarzak and merlin stores are written back out in the format that
+    /// cuckoo expects, so that the model may be opened in the editor again.
+    ///
+    /// A couple of things don't survive the trip: cuckoo's `config`, and the
+    /// `formalizing_attribute_name` on the ends of an associative relationship.
+    /// Neither has a home in the metamodel.
+    pub fn persist_cuckoo<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let model = self.to_cuckoo()?;
+
+        let file = fs::File::create(path).context(FileOpenSnafu { path })?;
+        let writer = io::BufWriter::new(file);
+        serde_json::to_writer(writer, &model).context(CuckooWriteSnafu { path })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn to_cuckoo(&self) -> Result<CuckooModel> {
+        let paper = Paper {
+            id: self.meta.id,
+            description: self.meta.description.clone(),
+            domain_name: self.meta.domain.clone(),
+            domain_ns: self.meta.domain_ns,
+            width: self.meta.extents[0] as i64,
+            height: self.meta.extents[1] as i64,
+            offset: Offset {
+                x: self.meta.view[0] as i64,
+                y: self.meta.view[1] as i64,
+            },
+            objects: self
+                .merlin
+                .iter_x_box()
+                .map(|x_box| {
+                    let x_box = x_box.read().unwrap();
+                    (
+                        x_box.object,
+                        ObjectUi {
+                            id: Some(x_box.object),
+                            x: x_box.x,
+                            y: x_box.y,
+                            width: x_box.width,
+                            height: x_box.height,
+                        },
+                    )
+                })
+                .collect(),
+            relationships: self
+                .merlin
+                .iter_line()
+                .map(|line| {
+                    let line = line.read().unwrap();
+                    Ok((
+                        line.relationship,
+                        save_line(&line, &self.sarzak, &self.merlin)?,
+                    ))
+                })
+                .collect::<Result<_>>()?,
+            config: None,
+        };
+
+        let objects = self
+            .sarzak
+            .iter_object()
+            .map(|obj| {
+                let obj = obj.read().unwrap();
+                Ok((obj.id, save_object(&obj, &self.sarzak)?))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        let relationships = self
+            .sarzak
+            .iter_relationship()
+            .map(|rel| {
+                let rel = *rel.read().unwrap();
+                Ok((rel.id(), save_relationship(&rel, &self.sarzak)?))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        let enumerations: BTreeMap<_, _> = self
+            .sarzak
+            .iter_enumeration()
+            .map(|enumeration| {
+                let enumeration = enumeration.read().unwrap();
+                let enumerators = enumeration
+                    .enumerators(&self.sarzak)
+                    .into_iter()
+                    .map(|enumerator| {
+                        let enumerator = enumerator.read().unwrap();
+                        CuckooEnumerator {
+                            id: enumerator.id,
+                            name: enumerator.name.clone(),
+                        }
+                    })
+                    .collect();
+                let mut attributes: Vec<Uuid> = self
+                    .sarzak
+                    .exhume_attribute_by_ty(&enumeration.id)
+                    .into_iter()
+                    .map(|attr| attr.read().unwrap().id)
+                    .collect();
+                attributes.sort();
+
+                (
+                    enumeration.id,
+                    CuckooEnumeration {
+                        id: enumeration.id,
+                        name: enumeration.name.clone(),
+                        description: enumeration.description.clone(),
+                        enumerators,
+                        attributes,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(CuckooModel {
+            paper: Table {
+                ids: vec![paper.id],
+                entities: BTreeMap::from([(paper.id, paper)]),
+            },
+            objects: Table {
+                ids: objects.keys().cloned().collect(),
+                entities: objects,
+            },
+            relationships: Table {
+                ids: relationships.keys().cloned().collect(),
+                entities: relationships,
+            },
+            enumerations: Table {
+                ids: enumerations.keys().cloned().collect(),
+                entities: enumerations,
+            },
+        })
+    }
+}
+
+fn save_object(obj: &Object, sarzak: &SarzakStore) -> Result<CuckooObject> {
+    let attributes = sarzak
+        .iter_attribute()
+        .filter(|attr| attr.read().unwrap().obj_id == obj.id)
+        .map(|attr| {
+            let attr = attr.read().unwrap();
+            let ty = match attr.ty {
+                BOOLEAN => AttributeType::Boolean,
+                FLOAT => AttributeType::Float,
+                INTEGER => AttributeType::Integer,
+                Z_STRING => AttributeType::String,
+                Z_UUID => AttributeType::Uuid,
+                // The enumeration lists the attribute.
+                id if matches!(
+                    sarzak.exhume_ty(&id).map(|ty| *ty.read().unwrap()),
+                    Some(Ty::Enumeration(_))
+                ) =>
+                {
+                    AttributeType::Uuid
+                }
+                _ => CuckooExportSnafu {
+                    message: format!(
+                        "cuckoo can't express the type of {}::{}",
+                        obj.name, attr.name
+                    ),
+                }
+                .fail()?,
+            };
+
+            Ok((
+                attr.id,
+                CuckooAttribute {
+                    id: attr.id,
+                    name: attr.name.clone(),
+                    ty,
+                },
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    let events = sarzak
+        .exhume_event_by_obj_id(&obj.id)
+        .into_iter()
+        .map(|event| {
+            let event = event.read().unwrap();
+            let parameters = event
+                .parameters(sarzak)
+                .into_iter()
+                .map(|param| {
+                    let param = param.read().unwrap();
+                    Ok(CuckooParameter {
+                        id: param.id,
+                        name: param.name.clone(),
+                        ty: parameter_type(sarzak, &param.ty, || {
+                            format!("{}::{}::{}", obj.name, event.name, param.name)
+                        })?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+
+            Ok((
+                event.id,
+                CuckooEvent {
+                    id: event.id,
+                    name: event.name.clone(),
+                    parameters,
+                },
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    let operations = sarzak
+        .exhume_operation_by_obj_id(&obj.id)
+        .into_iter()
+        .map(|op| {
+            let op = op.read().unwrap();
+            let arguments = op
+                .arguments(sarzak)
+                .into_iter()
+                .map(|arg| {
+                    let arg = arg.read().unwrap();
+                    Ok(CuckooParameter {
+                        id: arg.id,
+                        name: arg.name.clone(),
+                        ty: parameter_type(sarzak, &arg.ty, || {
+                            format!("{}::{}::{}", obj.name, op.name, arg.name)
+                        })?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let returns = op
+                .ret
+                .map(|ret| parameter_type(sarzak, &ret, || format!("{}::{}", obj.name, op.name)))
+                .transpose()?;
+
+            Ok((
+                op.id,
+                CuckooOperation {
+                    id: op.id,
+                    name: op.name.clone(),
+                    description: op.description.clone(),
+                    class_based: op.class_based,
+                    body: op.has_body.then(|| op.body.clone()),
+                    arguments,
+                    returns,
+                },
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(CuckooObject {
+        id: obj.id,
+        key_letter: obj.key_letters.clone(),
+        name: obj.name.clone(),
+        description: obj.description.clone(),
+        attributes,
+        events,
+        operations,
+    })
+}
+
+/// The cuckoo type of the sarzak type with id `ty`
+///
+/// `what` names the thing that has the type, for the error.
+fn parameter_type<F>(sarzak: &SarzakStore, ty: &Uuid, what: F) -> Result<ParameterType>
+where
+    F: FnOnce() -> String,
+{
+    Ok(match sarzak.exhume_ty(ty).map(|ty| *ty.read().unwrap()) {
+        Some(Ty::Boolean(_)) => ParameterType::Boolean,
+        Some(Ty::Float(_)) => ParameterType::Float,
+        Some(Ty::Integer(_)) => ParameterType::Integer,
+        Some(Ty::ZString(_)) => ParameterType::String,
+        Some(Ty::ZUuid(_)) => ParameterType::Uuid,
+        Some(Ty::Object(id)) => ParameterType::Object(id),
+        Some(Ty::Enumeration(id)) => ParameterType::Enumeration(id),
+        Some(Ty::External(_)) | None => CuckooExportSnafu {
+            message: format!("cuckoo can't express the type of {}", what()),
+        }
+        .fail()?,
+    })
+}
+
+fn save_relationship(rel: &Relationship, sarzak: &SarzakStore) -> Result<CuckooRelationship> {
+    Ok(match rel {
+        Relationship::Binary(id) => {
+            let binary = sarzak.exhume_binary(id).unwrap();
+            let binary = binary.read().unwrap();
+            let referrer = sarzak.exhume_referrer(&binary.from).unwrap();
+            let referrer = referrer.read().unwrap();
+            let referent = sarzak.exhume_referent(&binary.to).unwrap();
+            let referent = referent.read().unwrap();
+
+            CuckooRelationship::Binary(CuckooBinary {
+                id: binary.id,
+                number: binary.number,
+                from: CuckooEnd {
+                    obj_id: referrer.obj_id,
+                    description: referrer.description.clone(),
+                    cardinality: save_cardinality(&referrer.cardinality),
+                    conditionality: save_conditionality(&referrer.conditionality),
+                    formalizing_attribute_name: Some(referrer.referential_attribute.clone()),
+                },
+                to: CuckooEnd {
+                    obj_id: referent.obj_id,
+                    description: referent.description.clone(),
+                    cardinality: save_cardinality(&referent.cardinality),
+                    conditionality: save_conditionality(&referent.conditionality),
+                    formalizing_attribute_name: None,
+                },
+            })
+        }
+        Relationship::Isa(id) => {
+            let isa = sarzak.exhume_isa(id).unwrap();
+            let isa = isa.read().unwrap();
+            let supertype = sarzak.exhume_supertype(&isa.supertype).unwrap();
+            let obj_id = supertype.read().unwrap().obj_id;
+
+            let mut subtypes: Vec<Uuid> = sarzak
+                .iter_subtype()
+                .filter(|sub| sub.read().unwrap().isa == isa.id)
+                .map(|sub| sub.read().unwrap().obj_id)
+                .collect();
+            subtypes.sort();
+
+            CuckooRelationship::Isa(CuckooIsa {
+                id: isa.id,
+                number: isa.number,
+                obj_id,
+                subtypes,
+            })
+        }
+        Relationship::Associative(id) => {
+            let assoc = sarzak.exhume_associative(id).unwrap();
+            let assoc = assoc.read().unwrap();
+            let referrer = sarzak.exhume_associative_referrer(&assoc.from).unwrap();
+            let referrer = referrer.read().unwrap();
+
+            let (one, other) = associative_sides(&assoc, sarzak)?;
+            let one = one.read().unwrap();
+            let other = other.read().unwrap();
+            let save_end = |an: &AnAssociativeReferent| {
+                let referent = sarzak.exhume_associative_referent(&an.referent).unwrap();
+                let referent = referent.read().unwrap();
+                CuckooEnd {
+                    obj_id: referent.obj_id,
+                    description: referent.description.clone(),
+                    cardinality: save_cardinality(&referent.cardinality),
+                    conditionality: save_conditionality(&referent.conditionality),
+                    formalizing_attribute_name: None,
+                }
+            };
+
+            CuckooRelationship::Associative(CuckooAssociative {
+                id: assoc.id,
+                number: assoc.number,
+                from: CuckooAssociativeFrom {
+                    id: referrer.id,
+                    obj_id: referrer.obj_id,
+                    cardinality: save_cardinality(&referrer.cardinality),
+                    one_referential_attribute: one.referential_attribute.clone(),
+                    other_referential_attribute: other.referential_attribute.clone(),
+                },
+                one: save_end(&one),
+                other: save_end(&other),
+            })
+        }
+    })
+}
+
+/// Sort out which end of an associative is "one" and which is "other"
+///
+/// The metamodel doesn't care, but cuckoo does. If we loaded the model then
+/// the id tells us. Otherwise we just pick one, consistently.
+fn associative_sides(
+    assoc: &Associative,
+    sarzak: &SarzakStore,
+) -> Result<(
+    Arc<RwLock<AnAssociativeReferent>>,
+    Arc<RwLock<AnAssociativeReferent>>,
+)> {
+    let mut sides: Vec<_> = sarzak
+        .iter_an_associative_referent()
+        .filter(|an| an.read().unwrap().associative == assoc.id)
+        .collect();
+    ensure!(
+        sides.len() == 2,
+        CuckooExportSnafu {
+            message: format!(
+                "R{} has {} associative referents, and cuckoo needs two",
+                assoc.number,
+                sides.len()
+            ),
+        }
+    );
+
+    let one = derived_id(&assoc.id, "one::an_associative_referent");
+    sides.sort_by_key(|an| {
+        let id = an.read().unwrap().id;
+        (id != one, id)
+    });
+    let other = sides.pop().unwrap();
+    let one = sides.pop().unwrap();
+
+    Ok((one, other))
+}
+
+fn save_cardinality(card: &Uuid) -> CuckooCardinality {
+    if card == &ONE {
+        CuckooCardinality::One
+    } else {
+        CuckooCardinality::Many
+    }
+}
+
+fn save_conditionality(cond: &Uuid) -> CuckooConditionality {
+    if cond == &CONDITIONAL {
+        CuckooConditionality::Conditional
+    } else {
+        CuckooConditionality::Unconditional
+    }
+}
+
+/// Save the drawing of a relationship
+///
+/// This is the inverse of [`load_paper`]. The trick is figuring out which
+/// [`Anchor`] belongs to which end of the relationship. We go by the object
+/// to which it's attached, and the phrase that's drawn next to it.
+fn save_line(line: &Line, sarzak: &SarzakStore, merlin: &MerlinStore) -> Result<RelationshipUi> {
+    let rel = sarzak
+        .exhume_relationship(&line.relationship)
+        .context(CuckooExportSnafu {
+            message: format!(
+                "a line is drawn for a missing relationship: {}",
+                line.relationship
+            ),
+        })?;
+    let rel = *rel.read().unwrap();
+    let mut anchors = line.r3_anchor(merlin);
+
+    Ok(match rel {
+        Relationship::Binary(ref id) => {
+            let binary = sarzak.exhume_binary(id).unwrap();
+            let binary = binary.read().unwrap();
+            let referrer = sarzak.exhume_referrer(&binary.from).unwrap();
+            let referrer = referrer.read().unwrap();
+            let referent = sarzak.exhume_referent(&binary.to).unwrap();
+            let referent = referent.read().unwrap();
+
+            RelationshipUi::Binary {
+                from: take_end(
+                    &mut anchors,
+                    &referrer.obj_id,
+                    Some(&referrer.description),
+                    binary.number,
+                    merlin,
+                )?,
+                to: take_end(
+                    &mut anchors,
+                    &referent.obj_id,
+                    Some(&referent.description),
+                    binary.number,
+                    merlin,
+                )?,
+            }
+        }
+        Relationship::Isa(ref id) => {
+            let isa = sarzak.exhume_isa(id).unwrap();
+            let number = isa.read().unwrap().number;
+
+            let index = anchors
+                .iter()
+                .position(|anchor| {
+                    let glyph = anchor.read().unwrap().r10_glyph(merlin)[0].clone();
+                    let is_super = matches!(glyph.read().unwrap().subtype, GlyphEnum::ZSuper(_));
+                    is_super
+                })
+                .context(CuckooExportSnafu {
+                    message: format!("the line for R{number} has no supertype end"),
+                })?;
+            let from = save_anchor(&anchors.remove(index).read().unwrap(), merlin);
+
+            let mut to: Vec<UiEnd> = anchors
+                .iter()
+                .map(|anchor| save_anchor(&anchor.read().unwrap(), merlin))
+                .collect();
+            to.sort_by_key(|end| end.id);
+
+            RelationshipUi::Isa { from, to }
+        }
+        Relationship::Associative(ref id) => {
+            let assoc = sarzak.exhume_associative(id).unwrap();
+            let assoc = assoc.read().unwrap();
+            let referrer = sarzak.exhume_associative_referrer(&assoc.from).unwrap();
+            let referrer = referrer.read().unwrap();
+            let (one, other) = associative_sides(&assoc, sarzak)?;
+
+            let from = line
+                .r4_line_segment(merlin)
+                .iter()
+                .flat_map(|seg| seg.read().unwrap().r5_line_segment_point(merlin))
+                .map(|lsp| lsp.read().unwrap().r5_point(merlin)[0].clone())
+                .find(|point| matches!(point.read().unwrap().subtype, PointEnum::Inflection(_)))
+                .context(CuckooExportSnafu {
+                    message: format!("the line for R{} has no inflection point", assoc.number),
+                })?;
+            let from = from.read().unwrap();
+
+            let middle = take_end(&mut anchors, &referrer.obj_id, None, assoc.number, merlin)?;
+
+            let mut take_side = |an: &Arc<RwLock<AnAssociativeReferent>>| {
+                let referent = an.read().unwrap().r22_associative_referent(sarzak)[0].clone();
+                let referent = referent.read().unwrap();
+                take_end(
+                    &mut anchors,
+                    &referent.obj_id,
+                    Some(&referent.description),
+                    assoc.number,
+                    merlin,
+                )
+            };
+            let one = take_side(&one)?;
+            let other = take_side(&other)?;
+
+            RelationshipUi::Associative {
+                from: Offset {
+                    x: from.x,
+                    y: from.y,
+                },
+                middle,
+                one,
+                other,
+            }
+        }
+    })
+}
+
+/// Find, and remove, the anchor for one end of a relationship
+///
+/// We look for an anchor on the right object with the right phrase. `None`
+/// means that there shouldn't be a phrase at all. Failing that, any anchor on
+/// the right object will do.
+fn take_end(
+    anchors: &mut Vec<Arc<RwLock<Anchor>>>,
+    obj_id: &Uuid,
+    text: Option<&str>,
+    number: i64,
+    merlin: &MerlinStore,
+) -> Result<UiEnd> {
+    let on_object = |anchor: &Arc<RwLock<Anchor>>| {
+        let x_box = anchor.read().unwrap().r3_x_box(merlin)[0].clone();
+        let object = x_box.read().unwrap().object;
+        &object == obj_id
+    };
+    let has_text = |anchor: &Arc<RwLock<Anchor>>| {
+        let phrase = anchor.read().unwrap().r13c_relationship_phrase(merlin);
+        let phrase = phrase
+            .first()
+            .map(|phrase| phrase.read().unwrap().text.clone());
+        phrase.as_deref() == text
+    };
+
+    let index = anchors
+        .iter()
+        .position(|anchor| on_object(anchor) && has_text(anchor))
+        .or_else(|| anchors.iter().position(on_object))
+        .context(CuckooExportSnafu {
+            message: format!("the line for R{number} is not attached to {obj_id}"),
+        })?;
+
+    let anchor = anchors.remove(index);
+    let anchor = anchor.read().unwrap();
+
+    Ok(save_anchor(&anchor, merlin))
+}
+
+fn save_anchor(anchor: &Anchor, merlin: &MerlinStore) -> UiEnd {
+    let x_box = anchor.r3_x_box(merlin)[0].clone();
+    let point = anchor.r6_point(merlin)[0].clone();
+    let point = point.read().unwrap();
+    let edge = anchor.r9_edge(merlin)[0].clone();
+    let dir = match *edge.read().unwrap() {
+        Edge::Top(_) => Direction::North,
+        Edge::Bottom(_) => Direction::South,
+        Edge::Right(_) => Direction::East,
+        Edge::Left(_) => Direction::West,
+    };
+
+    let id = x_box.read().unwrap().object;
+    UiEnd {
+        id,
+        dir,
+        x: point.x,
+        y: point.y,
+        offset: Offset {
+            x: anchor.x_offset,
+            y: anchor.y_offset,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_cuckoo_models() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        for model in ["sarzak", "merlin", "drawing", "lu_dog", "woog"] {
+            let path = format!("models/{model}.json");
+            let cuckoo = CuckooModel::load(&path).unwrap();
+            let domain = Domain::from_cuckoo(&path).unwrap();
+
+            assert_eq!(domain.name(), model);
+            assert_eq!(
+                domain.sarzak().iter_object().count(),
+                cuckoo.objects.ids.len()
+            );
+            assert_eq!(
+                domain.sarzak().iter_relationship().count(),
+                cuckoo.relationships.ids.len()
+            );
+            assert_eq!(
+                domain.merlin().iter_x_box().count(),
+                cuckoo.paper().unwrap().objects.len()
+            );
+            assert_eq!(
+                domain.merlin().iter_line().count(),
+                cuckoo.paper().unwrap().relationships.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_is_deterministic() {
+        let a = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let b = Domain::from_cuckoo("models/sarzak.json").unwrap();
+
+        let mut a: Vec<_> = a
+            .sarzak()
+            .iter_referrer()
+            .map(|r| r.read().unwrap().id)
+            .collect();
+        let mut b: Vec<_> = b
+            .sarzak()
+            .iter_referrer()
+            .map(|r| r.read().unwrap().id)
+            .collect();
+        a.sort();
+        b.sort();
+        assert_eq!(a, b);
+    }
+
+    /// Scrub the things that we don't round-trip, and put lists that have no
+    /// meaningful order into one.
+    ///
+    /// Attribute ids may be rewritten on load, so attributes are compared by
+    /// name and type. Box ids are only written by newer versions of cuckoo.
+    /// lu_dog lists one subtype twice, and the store only keeps one.
+    fn normalize(value: &mut serde_json::Value) {
+        use serde_json::Value;
+
+        match value {
+            Value::Object(map) => {
+                map.remove("config");
+                if map.contains_key("width") && map.contains_key("x") {
+                    map.remove("id");
+                }
+                if let Some(Value::Object(attrs)) = map.get("attributes") {
+                    let attrs = attrs
+                        .values()
+                        .map(|attr| Value::Array(vec![attr["name"].clone(), attr["type"].clone()]))
+                        .collect();
+                    map.insert("attributes".to_owned(), Value::Array(attrs));
+                }
+                for (key, value) in map.iter_mut() {
+                    if key == "one" || key == "other" {
+                        if let Value::Object(end) = value {
+                            end.remove("formalizing_attribute_name");
+                        }
+                    }
+                    normalize(value);
+                }
+            }
+            Value::Array(list) => {
+                list.iter_mut().for_each(normalize);
+                list.sort_by_key(|v| v.to_string());
+                list.dedup();
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_cuckoo_round_trip() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let scratch = super::super::migrate::scratch_dir("cuckoo-round-trip").unwrap();
+        for model in ["sarzak", "lu_dog"] {
+            let path = format!("models/{model}.json");
+            let domain = Domain::from_cuckoo(&path).unwrap();
+
+            let out = scratch.path().join(format!("{model}.json"));
+            domain.persist_cuckoo(&out).unwrap();
+
+            let mut expected: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            let mut actual: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+            normalize(&mut expected);
+            normalize(&mut actual);
+
+            assert_eq!(expected, actual, "{model} did not survive the round trip");
+        }
+    }
+
+    /// Add to the sarzak model with `build`, and take it through cuckoo JSON
+    /// and then through the JSON store
+    ///
+    /// Returns what `build` returned, and the domain as loaded from cuckoo and
+    /// then from the store.
+    fn round_trip<T>(name: &str, build: impl FnOnce(&mut SarzakStore) -> T) -> (T, Domain, Domain) {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let built = build(domain.sarzak_mut());
+
+        let scratch = super::super::migrate::scratch_dir(&format!("{name}-round-trip")).unwrap();
+        let out = scratch.path().join("sarzak.json");
+        domain.persist_cuckoo(&out).unwrap();
+        let loaded = Domain::from_cuckoo(&out).unwrap();
+
+        let path = scratch.path().join("sarzak");
+        loaded.persist(&path).unwrap();
+        let reloaded = Domain::load(&path).unwrap();
+
+        (built, loaded, reloaded)
+    }
+
+    #[test]
+    fn test_event_round_trip() {
+        let ((object, event), loaded, reloaded) = round_trip("event", |sarzak| {
+            let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+            let object = sarzak.exhume_object(&object).unwrap();
+            let event = Event::new("renamed".to_owned(), &object, sarzak);
+            let string = sarzak.exhume_ty(&Z_STRING).unwrap();
+            let ty = sarzak.exhume_ty(&object.read().unwrap().id).unwrap();
+            // Out of order, to make sure that position wins
+            Parameter::new("by".to_owned(), 1, &event, &ty, sarzak);
+            Parameter::new("name".to_owned(), 0, &event, &string, sarzak);
+            let object = object.read().unwrap().id;
+            let event = event.read().unwrap().id;
+            (object, event)
+        });
+
+        let event = loaded.sarzak().exhume_event(&event).unwrap();
+        let event = event.read().unwrap();
+        assert_eq!(event.name, "renamed");
+        assert_eq!(event.obj_id, object);
+        let params: Vec<_> = event
+            .parameters(loaded.sarzak())
+            .into_iter()
+            .map(|p| {
+                let p = p.read().unwrap();
+                (p.name.clone(), p.position, p.ty)
+            })
+            .collect();
+        assert_eq!(
+            params,
+            [
+                ("name".to_owned(), 0, Z_STRING),
+                ("by".to_owned(), 1, object)
+            ]
+        );
+
+        assert_eq!(
+            reloaded.sarzak().exhume_parameter_by_event(&event.id).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_operation_round_trip() {
+        let ((object, baz, describe), loaded, reloaded) = round_trip("operation", |sarzak| {
+            let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+            let object = sarzak.exhume_object(&object).unwrap();
+            let boolean = sarzak.exhume_ty(&BOOLEAN).unwrap();
+            let integer = sarzak.exhume_ty(&INTEGER).unwrap();
+            let ty = sarzak.exhume_ty(&object.read().unwrap().id).unwrap();
+            let baz = Operation::new(
+                "baz".to_owned(),
+                "".to_owned(),
+                true,
+                "baz.dwarf".to_owned(),
+                true,
+                &object,
+                Some(&boolean),
+                sarzak,
+            );
+            Argument::new("count".to_owned(), 1, &baz, &integer, sarzak);
+            Argument::new("other".to_owned(), 0, &baz, &ty, sarzak);
+            let describe = Operation::new(
+                "describe".to_owned(),
+                "".to_owned(),
+                false,
+                "".to_owned(),
+                false,
+                &object,
+                None,
+                sarzak,
+            );
+            let object = object.read().unwrap().id;
+            let baz = baz.read().unwrap().id;
+            let describe = describe.read().unwrap().id;
+            (object, baz, describe)
+        });
+
+        let baz = loaded.sarzak().exhume_operation(&baz).unwrap();
+        let baz = baz.read().unwrap();
+        assert_eq!(baz.name, "baz");
+        assert!(baz.class_based);
+        assert!(baz.has_body);
+        assert_eq!(baz.body, "baz.dwarf");
+        assert_eq!(baz.ret, Some(BOOLEAN));
+        let args: Vec<_> = baz
+            .arguments(loaded.sarzak())
+            .into_iter()
+            .map(|a| {
+                let a = a.read().unwrap();
+                (a.name.clone(), a.position, a.ty)
+            })
+            .collect();
+        assert_eq!(
+            args,
+            [
+                ("other".to_owned(), 0, object),
+                ("count".to_owned(), 1, INTEGER)
+            ]
+        );
+
+        let describe = loaded.sarzak().exhume_operation(&describe).unwrap();
+        let describe = describe.read().unwrap();
+        assert!(!describe.class_based);
+        assert!(!describe.has_body && describe.ret.is_none());
+        let object = loaded.sarzak().exhume_object(&object).unwrap();
+        assert_eq!(
+            object.read().unwrap().r33_operation(loaded.sarzak()).len(),
+            2
+        );
+
+        assert_eq!(
+            reloaded
+                .sarzak()
+                .exhume_argument_by_operation(&baz.id)
+                .len(),
+            2
+        );
+        assert_eq!(reloaded.sarzak().exhume_operation_by_ret(&BOOLEAN).len(), 1);
+    }
+
+    #[test]
+    fn test_enumeration_round_trip() {
+        let ((color, attr), loaded, reloaded) = round_trip("enumeration", |sarzak| {
+            let color = Enumeration::new("Color".to_owned(), "".to_owned(), sarzak);
+            // Out of order, to make sure that position wins
+            Enumerator::new("green".to_owned(), 1, &color, sarzak);
+            Enumerator::new("red".to_owned(), 0, &color, sarzak);
+            Enumerator::new("blue".to_owned(), 2, &color, sarzak);
+            let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+            let object = sarzak.exhume_object(&object).unwrap();
+            let ty = Ty::new_enumeration(&color, sarzak);
+            assert_eq!(ty.read().unwrap().id(), color.read().unwrap().id);
+            let attr = Attribute::new("color".to_owned(), &object, &ty, sarzak);
+            let color = color.read().unwrap().id;
+            let attr = attr.read().unwrap().id;
+            (color, attr)
+        });
+
+        let enumeration = loaded.sarzak().exhume_enumeration(&color).unwrap();
+        let enumeration = enumeration.read().unwrap();
+        assert_eq!(enumeration.name, "Color");
+        let names: Vec<_> = enumeration
+            .enumerators(loaded.sarzak())
+            .into_iter()
+            .map(|e| e.read().unwrap().name.clone())
+            .collect();
+        assert_eq!(names, ["red", "green", "blue"]);
+        assert_eq!(
+            *loaded.sarzak().exhume_ty(&color).unwrap().read().unwrap(),
+            Ty::Enumeration(color)
+        );
+        let attr = loaded.sarzak().exhume_attribute(&attr).unwrap();
+        assert_eq!(attr.read().unwrap().ty, color);
+
+        assert_eq!(
+            reloaded
+                .sarzak()
+                .exhume_enumerator_by_enumeration(&color)
+                .len(),
+            3
+        );
+        assert!(matches!(
+            *reloaded.sarzak().exhume_ty(&color).unwrap().read().unwrap(),
+            Ty::Enumeration(_)
+        ));
+    }
+
+    /// Load the sarzak model, after making a hand edit to its JSON
+    fn load_edited(edit: impl FnOnce(&mut serde_json::Value)) -> Result<Domain> {
+        let mut model: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string("models/sarzak.json").unwrap()).unwrap();
+        edit(&mut model);
+
+        let scratch = super::super::migrate::scratch_dir("cuckoo-edited").unwrap();
+        let path = scratch.path().join("sarzak.json");
+        fs::write(&path, model.to_string()).unwrap();
+
+        Domain::from_cuckoo(&path)
+    }
+
+    /// The id of the object called `name`, in the JSON
+    fn object_id(model: &serde_json::Value, name: &str) -> String {
+        model["objects"]["entities"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .find(|(_, obj)| obj["name"] == name)
+            .map(|(id, _)| id.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_enumeration_typing() {
+        // The attribute's type is still one that cuckoo knows.
+        let mut attr = Uuid::nil();
+        let domain = load_edited(|model| {
+            let object = object_id(model, "Object");
+            let (id, name) = model["objects"]["entities"][&object]["attributes"]
+                .as_object()
+                .unwrap()
+                .iter()
+                .find(|(_, attr)| attr["name"] == "name")
+                .map(|(id, attr)| (id.clone(), attr["type"].clone()))
+                .unwrap();
+            assert_eq!(name, "String");
+            attr = Uuid::parse_str(&id).unwrap();
+            let color = Uuid::new_v4().to_string();
+            model["enumerations"] = serde_json::json!({
+                "ids": [color],
+                "entities": {color.clone(): {
+                    "id": color,
+                    "name": "Color",
+                    "enumerators": [],
+                    "attributes": [id],
+                }},
+            });
+        })
+        .unwrap();
+        let attr = domain.sarzak().exhume_attribute(&attr).unwrap();
+        let ty = domain.sarzak().exhume_ty(&attr.read().unwrap().ty).unwrap();
+        assert!(matches!(*ty.read().unwrap(), Ty::Enumeration(_)));
+
+        // An enumeration that types an attribute that doesn't exist
+        let error = load_edited(|model| {
+            let color = Uuid::new_v4().to_string();
+            model["enumerations"] = serde_json::json!({
+                "ids": [color],
+                "entities": {color.clone(): {
+                    "id": color,
+                    "name": "Color",
+                    "enumerators": [],
+                    "attributes": [Uuid::new_v4()],
+                }},
+            });
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("Color"), "{error}");
+    }
+
+    #[test]
+    fn test_unknown_parameter_type() {
+        let error = load_edited(|model| {
+            let object = object_id(model, "Object");
+            let event = Uuid::new_v4().to_string();
+            model["objects"]["entities"][&object]["events"] = serde_json::json!({
+                event.clone(): {
+                    "id": event,
+                    "name": "renamed",
+                    "parameters": [{
+                        "id": Uuid::new_v4(),
+                        "name": "by",
+                        "type": {"Object": Uuid::new_v4()},
+                    }],
+                },
+            });
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("Object::renamed::by"), "{error}");
+    }
+
+    #[test]
+    fn test_unknown_operation_type() {
+        let operation = |returns: serde_json::Value, argument: serde_json::Value| {
+            move |model: &mut serde_json::Value| {
+                let object = object_id(model, "Object");
+                let op = Uuid::new_v4().to_string();
+                model["objects"]["entities"][&object]["operations"] = serde_json::json!({
+                    op.clone(): {
+                        "id": op,
+                        "name": "baz",
+                        "arguments": [{
+                            "id": Uuid::new_v4(),
+                            "name": "other",
+                            "type": argument,
+                        }],
+                        "returns": returns,
+                    },
+                });
+            }
+        };
+        let bogus = || serde_json::json!({"Enumeration": Uuid::new_v4()});
+
+        let error = load_edited(operation(bogus(), serde_json::json!("Integer"))).unwrap_err();
+        assert!(error.to_string().contains("Object::baz refers"), "{error}");
+        let error = load_edited(operation(serde_json::json!("Boolean"), bogus())).unwrap_err();
+        assert!(error.to_string().contains("Object::baz::other"), "{error}");
+        assert!(load_edited(operation(
+            serde_json::json!("Boolean"),
+            serde_json::json!("Integer")
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_missing_model() {
+        assert!(Domain::from_cuckoo("models/no_such_model.json").is_err());
+    }
+}
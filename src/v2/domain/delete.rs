@@ -0,0 +1,636 @@
+//! Referential Integrity Aware Deletion
+//!
+//! The `exorcise_*` methods on the stores remove exactly one instance, and
+//! leave anything that referred to it dangling. This module deletes along the
+//! relationships in the metamodel instead.
+//!
+//! There are two sorts of things that go along with an instance.
+//!
+//! Some instances are *parts* of another, and are always deleted with it. A
+//! binary relationship is nothing without its referrer and referent, an object
+//! has its entry in the [`Ty`] table, and the drawing of a model element is
+//! no use once the element is gone.
+//!
+//! Other instances are *dependents*: they refer to the instance being deleted,
+//! but are model elements in their own right, e.g. the attributes of an
+//! object, or a relationship that an object participates in. What happens to
+//! them depends on the [`DeleteMode`].
+use std::{collections::BTreeSet, fmt};
+
+use uuid::Uuid;
+
+use crate::v2::{
+    domain::Domain,
+    merlin::{store::ObjectStore as MerlinStore, types::PointEnum},
+    sarzak::{
+        store::ObjectStore as SarzakStore,
+        types::{Relationship, TransitionEnum, Ty},
+    },
+};
+
+/// What to do about dependents
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeleteMode {
+    /// Delete them too, along with their dependents, and so on.
+    Cascade,
+    /// Don't delete anything if there are dependents.
+    Reject,
+}
+
+/// An instance in either of a domain's stores
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Instance {
+    // sarzak
+    AcknowledgedEvent(Uuid),
+    AnAssociativeReferent(Uuid),
+    Argument(Uuid),
+    Associative(Uuid),
+    AssociativeReferent(Uuid),
+    AssociativeReferrer(Uuid),
+    Attribute(Uuid),
+    Binary(Uuid),
+    Enumeration(Uuid),
+    Enumerator(Uuid),
+    Event(Uuid),
+    Isa(Uuid),
+    NextState(Uuid),
+    Object(Uuid),
+    Operation(Uuid),
+    Parameter(Uuid),
+    Referent(Uuid),
+    Referrer(Uuid),
+    Relationship(Uuid),
+    State(Uuid),
+    Subtype(Uuid),
+    Supertype(Uuid),
+    Transition(Uuid),
+    Ty(Uuid),
+    // merlin
+    Anchor(Uuid),
+    Bisection(Uuid),
+    Glyph(Uuid),
+    Line(Uuid),
+    LineSegment(Uuid),
+    LineSegmentPoint(Uuid),
+    Point(Uuid),
+    RelationshipName(Uuid),
+    RelationshipPhrase(Uuid),
+    XBox(Uuid),
+}
+
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Debug is `Kind(id)`, which is just what we want.
+        write!(f, "{self:?}")
+    }
+}
+
+/// A deletion that was refused
+///
+/// `blockers` are the dependents that would have to be deleted first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Blocked {
+    pub instance: Instance,
+    pub blockers: Vec<Instance>,
+}
+
+impl fmt::Display for Blocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is still referred to by ", self.instance)?;
+        let blockers: Vec<String> = self.blockers.iter().map(|b| b.to_string()).collect();
+        write!(f, "{}", blockers.join(", "))
+    }
+}
+
+impl std::error::Error for Blocked {}
+
+impl Domain {
+    /// Delete an instance, and everything that goes with it
+    ///
+    /// Returns every instance that was deleted. In [`DeleteMode::Reject`],
+    /// nothing is deleted if anything depends on `instance`, and the
+    /// dependents are returned in the error. Deleting something that isn't
+    /// there does nothing.
+    pub fn delete(
+        &mut self,
+        instance: Instance,
+        mode: DeleteMode,
+    ) -> Result<Vec<Instance>, Blocked> {
+        let (sarzak, merlin) = (&self.sarzak, &self.merlin);
+
+        if !exists(instance, sarzak, merlin) {
+            return Ok(Vec::new());
+        }
+
+        let mut doomed = BTreeSet::new();
+        let mut blockers = BTreeSet::new();
+        let mut work = vec![instance];
+
+        while let Some(next) = work.pop() {
+            if !doomed.insert(next) {
+                continue;
+            }
+            work.extend(parts(next, sarzak, merlin));
+            match mode {
+                DeleteMode::Cascade => work.extend(dependents(next, &doomed, sarzak)),
+                DeleteMode::Reject => blockers.extend(dependents(next, &doomed, sarzak)),
+            }
+        }
+
+        let blockers: Vec<Instance> = blockers.difference(&doomed).cloned().collect();
+        if !blockers.is_empty() {
+            return Err(Blocked { instance, blockers });
+        }
+
+        for instance in &doomed {
+            exorcise(*instance, &mut self.sarzak, &mut self.merlin);
+        }
+
+        Ok(doomed.into_iter().collect())
+    }
+}
+
+/// Instances that are deleted along with `instance`, no matter what
+fn parts(instance: Instance, sarzak: &SarzakStore, merlin: &MerlinStore) -> Vec<Instance> {
+    let mut parts = Vec::new();
+
+    macro_rules! by {
+        ($lookup:ident, $id:expr, $kind:ident) => {
+            merlin
+                .$lookup($id)
+                .into_iter()
+                .map(|i| Instance::$kind(i.read().unwrap().id))
+        };
+    }
+
+    match instance {
+        Instance::Enumeration(id) => {
+            parts.push(Instance::Ty(id));
+            parts.extend(
+                sarzak
+                    .exhume_enumerator_by_enumeration(&id)
+                    .iter()
+                    .map(|e| Instance::Enumerator(e.read().unwrap().id)),
+            );
+        }
+        // A transition and its subtype go together, whichever end goes first.
+        Instance::Transition(id) => {
+            if let Some(transition) = sarzak.exhume_transition(&id) {
+                if let TransitionEnum::NextState(id) = transition.read().unwrap().subtype {
+                    parts.push(Instance::NextState(id));
+                }
+            }
+        }
+        Instance::NextState(id) => parts.extend(
+            sarzak
+                .iter_transition()
+                .filter(|t| t.read().unwrap().subtype == TransitionEnum::NextState(id))
+                .map(|t| Instance::Transition(t.read().unwrap().id)),
+        ),
+        Instance::Object(id) => {
+            parts.push(Instance::Ty(id));
+            parts.extend(by!(exhume_x_box_by_object, &id, XBox));
+        }
+        Instance::Relationship(id) => {
+            if let Some(rel) = sarzak.exhume_relationship(&id) {
+                parts.push(match *rel.read().unwrap() {
+                    Relationship::Associative(id) => Instance::Associative(id),
+                    Relationship::Binary(id) => Instance::Binary(id),
+                    Relationship::Isa(id) => Instance::Isa(id),
+                });
+            }
+            // The drawing of a relationship
+            parts.extend(by!(exhume_line_by_relationship, &id, Line));
+        }
+        Instance::Binary(id) => {
+            if let Some(binary) = sarzak.exhume_binary(&id) {
+                let binary = binary.read().unwrap();
+                parts.push(Instance::Referrer(binary.from));
+                parts.push(Instance::Referent(binary.to));
+            }
+            parts.push(Instance::Relationship(id));
+        }
+        Instance::Isa(id) => {
+            if let Some(isa) = sarzak.exhume_isa(&id) {
+                parts.push(Instance::Supertype(isa.read().unwrap().supertype));
+            }
+            parts.extend(
+                sarzak
+                    .exhume_subtype_by_isa(&id)
+                    .iter()
+                    .map(|s| Instance::Subtype(s.read().unwrap().id)),
+            );
+            parts.push(Instance::Relationship(id));
+        }
+        Instance::Associative(id) => {
+            if let Some(assoc) = sarzak.exhume_associative(&id) {
+                parts.push(Instance::AssociativeReferrer(assoc.read().unwrap().from));
+            }
+            parts.extend(
+                sarzak
+                    .exhume_an_associative_referent_by_associative(&id)
+                    .iter()
+                    .map(|a| Instance::AnAssociativeReferent(a.read().unwrap().id)),
+            );
+            parts.push(Instance::Relationship(id));
+        }
+        Instance::AnAssociativeReferent(id) => {
+            if let Some(an) = sarzak.exhume_an_associative_referent(&id) {
+                parts.push(Instance::AssociativeReferent(an.read().unwrap().referent));
+            }
+        }
+        Instance::XBox(id) => parts.extend(by!(exhume_anchor_by_x_box, &id, Anchor)),
+        Instance::Line(id) => {
+            parts.extend(by!(exhume_anchor_by_line, &id, Anchor));
+            parts.extend(by!(exhume_line_segment_by_line, &id, LineSegment));
+            parts.extend(by!(exhume_glyph_by_line, &id, Glyph));
+            parts.extend(by!(exhume_relationship_name_by_line, &id, RelationshipName));
+            parts.extend(by!(
+                exhume_relationship_phrase_by_line,
+                &id,
+                RelationshipPhrase
+            ));
+        }
+        Instance::LineSegment(id) => {
+            for lsp in merlin.exhume_line_segment_point_by_segment(&id) {
+                let lsp = lsp.read().unwrap();
+                parts.push(Instance::LineSegmentPoint(lsp.id));
+                parts.push(Instance::Point(lsp.point));
+            }
+            parts.extend(by!(exhume_bisection_by_segment, &id, Bisection));
+        }
+        Instance::Anchor(id) => {
+            if let Some(anchor) = merlin.exhume_anchor(&id) {
+                parts.push(Instance::Glyph(anchor.read().unwrap().glyph));
+            }
+            parts.push(Instance::Point(id));
+        }
+        Instance::Bisection(id) => parts.push(Instance::Point(id)),
+        Instance::Point(id) => {
+            // A point is the supertype of anchors and bisections, and is
+            // referred to by the segments that run through it.
+            if let Some(point) = merlin.exhume_point(&id) {
+                match point.read().unwrap().subtype {
+                    PointEnum::Anchor(id) => parts.push(Instance::Anchor(id)),
+                    PointEnum::Bisection(id) => parts.push(Instance::Bisection(id)),
+                    PointEnum::Inflection(_) => {}
+                }
+            }
+            parts.extend(by!(
+                exhume_line_segment_point_by_point,
+                &id,
+                LineSegmentPoint
+            ));
+        }
+        _ => {}
+    }
+
+    parts
+        .into_iter()
+        .filter(|part| exists(*part, sarzak, merlin))
+        .collect()
+}
+
+/// Instances that refer to `instance`, and are model elements in their own
+/// right
+///
+/// `doomed` is everything that is going so far.
+fn dependents(
+    instance: Instance,
+    doomed: &BTreeSet<Instance>,
+    sarzak: &SarzakStore,
+) -> Vec<Instance> {
+    macro_rules! by {
+        ($lookup:ident, $id:expr, $kind:ident) => {
+            sarzak
+                .$lookup($id)
+                .into_iter()
+                .map(|i| Instance::$kind(i.read().unwrap().id))
+        };
+    }
+
+    let mut dependents = Vec::new();
+
+    match instance {
+        Instance::Object(ref id) => {
+            dependents.extend(by!(exhume_attribute_by_obj_id, id, Attribute));
+            dependents.extend(by!(exhume_referrer_by_obj_id, id, Referrer));
+            dependents.extend(by!(exhume_referent_by_obj_id, id, Referent));
+            dependents.extend(by!(
+                exhume_associative_referrer_by_obj_id,
+                id,
+                AssociativeReferrer
+            ));
+            dependents.extend(by!(
+                exhume_associative_referent_by_obj_id,
+                id,
+                AssociativeReferent
+            ));
+            dependents.extend(by!(exhume_supertype_by_obj_id, id, Supertype));
+            dependents.extend(by!(exhume_subtype_by_obj_id, id, Subtype));
+            dependents.extend(by!(exhume_state_by_obj_id, id, State));
+            dependents.extend(by!(exhume_event_by_obj_id, id, Event));
+            dependents.extend(by!(exhume_operation_by_obj_id, id, Operation));
+        }
+        // Everything whose type is the object
+        Instance::Ty(ref id) => {
+            dependents.extend(by!(exhume_attribute_by_ty, id, Attribute));
+            dependents.extend(by!(exhume_parameter_by_ty, id, Parameter));
+            dependents.extend(by!(exhume_operation_by_ret, id, Operation));
+            dependents.extend(by!(exhume_argument_by_ty, id, Argument));
+        }
+        Instance::Referrer(ref id) => dependents.extend(by!(exhume_binary_by_from, id, Binary)),
+        Instance::Referent(ref id) => dependents.extend(by!(exhume_binary_by_to, id, Binary)),
+        Instance::Supertype(ref id) => dependents.extend(by!(exhume_isa_by_supertype, id, Isa)),
+        Instance::AssociativeReferrer(ref id) => {
+            dependents.extend(by!(exhume_associative_by_from, id, Associative))
+        }
+        // An associative is broken without both of its ends.
+        Instance::AssociativeReferent(ref id) => dependents.extend(
+            sarzak
+                .exhume_an_associative_referent_by_referent(id)
+                .into_iter()
+                .map(|an| Instance::Associative(an.read().unwrap().associative)),
+        ),
+        Instance::State(ref id) => {
+            dependents.extend(by!(
+                exhume_acknowledged_event_by_state_id,
+                id,
+                AcknowledgedEvent
+            ));
+            dependents.extend(by!(exhume_transition_by_from, id, Transition));
+            dependents.extend(by!(exhume_next_state_by_to, id, NextState));
+        }
+        Instance::Event(ref id) => {
+            dependents.extend(by!(
+                exhume_acknowledged_event_by_event_id,
+                id,
+                AcknowledgedEvent
+            ));
+            dependents.extend(by!(exhume_transition_by_event, id, Transition));
+            dependents.extend(by!(exhume_parameter_by_event, id, Parameter));
+        }
+        // An isa with no subtypes left means nothing, so it depends on the
+        // last of them.
+        Instance::Subtype(ref id) => {
+            if let Some(subtype) = sarzak.exhume_subtype(id) {
+                let isa = subtype.read().unwrap().isa;
+                if sarzak
+                    .exhume_subtype_by_isa(&isa)
+                    .iter()
+                    .all(|s| doomed.contains(&Instance::Subtype(s.read().unwrap().id)))
+                {
+                    dependents.push(Instance::Isa(isa));
+                }
+            }
+        }
+        Instance::Operation(ref id) => {
+            dependents.extend(by!(exhume_argument_by_operation, id, Argument))
+        }
+        _ => {}
+    }
+
+    dependents
+}
+
+fn exists(instance: Instance, sarzak: &SarzakStore, merlin: &MerlinStore) -> bool {
+    match instance {
+        Instance::AcknowledgedEvent(id) => sarzak.exhume_acknowledged_event(&id).is_some(),
+        Instance::AnAssociativeReferent(id) => sarzak.exhume_an_associative_referent(&id).is_some(),
+        Instance::Argument(id) => sarzak.exhume_argument(&id).is_some(),
+        Instance::Associative(id) => sarzak.exhume_associative(&id).is_some(),
+        Instance::AssociativeReferent(id) => sarzak.exhume_associative_referent(&id).is_some(),
+        Instance::AssociativeReferrer(id) => sarzak.exhume_associative_referrer(&id).is_some(),
+        Instance::Attribute(id) => sarzak.exhume_attribute(&id).is_some(),
+        Instance::Binary(id) => sarzak.exhume_binary(&id).is_some(),
+        Instance::Enumeration(id) => sarzak.exhume_enumeration(&id).is_some(),
+        Instance::Enumerator(id) => sarzak.exhume_enumerator(&id).is_some(),
+        Instance::Event(id) => sarzak.exhume_event(&id).is_some(),
+        Instance::Isa(id) => sarzak.exhume_isa(&id).is_some(),
+        Instance::NextState(id) => sarzak.exhume_next_state(&id).is_some(),
+        Instance::Object(id) => sarzak.exhume_object(&id).is_some(),
+        Instance::Operation(id) => sarzak.exhume_operation(&id).is_some(),
+        Instance::Parameter(id) => sarzak.exhume_parameter(&id).is_some(),
+        Instance::Referent(id) => sarzak.exhume_referent(&id).is_some(),
+        Instance::Referrer(id) => sarzak.exhume_referrer(&id).is_some(),
+        Instance::Relationship(id) => sarzak.exhume_relationship(&id).is_some(),
+        Instance::State(id) => sarzak.exhume_state(&id).is_some(),
+        Instance::Subtype(id) => sarzak.exhume_subtype(&id).is_some(),
+        Instance::Supertype(id) => sarzak.exhume_supertype(&id).is_some(),
+        Instance::Transition(id) => sarzak.exhume_transition(&id).is_some(),
+        // Only an object's or an enumeration's type goes with it, never one
+        // of the primitives.
+        Instance::Ty(id) => matches!(
+            sarzak.exhume_ty(&id).map(|ty| *ty.read().unwrap()),
+            Some(Ty::Object(_)) | Some(Ty::Enumeration(_))
+        ),
+        Instance::Anchor(id) => merlin.exhume_anchor(&id).is_some(),
+        Instance::Bisection(id) => merlin.exhume_bisection(&id).is_some(),
+        Instance::Glyph(id) => merlin.exhume_glyph(&id).is_some(),
+        Instance::Line(id) => merlin.exhume_line(&id).is_some(),
+        Instance::LineSegment(id) => merlin.exhume_line_segment(&id).is_some(),
+        Instance::LineSegmentPoint(id) => merlin.exhume_line_segment_point(&id).is_some(),
+        Instance::Point(id) => merlin.exhume_point(&id).is_some(),
+        Instance::RelationshipName(id) => merlin.exhume_relationship_name(&id).is_some(),
+        Instance::RelationshipPhrase(id) => merlin.exhume_relationship_phrase(&id).is_some(),
+        Instance::XBox(id) => merlin.exhume_x_box(&id).is_some(),
+    }
+}
+
+fn exorcise(instance: Instance, sarzak: &mut SarzakStore, merlin: &mut MerlinStore) {
+    match instance {
+        Instance::AcknowledgedEvent(id) => drop(sarzak.exorcise_acknowledged_event(&id)),
+        Instance::AnAssociativeReferent(id) => drop(sarzak.exorcise_an_associative_referent(&id)),
+        Instance::Argument(id) => drop(sarzak.exorcise_argument(&id)),
+        Instance::Associative(id) => drop(sarzak.exorcise_associative(&id)),
+        Instance::AssociativeReferent(id) => drop(sarzak.exorcise_associative_referent(&id)),
+        Instance::AssociativeReferrer(id) => drop(sarzak.exorcise_associative_referrer(&id)),
+        Instance::Attribute(id) => drop(sarzak.exorcise_attribute(&id)),
+        Instance::Binary(id) => drop(sarzak.exorcise_binary(&id)),
+        Instance::Enumeration(id) => drop(sarzak.exorcise_enumeration(&id)),
+        Instance::Enumerator(id) => drop(sarzak.exorcise_enumerator(&id)),
+        Instance::Event(id) => drop(sarzak.exorcise_event(&id)),
+        Instance::Isa(id) => drop(sarzak.exorcise_isa(&id)),
+        Instance::NextState(id) => drop(sarzak.exorcise_next_state(&id)),
+        Instance::Object(id) => drop(sarzak.exorcise_object(&id)),
+        Instance::Operation(id) => drop(sarzak.exorcise_operation(&id)),
+        Instance::Parameter(id) => drop(sarzak.exorcise_parameter(&id)),
+        Instance::Referent(id) => drop(sarzak.exorcise_referent(&id)),
+        Instance::Referrer(id) => drop(sarzak.exorcise_referrer(&id)),
+        Instance::Relationship(id) => drop(sarzak.exorcise_relationship(&id)),
+        Instance::State(id) => drop(sarzak.exorcise_state(&id)),
+        Instance::Subtype(id) => drop(sarzak.exorcise_subtype(&id)),
+        Instance::Supertype(id) => drop(sarzak.exorcise_supertype(&id)),
+        Instance::Transition(id) => drop(sarzak.exorcise_transition(&id)),
+        Instance::Ty(id) => drop(sarzak.exorcise_ty(&id)),
+        Instance::Anchor(id) => drop(merlin.exorcise_anchor(&id)),
+        Instance::Bisection(id) => drop(merlin.exorcise_bisection(&id)),
+        Instance::Glyph(id) => drop(merlin.exorcise_glyph(&id)),
+        Instance::Line(id) => drop(merlin.exorcise_line(&id)),
+        Instance::LineSegment(id) => drop(merlin.exorcise_line_segment(&id)),
+        Instance::LineSegmentPoint(id) => drop(merlin.exorcise_line_segment_point(&id)),
+        Instance::Point(id) => drop(merlin.exorcise_point(&id)),
+        Instance::RelationshipName(id) => drop(merlin.exorcise_relationship_name(&id)),
+        Instance::RelationshipPhrase(id) => drop(merlin.exorcise_relationship_phrase(&id)),
+        Instance::XBox(id) => drop(merlin.exorcise_x_box(&id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::lint::{lint, Diagnostic};
+
+    fn dangling(domain: &Domain) -> Vec<Diagnostic> {
+        lint(domain.sarzak())
+            .into_iter()
+            .filter(|d| matches!(d, Diagnostic::DanglingReference { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let state = domain.sarzak().exhume_object_id_by_name("State").unwrap();
+        let objects = domain.sarzak().iter_object().count();
+
+        let blocked = domain
+            .delete(Instance::Object(state), DeleteMode::Reject)
+            .unwrap_err();
+        assert_eq!(blocked.instance, Instance::Object(state));
+        assert!(blocked
+            .blockers
+            .iter()
+            .any(|b| matches!(b, Instance::Attribute(_))));
+        assert!(blocked
+            .blockers
+            .iter()
+            .any(|b| matches!(b, Instance::Referrer(_))));
+
+        // Nothing was touched
+        assert_eq!(domain.sarzak().iter_object().count(), objects);
+        assert!(domain.sarzak().exhume_object(&state).is_some());
+    }
+
+    #[test]
+    fn test_cascade() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        assert!(dangling(&domain).is_empty());
+
+        let state = domain.sarzak().exhume_object_id_by_name("State").unwrap();
+        let attributes = domain.sarzak().exhume_attribute_by_obj_id(&state).len();
+
+        let deleted = domain
+            .delete(Instance::Object(state), DeleteMode::Cascade)
+            .unwrap();
+        assert!(deleted.contains(&Instance::Object(state)));
+        assert!(deleted.contains(&Instance::Ty(state)));
+        assert!(deleted.iter().any(|d| matches!(d, Instance::XBox(_))));
+        assert!(deleted.iter().any(|d| matches!(d, Instance::Binary(_))));
+        assert!(
+            deleted
+                .iter()
+                .filter(|d| matches!(d, Instance::Attribute(_)))
+                .count()
+                >= attributes
+        );
+
+        assert!(domain.sarzak().exhume_object(&state).is_none());
+        assert!(domain.sarzak().exhume_object_id_by_name("State").is_none());
+        assert!(dangling(&domain).is_empty(), "{:?}", dangling(&domain));
+        assert!(domain.merlin().iter_x_box().all(|b| domain
+            .sarzak()
+            .exhume_object(&b.read().unwrap().object)
+            .is_some()));
+        assert!(domain.merlin().iter_line().all(|l| domain
+            .sarzak()
+            .exhume_relationship(&l.read().unwrap().relationship)
+            .is_some()));
+
+        // Once it's gone, it's gone
+        assert_eq!(
+            domain.delete(Instance::Object(state), DeleteMode::Cascade),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_enumeration() {
+        use crate::v2::sarzak::types::{Attribute, Enumeration, Enumerator};
+
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let color = Enumeration::new("Color".to_owned(), "".to_owned(), sarzak);
+        Enumerator::new("red".to_owned(), 0, &color, sarzak);
+        Enumerator::new("green".to_owned(), 1, &color, sarzak);
+        let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+        let object = sarzak.exhume_object(&object).unwrap();
+        let ty = Ty::new_enumeration(&color, sarzak);
+        Attribute::new("color".to_owned(), &object, &ty, sarzak);
+        let color = color.read().unwrap().id;
+
+        let blocked = domain
+            .delete(Instance::Enumeration(color), DeleteMode::Reject)
+            .unwrap_err();
+        assert!(matches!(blocked.blockers[..], [Instance::Attribute(_)]));
+
+        let deleted = domain
+            .delete(Instance::Enumeration(color), DeleteMode::Cascade)
+            .unwrap();
+        assert!(deleted.contains(&Instance::Ty(color)));
+        assert_eq!(
+            deleted
+                .iter()
+                .filter(|d| matches!(d, Instance::Enumerator(_)))
+                .count(),
+            2
+        );
+        assert!(domain.sarzak().exhume_ty(&color).is_none());
+        assert!(dangling(&domain).is_empty(), "{:?}", dangling(&domain));
+    }
+
+    #[test]
+    fn test_last_subtype() {
+        use crate::v2::sarzak::types::{Isa, Object, Relationship, Subtype, Supertype};
+
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let new_object = |name: &str, sarzak: &mut SarzakStore| {
+            let object = Object::new("".to_owned(), name.to_owned(), name.to_owned(), sarzak);
+            Ty::new_object(&object, sarzak);
+            object
+        };
+        let shape = new_object("Shape", sarzak);
+        let circle = new_object("Circle", sarzak);
+        let square = new_object("Square", sarzak);
+        let supertype = Supertype::new(&shape, sarzak);
+        let isa = Isa::new(99, &supertype, sarzak);
+        Relationship::new_isa(&isa, sarzak);
+        Subtype::new(&isa, &circle, sarzak);
+        Subtype::new(&isa, &square, sarzak);
+        let isa = isa.read().unwrap().id;
+
+        let circle = circle.read().unwrap().id;
+        let deleted = domain
+            .delete(Instance::Object(circle), DeleteMode::Cascade)
+            .unwrap();
+        assert!(!deleted.contains(&Instance::Isa(isa)));
+        assert!(domain.sarzak().exhume_isa(&isa).is_some());
+
+        let square = square.read().unwrap().id;
+        let deleted = domain
+            .delete(Instance::Object(square), DeleteMode::Cascade)
+            .unwrap();
+        assert!(deleted.contains(&Instance::Isa(isa)));
+        assert!(deleted.contains(&Instance::Relationship(isa)));
+        assert!(domain.sarzak().exhume_isa(&isa).is_none());
+        assert!(domain
+            .sarzak()
+            .exhume_supertype_by_obj_id(&shape.read().unwrap().id)
+            .is_empty());
+        assert!(!lint(domain.sarzak())
+            .iter()
+            .any(|d| matches!(d, Diagnostic::EmptyIsa { .. })));
+        assert!(dangling(&domain).is_empty(), "{:?}", dangling(&domain));
+    }
+}
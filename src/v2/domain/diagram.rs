@@ -0,0 +1,451 @@
+//! Class Diagram Export
+//!
+//! Generate class diagrams from the sarzak store, as either Mermaid or
+//! Graphviz DOT text. These are meant for docs and code reviews, where one
+//! wants a picture of the model, but doesn't care where the boxes go. If you
+//! care where the boxes go, you want [`Domain::to_svg`].
+//!
+//! Objects, and relationships, are sorted by name and number respectively, so
+//! that the output doesn't change unless the model does.
+use std::fmt::Write;
+
+use heck::ToUpperCamelCase;
+use uuid::Uuid;
+
+use crate::v2::{
+    domain::Domain,
+    sarzak::{
+        store::ObjectStore as SarzakStore,
+        types::{Relationship, Ty, CONDITIONAL, ONE},
+    },
+};
+
+/// An object, as it appears on a diagram
+struct Class {
+    id: Uuid,
+    name: String,
+    /// Attribute name and type name pairs
+    attributes: Vec<(String, String)>,
+}
+
+/// One end of a relationship
+struct End {
+    obj_id: Uuid,
+    /// UML multiplicity: "1", "0..1", "0..*", "1..*"
+    multiplicity: &'static str,
+}
+
+enum Edge {
+    Binary {
+        number: i64,
+        from: End,
+        to: End,
+    },
+    Isa {
+        number: i64,
+        supertype: Uuid,
+        subtypes: Vec<Uuid>,
+    },
+    Associative {
+        number: i64,
+        from: Uuid,
+        one: End,
+        other: End,
+    },
+}
+
+impl Edge {
+    fn number(&self) -> i64 {
+        match self {
+            Self::Binary { number, .. } => *number,
+            Self::Isa { number, .. } => *number,
+            Self::Associative { number, .. } => *number,
+        }
+    }
+}
+
+/// The parts of the model that make it onto the diagram
+struct Diagram {
+    classes: Vec<Class>,
+    edges: Vec<Edge>,
+}
+
+impl Diagram {
+    fn new(sarzak: &SarzakStore) -> Self {
+        let mut classes: Vec<Class> = sarzak
+            .iter_object()
+            .map(|obj| {
+                let obj = obj.read().unwrap();
+                let mut attributes: Vec<(String, String)> = sarzak
+                    .iter_attribute()
+                    .filter(|attr| attr.read().unwrap().obj_id == obj.id)
+                    .map(|attr| {
+                        let attr = attr.read().unwrap();
+                        (attr.name.clone(), type_name(&attr.ty, sarzak))
+                    })
+                    .collect();
+                attributes.sort();
+
+                Class {
+                    id: obj.id,
+                    name: obj.name.to_upper_camel_case(),
+                    attributes,
+                }
+            })
+            .collect();
+        classes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut edges: Vec<Edge> = sarzak
+            .iter_relationship()
+            .filter_map(|rel| edge(&rel.read().unwrap(), sarzak))
+            .collect();
+        edges.sort_by_key(|edge| edge.number());
+
+        Self { classes, edges }
+    }
+
+    fn name(&self, id: &Uuid) -> &str {
+        self.classes
+            .iter()
+            .find(|class| &class.id == id)
+            .map(|class| class.name.as_str())
+            .unwrap_or("Unknown")
+    }
+}
+
+pub(super) fn type_name(ty: &Uuid, sarzak: &SarzakStore) -> String {
+    match sarzak.exhume_ty(ty).map(|ty| *ty.read().unwrap()) {
+        Some(Ty::Boolean(_)) => "Boolean".to_owned(),
+        Some(Ty::Float(_)) => "Float".to_owned(),
+        Some(Ty::Integer(_)) => "Integer".to_owned(),
+        Some(Ty::ZString(_)) => "String".to_owned(),
+        Some(Ty::ZUuid(_)) => "Uuid".to_owned(),
+        Some(Ty::Object(ref id)) => sarzak
+            .exhume_object(id)
+            .map(|obj| obj.read().unwrap().name.to_upper_camel_case())
+            .unwrap_or_else(|| "Unknown".to_owned()),
+        Some(Ty::Enumeration(ref id)) => sarzak
+            .exhume_enumeration(id)
+            .map(|e| e.read().unwrap().name.to_upper_camel_case())
+            .unwrap_or_else(|| "Unknown".to_owned()),
+        Some(Ty::External(ref id)) => sarzak
+            .exhume_external(id)
+            .map(|ext| ext.read().unwrap().name.clone())
+            .unwrap_or_else(|| "Unknown".to_owned()),
+        None => "Unknown".to_owned(),
+    }
+}
+
+/// UML multiplicity of one end of a relationship
+///
+/// The cardinality comes from this end, but the conditionality comes from the
+/// far end. "Attribute lives in an Object" being unconditional means that the
+/// Object end is "1", not the Attribute end.
+fn multiplicity(cardinality: &Uuid, conditionality: &Uuid) -> &'static str {
+    match (cardinality == &ONE, conditionality == &CONDITIONAL) {
+        (true, false) => "1",
+        (true, true) => "0..1",
+        (false, true) => "0..*",
+        (false, false) => "1..*",
+    }
+}
+
+/// Build an edge from a relationship
+///
+/// Anything that's missing from the store is skipped. Lint will tell you about
+/// it.
+fn edge(rel: &Relationship, sarzak: &SarzakStore) -> Option<Edge> {
+    match rel {
+        Relationship::Binary(id) => {
+            let binary = sarzak.exhume_binary(id)?;
+            let binary = binary.read().unwrap();
+            let referrer = sarzak.exhume_referrer(&binary.from)?;
+            let referrer = referrer.read().unwrap();
+            let referent = sarzak.exhume_referent(&binary.to)?;
+            let referent = referent.read().unwrap();
+
+            Some(Edge::Binary {
+                number: binary.number,
+                from: End {
+                    obj_id: referrer.obj_id,
+                    multiplicity: multiplicity(&referrer.cardinality, &referent.conditionality),
+                },
+                to: End {
+                    obj_id: referent.obj_id,
+                    multiplicity: multiplicity(&referent.cardinality, &referrer.conditionality),
+                },
+            })
+        }
+        Relationship::Isa(id) => {
+            let isa = sarzak.exhume_isa(id)?;
+            let isa = isa.read().unwrap();
+            let supertype = sarzak.exhume_supertype(&isa.supertype)?;
+            let supertype = supertype.read().unwrap().obj_id;
+            let mut subtypes: Vec<Uuid> = sarzak
+                .iter_subtype()
+                .filter(|sub| sub.read().unwrap().isa == isa.id)
+                .map(|sub| sub.read().unwrap().obj_id)
+                .collect();
+            subtypes.sort();
+
+            Some(Edge::Isa {
+                number: isa.number,
+                supertype,
+                subtypes,
+            })
+        }
+        Relationship::Associative(id) => {
+            let assoc = sarzak.exhume_associative(id)?;
+            let assoc = assoc.read().unwrap();
+            let referrer = sarzak.exhume_associative_referrer(&assoc.from)?;
+            let from = referrer.read().unwrap().obj_id;
+
+            let mut ends: Vec<_> = sarzak
+                .iter_an_associative_referent()
+                .filter(|an| an.read().unwrap().associative == assoc.id)
+                .filter_map(|an| sarzak.exhume_associative_referent(&an.read().unwrap().referent))
+                .map(|referent| referent.read().unwrap().clone())
+                .collect();
+            if ends.len() != 2 {
+                return None;
+            }
+            ends.sort_by_key(|end| end.obj_id);
+            let (a, b) = (&ends[0], &ends[1]);
+            let one = End {
+                obj_id: a.obj_id,
+                multiplicity: multiplicity(&a.cardinality, &b.conditionality),
+            };
+            let other = End {
+                obj_id: b.obj_id,
+                multiplicity: multiplicity(&b.cardinality, &a.conditionality),
+            };
+
+            Some(Edge::Associative {
+                number: assoc.number,
+                from,
+                one,
+                other,
+            })
+        }
+    }
+}
+
+impl Domain {
+    /// Generate a Mermaid class diagram
+    ///
+    /// Mermaid doesn't have association classes, so the associative object is
+    /// connected to both ends of the relationship with a dotted line.
+    pub fn to_mermaid(&self) -> String {
+        let diagram = Diagram::new(&self.sarzak);
+        let mut out = String::new();
+
+        writeln!(out, "classDiagram").unwrap();
+        for class in &diagram.classes {
+            if class.attributes.is_empty() {
+                writeln!(out, "    class {}", class.name).unwrap();
+            } else {
+                writeln!(out, "    class {} {{", class.name).unwrap();
+                for (name, ty) in &class.attributes {
+                    writeln!(out, "        -{ty} {name}").unwrap();
+                }
+                writeln!(out, "    }}").unwrap();
+            }
+        }
+
+        if !diagram.edges.is_empty() {
+            writeln!(out).unwrap();
+        }
+        for edge in &diagram.edges {
+            match edge {
+                Edge::Binary { number, from, to } => writeln!(
+                    out,
+                    "    {} \"{}\" -- \"{}\" {}: R{number}",
+                    diagram.name(&from.obj_id),
+                    from.multiplicity,
+                    to.multiplicity,
+                    diagram.name(&to.obj_id),
+                )
+                .unwrap(),
+                Edge::Isa {
+                    number,
+                    supertype,
+                    subtypes,
+                } => {
+                    for sub in subtypes {
+                        writeln!(
+                            out,
+                            "    {} <|-- {}: R{number}",
+                            diagram.name(supertype),
+                            diagram.name(sub)
+                        )
+                        .unwrap();
+                    }
+                }
+                Edge::Associative {
+                    number,
+                    from,
+                    one,
+                    other,
+                } => {
+                    writeln!(
+                        out,
+                        "    {} \"{}\" -- \"{}\" {}: R{number}",
+                        diagram.name(&one.obj_id),
+                        one.multiplicity,
+                        other.multiplicity,
+                        diagram.name(&other.obj_id),
+                    )
+                    .unwrap();
+                    for end in [one, other] {
+                        writeln!(
+                            out,
+                            "    {} .. {}: R{number}",
+                            diagram.name(from),
+                            diagram.name(&end.obj_id)
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Generate a Graphviz DOT class diagram
+    ///
+    /// Objects are drawn as record nodes. An associative relationship gets a
+    /// point node in the middle of the line, and the associative object is
+    /// attached to it with a dashed line.
+    pub fn to_dot(&self) -> String {
+        let diagram = Diagram::new(&self.sarzak);
+        let mut out = String::new();
+
+        writeln!(out, "digraph \"{}\" {{", escape(self.name())).unwrap();
+        writeln!(out, "    node [shape=record];").unwrap();
+        writeln!(out, "    edge [arrowhead=none];").unwrap();
+        writeln!(out).unwrap();
+
+        for class in &diagram.classes {
+            let attributes: String = class
+                .attributes
+                .iter()
+                .map(|(name, ty)| format!("{}: {}\\l", escape(name), escape(ty)))
+                .collect();
+            writeln!(
+                out,
+                "    \"{}\" [label=\"{{{}|{}}}\"];",
+                class.name,
+                escape(&class.name),
+                attributes
+            )
+            .unwrap();
+        }
+
+        if !diagram.edges.is_empty() {
+            writeln!(out).unwrap();
+        }
+        for edge in &diagram.edges {
+            match edge {
+                Edge::Binary { number, from, to } => writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\" [label=\"R{number}\", taillabel=\"{}\", headlabel=\"{}\"];",
+                    diagram.name(&from.obj_id),
+                    diagram.name(&to.obj_id),
+                    from.multiplicity,
+                    to.multiplicity,
+                )
+                .unwrap(),
+                Edge::Isa {
+                    number,
+                    supertype,
+                    subtypes,
+                } => {
+                    for sub in subtypes {
+                        writeln!(
+                            out,
+                            "    \"{}\" -> \"{}\" [label=\"R{number}\", arrowhead=empty];",
+                            diagram.name(sub),
+                            diagram.name(supertype),
+                        )
+                        .unwrap();
+                    }
+                }
+                Edge::Associative {
+                    number,
+                    from,
+                    one,
+                    other,
+                } => {
+                    writeln!(out, "    \"R{number}\" [shape=point, label=\"\"];").unwrap();
+                    writeln!(
+                        out,
+                        "    \"{}\" -> \"R{number}\" [label=\"R{number}\", taillabel=\"{}\"];",
+                        diagram.name(&one.obj_id),
+                        one.multiplicity,
+                    )
+                    .unwrap();
+                    writeln!(
+                        out,
+                        "    \"R{number}\" -> \"{}\" [headlabel=\"{}\"];",
+                        diagram.name(&other.obj_id),
+                        other.multiplicity,
+                    )
+                    .unwrap();
+                    writeln!(
+                        out,
+                        "    \"{}\" -> \"R{number}\" [style=dashed];",
+                        diagram.name(from),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        writeln!(out, "}}").unwrap();
+
+        out
+    }
+}
+
+/// Escape the characters that mean something in a DOT record label
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '"' | '{' | '}' | '|' | '<' | '>' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mermaid() {
+        let domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let mermaid = domain.to_mermaid();
+
+        assert!(mermaid.starts_with("classDiagram\n"));
+        assert!(mermaid.contains("    class Object {\n"));
+        assert!(mermaid.contains("        -String key letters\n"));
+        assert!(mermaid.contains("    Type <|-- Object: R3\n"));
+        assert!(mermaid.contains("    Attribute \"0..*\" -- \"1\" Object: R1\n"));
+        assert_eq!(mermaid, domain.to_mermaid());
+    }
+
+    #[test]
+    fn test_dot() {
+        let domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let dot = domain.to_dot();
+
+        assert!(dot.starts_with("digraph \"sarzak\" {\n"));
+        assert!(dot.contains("    \"Object\" -> \"Type\" [label=\"R3\", arrowhead=empty];\n"));
+        assert!(dot.contains("[shape=point, label=\"\"];"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
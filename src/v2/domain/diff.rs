@@ -0,0 +1,815 @@
+//! Domain Diff
+//!
+//! Compare two versions of a domain, and say what changed in terms of the
+//! model, rather than in terms of JSON files.
+//!
+//! Elements are matched by id. Anything left over is matched by name, so that
+//! an object that was deleted and recreated with the same name shows up as
+//! modified, rather than as a removal and an addition.
+//!
+//! References are compared by name. If an attribute's type changes from one
+//! object to another, the diff says so by name, and if an object is recreated
+//! with a new id, its attributes don't all show up as modified.
+use std::fmt;
+
+use rustc_hash::FxHashMap as HashMap;
+use uuid::Uuid;
+
+use crate::v2::{
+    domain::{diagram::type_name, Domain},
+    sarzak::{
+        store::ObjectStore as SarzakStore,
+        types::{Relationship, TransitionEnum, CONDITIONAL, ONE},
+    },
+};
+
+/// The kinds of model element that we compare
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ElementKind {
+    Object,
+    Attribute,
+    Relationship,
+    Subtype,
+    State,
+    Event,
+    Transition,
+    Parameter,
+    Operation,
+    Argument,
+    Enumeration,
+    Enumerator,
+}
+
+impl fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Object => "object",
+            Self::Attribute => "attribute",
+            Self::Relationship => "relationship",
+            Self::Subtype => "subtype",
+            Self::State => "state",
+            Self::Event => "event",
+            Self::Transition => "transition",
+            Self::Parameter => "parameter",
+            Self::Operation => "operation",
+            Self::Argument => "argument",
+            Self::Enumeration => "enumeration",
+            Self::Enumerator => "enumerator",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A field that has a different value in the new domain
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// A single difference between two domains
+///
+/// `name` is a readable name for the element, e.g. `Object.name` for an
+/// attribute, or `R12` for a relationship.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Added {
+        kind: ElementKind,
+        id: Uuid,
+        name: String,
+    },
+    Removed {
+        kind: ElementKind,
+        id: Uuid,
+        name: String,
+    },
+    /// The element exists in both domains, but is different.
+    ///
+    /// The ids are the same, unless the element was matched by name.
+    Modified {
+        kind: ElementKind,
+        old_id: Uuid,
+        new_id: Uuid,
+        name: String,
+        fields: Vec<FieldChange>,
+    },
+}
+
+impl Change {
+    pub fn kind(&self) -> ElementKind {
+        match self {
+            Self::Added { kind, .. } => *kind,
+            Self::Removed { kind, .. } => *kind,
+            Self::Modified { kind, .. } => *kind,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Added { name, .. } => name,
+            Self::Removed { name, .. } => name,
+            Self::Modified { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { kind, id, name } => write!(f, "+ {kind} {name} ({id})"),
+            Self::Removed { kind, id, name } => write!(f, "- {kind} {name} ({id})"),
+            Self::Modified {
+                kind,
+                old_id,
+                new_id,
+                name,
+                fields,
+            } => {
+                if old_id == new_id {
+                    write!(f, "~ {kind} {name} ({new_id})")?;
+                } else {
+                    write!(f, "~ {kind} {name} ({old_id} -> {new_id})")?;
+                }
+                for change in fields {
+                    write!(
+                        f,
+                        "\n    {}: {:?} -> {:?}",
+                        change.field, change.old, change.new
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The differences between two domains
+///
+/// Changes are grouped by kind, and sorted by name within each kind.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DomainDiff {
+    pub changes: Vec<Change>,
+}
+
+impl DomainDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for DomainDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Domain {
+    /// Compare this domain with a newer version of itself
+    ///
+    /// The result lists what would need to be done to `self` to get `new`.
+    pub fn diff(&self, new: &Domain) -> DomainDiff {
+        let old = elements(&self.sarzak);
+        let new = elements(&new.sarzak);
+
+        let mut changes = Vec::new();
+        for kind in [
+            ElementKind::Object,
+            ElementKind::Attribute,
+            ElementKind::Relationship,
+            ElementKind::Subtype,
+            ElementKind::State,
+            ElementKind::Event,
+            ElementKind::Transition,
+            ElementKind::Parameter,
+            ElementKind::Operation,
+            ElementKind::Argument,
+            ElementKind::Enumeration,
+            ElementKind::Enumerator,
+        ] {
+            let select = |elements: &[Element]| -> Vec<Element> {
+                elements
+                    .iter()
+                    .filter(|e| e.kind == kind)
+                    .cloned()
+                    .collect()
+            };
+            diff_kind(kind, select(&old), select(&new), &mut changes);
+        }
+
+        DomainDiff { changes }
+    }
+}
+
+/// A model element flattened for comparison
+#[derive(Clone, Debug)]
+struct Element {
+    kind: ElementKind,
+    id: Uuid,
+    name: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+fn diff_kind(kind: ElementKind, old: Vec<Element>, new: Vec<Element>, changes: &mut Vec<Change>) {
+    let mut new: HashMap<Uuid, Element> = new.into_iter().map(|e| (e.id, e)).collect();
+    let mut unmatched = Vec::new();
+    let mut found = Vec::new();
+
+    // First by id
+    for old in old {
+        match new.remove(&old.id) {
+            Some(new) => found.push((old, new)),
+            None => unmatched.push(old),
+        }
+    }
+
+    // Then by name
+    for old in unmatched {
+        let by_name = new
+            .values()
+            .filter(|e| e.name == old.name)
+            .map(|e| e.id)
+            .min();
+        match by_name {
+            Some(id) => found.push((old, new.remove(&id).unwrap())),
+            None => changes.push(Change::Removed {
+                kind,
+                id: old.id,
+                name: old.name,
+            }),
+        }
+    }
+
+    for (old, new) in found {
+        let fields: Vec<FieldChange> = old
+            .fields
+            .iter()
+            .zip(new.fields.iter())
+            .filter(|((_, a), (_, b))| a != b)
+            .map(|((field, a), (_, b))| FieldChange {
+                field,
+                old: a.clone(),
+                new: b.clone(),
+            })
+            .collect();
+
+        if old.id != new.id || !fields.is_empty() || old.fields.len() != new.fields.len() {
+            changes.push(Change::Modified {
+                kind,
+                old_id: old.id,
+                new_id: new.id,
+                name: new.name,
+                fields: if old.fields.len() == new.fields.len() {
+                    fields
+                } else {
+                    // The relationship changed shape, e.g. from binary to
+                    // associative. Just report the whole thing.
+                    vec![FieldChange {
+                        field: "kind",
+                        old: describe(&old.fields),
+                        new: describe(&new.fields),
+                    }]
+                },
+            });
+        }
+    }
+
+    changes.extend(new.into_values().map(|e| Change::Added {
+        kind,
+        id: e.id,
+        name: e.name,
+    }));
+
+    let first = changes
+        .iter()
+        .position(|c| c.kind() == kind)
+        .unwrap_or(changes.len());
+    changes[first..].sort_by(|a, b| (a.name(), a.to_string()).cmp(&(b.name(), b.to_string())));
+}
+
+fn describe(fields: &[(&'static str, String)]) -> String {
+    fields
+        .iter()
+        .map(|(field, value)| format!("{field}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn elements(sarzak: &SarzakStore) -> Vec<Element> {
+    let object_name = |id: &Uuid| {
+        sarzak
+            .exhume_object(id)
+            .map(|obj| obj.read().unwrap().name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let cardinality = |id: &Uuid| if id == &ONE { "One" } else { "Many" }.to_owned();
+    let conditionality = |id: &Uuid| {
+        if id == &CONDITIONAL {
+            "Conditional"
+        } else {
+            "Unconditional"
+        }
+        .to_owned()
+    };
+
+    let state_name = |id: &Uuid| {
+        sarzak
+            .exhume_state(id)
+            .map(|state| state.read().unwrap().name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let event_name = |id: &Uuid| {
+        sarzak
+            .exhume_event(id)
+            .map(|event| {
+                let event = event.read().unwrap();
+                format!("{}.{}", object_name(&event.obj_id), event.name)
+            })
+            .unwrap_or_else(|| id.to_string())
+    };
+    let operation_name = |id: &Uuid| {
+        sarzak
+            .exhume_operation(id)
+            .map(|op| {
+                let op = op.read().unwrap();
+                format!("{}.{}", object_name(&op.obj_id), op.name)
+            })
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let mut elements = Vec::new();
+
+    for obj in sarzak.iter_object() {
+        let obj = obj.read().unwrap();
+        elements.push(Element {
+            kind: ElementKind::Object,
+            id: obj.id,
+            name: obj.name.clone(),
+            fields: vec![
+                ("name", obj.name.clone()),
+                ("key_letters", obj.key_letters.clone()),
+                ("description", obj.description.clone()),
+            ],
+        });
+    }
+
+    for attr in sarzak.iter_attribute() {
+        let attr = attr.read().unwrap();
+        let object = object_name(&attr.obj_id);
+        elements.push(Element {
+            kind: ElementKind::Attribute,
+            id: attr.id,
+            name: format!("{object}.{}", attr.name),
+            fields: vec![
+                ("name", attr.name.clone()),
+                ("object", object),
+                ("type", type_name(&attr.ty, sarzak)),
+            ],
+        });
+    }
+
+    let numbers: HashMap<Uuid, i64> = sarzak
+        .iter_binary()
+        .map(|b| (b.read().unwrap().id, b.read().unwrap().number))
+        .chain(
+            sarzak
+                .iter_isa()
+                .map(|i| (i.read().unwrap().id, i.read().unwrap().number)),
+        )
+        .chain(
+            sarzak
+                .iter_associative()
+                .map(|a| (a.read().unwrap().id, a.read().unwrap().number)),
+        )
+        .collect();
+
+    for rel in sarzak.iter_relationship() {
+        let rel = *rel.read().unwrap();
+        let number = numbers.get(&rel.id()).copied().unwrap_or_default();
+        let fields = match rel {
+            Relationship::Binary(ref id) => {
+                let Some(binary) = sarzak.exhume_binary(id) else {
+                    continue;
+                };
+                let binary = binary.read().unwrap();
+                let Some(from) = sarzak.exhume_referrer(&binary.from) else {
+                    continue;
+                };
+                let from = from.read().unwrap();
+                let Some(to) = sarzak.exhume_referent(&binary.to) else {
+                    continue;
+                };
+                let to = to.read().unwrap();
+
+                vec![
+                    ("kind", "Binary".to_owned()),
+                    ("from", object_name(&from.obj_id)),
+                    ("from.description", from.description.clone()),
+                    ("from.cardinality", cardinality(&from.cardinality)),
+                    ("from.conditionality", conditionality(&from.conditionality)),
+                    ("referential_attribute", from.referential_attribute.clone()),
+                    ("to", object_name(&to.obj_id)),
+                    ("to.description", to.description.clone()),
+                    ("to.cardinality", cardinality(&to.cardinality)),
+                    ("to.conditionality", conditionality(&to.conditionality)),
+                ]
+            }
+            Relationship::Isa(ref id) => {
+                let Some(isa) = sarzak.exhume_isa(id) else {
+                    continue;
+                };
+                let supertype = sarzak
+                    .exhume_supertype(&isa.read().unwrap().supertype)
+                    .map(|s| object_name(&s.read().unwrap().obj_id))
+                    .unwrap_or_default();
+
+                vec![("kind", "Isa".to_owned()), ("supertype", supertype)]
+            }
+            Relationship::Associative(ref id) => {
+                let Some(assoc) = sarzak.exhume_associative(id) else {
+                    continue;
+                };
+                let assoc = assoc.read().unwrap();
+                let Some(from) = sarzak.exhume_associative_referrer(&assoc.from) else {
+                    continue;
+                };
+                let from = from.read().unwrap();
+
+                // There are two ends, and no way to tell them apart but for
+                // the objects that they point at.
+                let mut ends: Vec<String> = sarzak
+                    .iter_an_associative_referent()
+                    .filter(|an| an.read().unwrap().associative == assoc.id)
+                    .filter_map(|an| {
+                        let an = an.read().unwrap();
+                        let referent = sarzak.exhume_associative_referent(&an.referent)?;
+                        let referent = referent.read().unwrap();
+                        Some(format!(
+                            "{} ({}, {}, {}, via {})",
+                            object_name(&referent.obj_id),
+                            referent.description,
+                            cardinality(&referent.cardinality),
+                            conditionality(&referent.conditionality),
+                            an.referential_attribute
+                        ))
+                    })
+                    .collect();
+                ends.sort();
+
+                vec![
+                    ("kind", "Associative".to_owned()),
+                    ("from", object_name(&from.obj_id)),
+                    ("from.cardinality", cardinality(&from.cardinality)),
+                    ("ends", ends.join("; ")),
+                ]
+            }
+        };
+
+        elements.push(Element {
+            kind: ElementKind::Relationship,
+            id: rel.id(),
+            name: format!("R{number}"),
+            fields,
+        });
+    }
+
+    for sub in sarzak.iter_subtype() {
+        let sub = sub.read().unwrap();
+        let number = numbers.get(&sub.isa).copied().unwrap_or_default();
+        let object = object_name(&sub.obj_id);
+        elements.push(Element {
+            kind: ElementKind::Subtype,
+            id: sub.id,
+            name: format!("R{number}.{object}"),
+            fields: vec![("isa", format!("R{number}")), ("object", object)],
+        });
+    }
+
+    for state in sarzak.iter_state() {
+        let state = state.read().unwrap();
+        let object = object_name(&state.obj_id);
+        elements.push(Element {
+            kind: ElementKind::State,
+            id: state.id,
+            name: format!("{object}.{}", state.name),
+            fields: vec![("name", state.name.clone()), ("object", object)],
+        });
+    }
+
+    for event in sarzak.iter_event() {
+        let event = event.read().unwrap();
+        let object = object_name(&event.obj_id);
+        elements.push(Element {
+            kind: ElementKind::Event,
+            id: event.id,
+            name: format!("{object}.{}", event.name),
+            fields: vec![("name", event.name.clone()), ("object", object)],
+        });
+    }
+
+    for transition in sarzak.iter_transition() {
+        let transition = transition.read().unwrap();
+        let from = state_name(&transition.from);
+        let event = event_name(&transition.event);
+        let to = match transition.subtype {
+            TransitionEnum::NextState(ref id) => sarzak
+                .exhume_next_state(id)
+                .map(|next| state_name(&next.read().unwrap().to))
+                .unwrap_or_else(|| id.to_string()),
+            TransitionEnum::Ignored(_) => "ignored".to_owned(),
+            TransitionEnum::CantHappen(_) => "can't happen".to_owned(),
+        };
+        elements.push(Element {
+            kind: ElementKind::Transition,
+            id: transition.id,
+            name: format!("{event} in {from}"),
+            fields: vec![("from", from), ("event", event), ("to", to)],
+        });
+    }
+
+    for param in sarzak.iter_parameter() {
+        let param = param.read().unwrap();
+        let event = event_name(&param.event);
+        elements.push(Element {
+            kind: ElementKind::Parameter,
+            id: param.id,
+            name: format!("{event}.{}", param.name),
+            fields: vec![
+                ("name", param.name.clone()),
+                ("event", event),
+                ("position", param.position.to_string()),
+                ("type", type_name(&param.ty, sarzak)),
+            ],
+        });
+    }
+
+    for op in sarzak.iter_operation() {
+        let op = op.read().unwrap();
+        let object = object_name(&op.obj_id);
+        elements.push(Element {
+            kind: ElementKind::Operation,
+            id: op.id,
+            name: format!("{object}.{}", op.name),
+            fields: vec![
+                ("name", op.name.clone()),
+                ("object", object),
+                ("description", op.description.clone()),
+                ("class_based", op.class_based.to_string()),
+                (
+                    "body",
+                    if op.has_body {
+                        op.body.clone()
+                    } else {
+                        String::new()
+                    },
+                ),
+                (
+                    "returns",
+                    op.ret
+                        .map(|ret| type_name(&ret, sarzak))
+                        .unwrap_or_default(),
+                ),
+            ],
+        });
+    }
+
+    for arg in sarzak.iter_argument() {
+        let arg = arg.read().unwrap();
+        let operation = operation_name(&arg.operation);
+        elements.push(Element {
+            kind: ElementKind::Argument,
+            id: arg.id,
+            name: format!("{operation}.{}", arg.name),
+            fields: vec![
+                ("name", arg.name.clone()),
+                ("operation", operation),
+                ("position", arg.position.to_string()),
+                ("type", type_name(&arg.ty, sarzak)),
+            ],
+        });
+    }
+
+    for enumeration in sarzak.iter_enumeration() {
+        let enumeration = enumeration.read().unwrap();
+        elements.push(Element {
+            kind: ElementKind::Enumeration,
+            id: enumeration.id,
+            name: enumeration.name.clone(),
+            fields: vec![
+                ("name", enumeration.name.clone()),
+                ("description", enumeration.description.clone()),
+            ],
+        });
+    }
+
+    for enumerator in sarzak.iter_enumerator() {
+        let enumerator = enumerator.read().unwrap();
+        let enumeration = sarzak
+            .exhume_enumeration(&enumerator.enumeration)
+            .map(|e| e.read().unwrap().name.clone())
+            .unwrap_or_else(|| enumerator.enumeration.to_string());
+        elements.push(Element {
+            kind: ElementKind::Enumerator,
+            id: enumerator.id,
+            name: format!("{enumeration}.{}", enumerator.name),
+            fields: vec![
+                ("name", enumerator.name.clone()),
+                ("enumeration", enumeration),
+                ("position", enumerator.position.to_string()),
+            ],
+        });
+    }
+
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::v2::{
+        id::IdStrategy,
+        sarzak::{
+            types::{
+                Argument, Enumeration, Enumerator, Event, NextState, Object, Operation, Parameter,
+                State, Transition, Ty,
+            },
+            UUID_NS,
+        },
+    };
+
+    #[test]
+    fn test_no_changes() {
+        let a = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let b = Domain::from_cuckoo("models/sarzak.json").unwrap();
+
+        let diff = a.diff(&b);
+        assert!(diff.is_empty(), "{diff}");
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let mut new = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = new.sarzak_mut();
+
+        // Modify one object, and delete and recreate another with a new id.
+        let isa = sarzak.exhume_object_id_by_name("Isa").unwrap();
+        let isa = sarzak.exhume_object(&isa).unwrap();
+        isa.write().unwrap().key_letters = "ISA".to_owned();
+
+        let state = sarzak.exhume_object_id_by_name("State").unwrap();
+        let state = sarzak.exorcise_object(&state).unwrap();
+        let mut recreated = state.read().unwrap().clone();
+        recreated.id = Uuid::new_v4();
+        sarzak.inter_object(Arc::new(RwLock::new(recreated.clone())));
+
+        let added = Object::new(
+            "A new object".to_owned(),
+            "NEW".to_owned(),
+            "New".to_owned(),
+            sarzak,
+        );
+        let added = added.read().unwrap().id;
+        State::new(
+            "Idle".to_owned(),
+            &sarzak.exhume_object(&added).unwrap(),
+            sarzak,
+        );
+
+        let diff = old.diff(&new);
+        let rendered = diff.to_string();
+
+        assert!(diff.changes.contains(&Change::Added {
+            kind: ElementKind::Object,
+            id: added,
+            name: "New".to_owned(),
+        }));
+        assert!(rendered.contains("+ state New.Idle"));
+        assert!(rendered.contains("    key_letters: \"R_ISA\" -> \"ISA\""));
+
+        let state = state.read().unwrap();
+        assert!(diff
+            .changes
+            .iter()
+            .any(|c| matches!(c, Change::Modified { name, old_id, new_id, fields, .. }
+                if name == "State" && old_id == &state.id && new_id == &recreated.id && fields.is_empty())));
+        // The recreated object kept its name, so its attributes are not
+        // changed.
+        assert!(!rendered.contains("attribute State."), "{rendered}");
+    }
+
+    #[test]
+    fn test_state_machine() {
+        // Idle -start-> Busy, and start is ignored in Busy
+        let machine = || {
+            let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+            let sarzak = domain.sarzak_mut();
+            sarzak.set_id_strategy(IdStrategy::Derived(UUID_NS));
+            let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+            let object = sarzak.exhume_object(&object).unwrap();
+            let idle = State::new("Idle".to_owned(), &object, sarzak);
+            let busy = State::new("Busy".to_owned(), &object, sarzak);
+            let start = Event::new("start".to_owned(), &object, sarzak);
+            let enter =
+                Transition::new_next_state(&NextState::new(&busy, sarzak), &start, &idle, sarzak);
+            Transition::new_ignored(&start, &busy, sarzak);
+            let enter = enter.read().unwrap().id;
+            (domain, idle, start, enter)
+        };
+
+        let (old, ..) = machine();
+        let (mut new, idle, start, enter) = machine();
+        let sarzak = new.sarzak_mut();
+        sarzak.exorcise_transition(&enter);
+        Transition::new_cant_happen(&start, &idle, sarzak);
+        let ty = Ty::new_integer(sarzak);
+        Parameter::new("count".to_owned(), 0, &start, &ty, sarzak);
+
+        let rendered = old.diff(&new).to_string();
+        assert!(
+            rendered.contains("~ transition Object.start in Idle"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("    to: \"Busy\" -> \"can't happen\""));
+        assert!(
+            !rendered.contains("transition Object.start in Busy"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("+ parameter Object.start.count"));
+    }
+
+    #[test]
+    fn test_operations() {
+        let operation = || {
+            let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+            let sarzak = domain.sarzak_mut();
+            sarzak.set_id_strategy(IdStrategy::Derived(UUID_NS));
+            let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+            let object = sarzak.exhume_object(&object).unwrap();
+            let rename = Operation::new(
+                "rename".to_owned(),
+                "".to_owned(),
+                false,
+                "".to_owned(),
+                false,
+                &object,
+                None,
+                sarzak,
+            );
+            let string = Ty::new_z_string(sarzak);
+            Argument::new("name".to_owned(), 0, &rename, &string, sarzak);
+            (domain, rename)
+        };
+
+        let (old, _) = operation();
+        let (mut new, rename) = operation();
+        let sarzak = new.sarzak_mut();
+        {
+            let mut rename = rename.write().unwrap();
+            rename.body = "rename.dwarf".to_owned();
+            rename.has_body = true;
+        }
+        let boolean = Ty::new_boolean(sarzak);
+        Argument::new("force".to_owned(), 1, &rename, &boolean, sarzak);
+
+        let rendered = old.diff(&new).to_string();
+        assert!(rendered.contains("~ operation Object.rename"), "{rendered}");
+        assert!(rendered.contains("    body: \"\" -> \"rename.dwarf\""));
+        assert!(rendered.contains("+ argument Object.rename.force"));
+        assert!(!rendered.contains("argument Object.rename.name"));
+    }
+
+    #[test]
+    fn test_enumerations() {
+        let enumeration = || {
+            let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+            let sarzak = domain.sarzak_mut();
+            sarzak.set_id_strategy(IdStrategy::Derived(UUID_NS));
+            let color = Enumeration::new("Color".to_owned(), "".to_owned(), sarzak);
+            Ty::new_enumeration(&color, sarzak);
+            let red = Enumerator::new("red".to_owned(), 0, &color, sarzak);
+            Enumerator::new("green".to_owned(), 1, &color, sarzak);
+            (domain, color, red)
+        };
+
+        let (old, ..) = enumeration();
+        let (mut new, color, red) = enumeration();
+        let sarzak = new.sarzak_mut();
+        red.write().unwrap().position = 2;
+        Enumerator::new("blue".to_owned(), 0, &color, sarzak);
+        color.write().unwrap().description = "Primary colors".to_owned();
+
+        let rendered = old.diff(&new).to_string();
+        assert!(rendered.contains("~ enumeration Color"), "{rendered}");
+        assert!(rendered.contains("    description: \"\" -> \"Primary colors\""));
+        assert!(rendered.contains("~ enumerator Color.red"));
+        assert!(rendered.contains("    position: \"0\" -> \"2\""));
+        assert!(rendered.contains("+ enumerator Color.blue"));
+        assert!(!rendered.contains("enumerator Color.green"));
+    }
+}
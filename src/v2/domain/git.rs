@@ -0,0 +1,308 @@
+//! Git Integration
+//!
+//! Persisted domains are meant to be checked into version control. This
+//! drives the `git` command line tool so that persisting a domain can also
+//! stage, and commit, the files that changed, with a commit message that says
+//! what changed in the model. Old versions of a domain can be loaded straight
+//! out of a local commit.
+//!
+//! The domain directory needs to be inside a git working tree. Nothing here
+//! talks to a remote.
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use super::{
+    diff::{Change, DomainDiff},
+    migrate, Domain,
+};
+
+/// What to do with git after persisting a domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Git {
+    /// Stage the domain's changed files
+    Stage,
+    /// Stage the domain's changed files, and commit them
+    Commit,
+}
+
+impl Domain {
+    /// Persist the domain into a git working tree
+    ///
+    /// The domain is persisted to `path` as usual, and then the changes to
+    /// files under `path` are staged. Nothing else in the working tree is
+    /// touched. With [`Git::Commit`] the changes are committed, with a message
+    /// built from a [`Domain::diff`] against the domain in `HEAD`.
+    ///
+    /// Returns the id of the new commit. There is no commit if nothing under
+    /// `path` changed.
+    pub fn persist_git<P: AsRef<Path>>(&self, path: P, git: Git) -> io::Result<Option<String>> {
+        let path = path.as_ref();
+
+        // Work out the message before we overwrite what's on disk.
+        let message = match git {
+            Git::Commit => Some(self.commit_message(path)?),
+            Git::Stage => None,
+        };
+
+        self.persist(path)?;
+        run(path, ["add", "--all", "--", "."])?;
+
+        let message = match message {
+            Some(message) => message,
+            None => return Ok(None),
+        };
+
+        // `diff --quiet` exits with 1 when there are differences.
+        if succeeds(path, ["diff", "--cached", "--quiet", "--", "."])? {
+            return Ok(None);
+        }
+        run(
+            path,
+            ["commit", "--quiet", "--message", &message, "--", "."],
+        )?;
+
+        Ok(Some(run(path, ["rev-parse", "HEAD"])?))
+    }
+
+    /// Load a domain as it was in a local commit
+    ///
+    /// `path` is where the domain lives in the working tree, and `rev` is
+    /// anything that git can turn into a commit: a hash, a branch, `HEAD~2`.
+    /// The working tree and the index are left alone, and the domain
+    /// doesn't need to exist in the working tree any more.
+    pub fn load_at_commit<P: AsRef<Path>>(path: P, rev: &str) -> io::Result<Self> {
+        let (repo, prefix) = locate(path.as_ref())?;
+        let commit = run(
+            &repo,
+            ["rev-parse", "--verify", &format!("{rev}^{{commit}}")],
+        )?;
+
+        // Check the tree out into a scratch directory, through a scratch
+        // index, so that the user's index isn't disturbed.
+        let scratch = migrate::scratch_dir("git")?;
+        let index = scratch.path().join("index");
+        let domain = scratch.path().join("domain");
+        let tree = format!("{commit}:{}", prefix.to_string_lossy());
+
+        let mut checkout = OsString::from("--prefix=");
+        checkout.push(&domain);
+        checkout.push("/");
+
+        let git = |args: &[&OsStr]| -> io::Result<String> {
+            output(
+                Command::new("git")
+                    .arg("-C")
+                    .arg(&repo)
+                    .env("GIT_INDEX_FILE", &index)
+                    .args(args),
+            )
+        };
+        git(&["read-tree".as_ref(), tree.as_ref()])?;
+        git(&["checkout-index".as_ref(), "--all".as_ref(), &checkout])?;
+
+        Domain::load(domain)
+    }
+
+    /// Describe the difference between this domain and the one in `HEAD`
+    ///
+    fn commit_message(&self, path: &Path) -> io::Result<String> {
+        let (repo, prefix) = locate(path)?;
+        let metadata = format!("HEAD:{}", prefix.join("metadata.json").to_string_lossy());
+
+        // There is nothing to compare with in a new repository, or if the
+        // domain has never been committed.
+        let committed = if exists(&repo, &metadata)? {
+            Some(Domain::load_at_commit(path, "HEAD")?)
+        } else {
+            None
+        };
+
+        Ok(match committed {
+            Some(committed) => summarize(self.name(), &committed.diff(self)),
+            None => format!("Add {} domain", self.name()),
+        })
+    }
+}
+
+/// Make a commit message out of a diff
+///
+/// The subject line counts the changes, and the body lists them.
+fn summarize(name: &str, diff: &DomainDiff) -> String {
+    if diff.is_empty() {
+        return format!("Update {name} domain drawing");
+    }
+
+    let count = |f: fn(&Change) -> bool| diff.changes.iter().filter(|c| f(c)).count();
+    let counts = [
+        (count(|c| matches!(c, Change::Added { .. })), "added"),
+        (count(|c| matches!(c, Change::Removed { .. })), "removed"),
+        (count(|c| matches!(c, Change::Modified { .. })), "modified"),
+    ];
+    let counts: Vec<_> = counts
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, what)| format!("{n} {what}"))
+        .collect();
+
+    format!("Update {name} domain: {}\n\n{diff}", counts.join(", "))
+}
+
+/// Find the top of the working tree, and where `path` is in it
+///
+/// `path` need not exist, so long as one of its ancestors does.
+fn locate(path: &Path) -> io::Result<(PathBuf, PathBuf)> {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.is_dir() {
+        rest.push(existing.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in a git working tree", path.display()),
+            )
+        })?);
+        existing = existing.parent().unwrap_or(Path::new("."));
+        if existing.as_os_str().is_empty() {
+            existing = Path::new(".");
+        }
+    }
+
+    let repo = PathBuf::from(run(existing, ["rev-parse", "--show-toplevel"])?);
+    let mut prefix = PathBuf::from(run(existing, ["rev-parse", "--show-prefix"])?);
+    for part in rest.into_iter().rev() {
+        prefix.push(part);
+    }
+
+    Ok((repo, prefix))
+}
+
+/// Run git in `dir`, and return what it printed
+fn run<const N: usize>(dir: &Path, args: [&str; N]) -> io::Result<String> {
+    output(Command::new("git").arg("-C").arg(dir).args(args))
+}
+
+/// Run git in `dir`, and say if it exited happily
+///
+/// Exiting with 1 is how git says "no" to a question. Anything else is an
+/// error.
+fn succeeds<const N: usize>(dir: &Path, args: [&str; N]) -> io::Result<bool> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => Err(failed(&args.join(" "), &output.stderr)),
+    }
+}
+
+/// Say if git knows about `object`
+///
+/// `cat-file -e` doesn't distinguish between an object that's missing and
+/// one that can't be named, e.g. `HEAD` in an empty repository. Either way
+/// there's nothing there.
+fn exists(dir: &Path, object: &str) -> io::Result<bool> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["cat-file", "-e", object])
+        .stderr(Stdio::null())
+        .status()?;
+
+    Ok(status.success())
+}
+
+fn output(command: &mut Command) -> io::Result<String> {
+    let output = command.output()?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    } else {
+        let args: Vec<_> = command
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        Err(failed(&args.join(" "), &output.stderr))
+    }
+}
+
+fn failed(args: &str, stderr: &[u8]) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!(
+            "git {args} failed: {}",
+            String::from_utf8_lossy(stderr).trim()
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::v2::sarzak::types::Object;
+
+    fn repo(name: &str) -> migrate::ScratchDir {
+        let scratch = migrate::scratch_dir(&format!("git-{name}")).unwrap();
+        let repo = scratch.path();
+
+        run(repo, ["init", "--quiet"]).unwrap();
+        run(repo, ["config", "user.name", "sarzak"]).unwrap();
+        run(repo, ["config", "user.email", "sarzak@example.com"]).unwrap();
+        run(repo, ["config", "commit.gpgsign", "false"]).unwrap();
+
+        scratch
+    }
+
+    #[test]
+    fn test_commit() {
+        let scratch = repo("commit");
+        let repo = scratch.path();
+        let path = repo.join("models").join("sarzak");
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let objects = domain.sarzak().iter_object().count();
+
+        let first = domain.persist_git(&path, Git::Commit).unwrap().unwrap();
+        assert_eq!(
+            run(repo, ["log", "-1", "--format=%s"]).unwrap(),
+            "Add sarzak domain"
+        );
+
+        // Nothing changed, nothing to commit
+        assert!(domain.persist_git(&path, Git::Commit).unwrap().is_none());
+
+        Object::new(
+            "".to_owned(),
+            "NEW".to_owned(),
+            "New".to_owned(),
+            domain.sarzak_mut(),
+        );
+        let second = domain.persist_git(&path, Git::Commit).unwrap().unwrap();
+        assert_ne!(first, second);
+        let message = run(repo, ["log", "-1", "--format=%B"]).unwrap();
+        assert!(message.starts_with("Update sarzak domain: 1 added"));
+        assert!(message.contains("+ object New"));
+
+        let then = Domain::load_at_commit(&path, &first).unwrap();
+        assert_eq!(then.sarzak().iter_object().count(), objects);
+        assert!(then.sarzak().exhume_object_id_by_name("New").is_none());
+
+        // The domain can be loaded from history after it's gone
+        fs::remove_dir_all(&path).unwrap();
+        let now = Domain::load_at_commit(&path, "HEAD").unwrap();
+        assert!(now.sarzak().exhume_object_id_by_name("New").is_some());
+    }
+
+    #[test]
+    fn test_stage() {
+        let scratch = repo("stage");
+        let repo = scratch.path();
+        let path = repo.join("sarzak");
+        let domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+
+        assert!(domain.persist_git(&path, Git::Stage).unwrap().is_none());
+        assert!(!succeeds(repo, ["diff", "--cached", "--quiet"]).unwrap());
+        assert!(!exists(repo, "HEAD").unwrap());
+    }
+}
@@ -0,0 +1,636 @@
+//! Three-way Merge
+//!
+//! A domain persisted with [`Domain::persist`] is a directory with one JSON
+//! file per instance. That's friendly to version control, right up until two
+//! people edit the same domain, and a textual merge leaves references pointing
+//! at instances that aren't there anymore.
+//!
+//! This merges instances instead of text. Given the common ancestor and two
+//! descendants, instances are matched by id, and changes from each side are
+//! applied field by field. Changes that can't both be applied are reported as
+//! [`Conflict`]s, as are references that only dangle after merging, e.g. an
+//! attribute added to an object that the other side deleted.
+//!
+//! Where there is a conflict, the merged domain takes our side. If one side
+//! deleted an instance that the other side changed, the changed instance is
+//! kept.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::v2::{
+    domain::Domain,
+    merlin::store::ObjectStore as MerlinStore,
+    sarzak::{
+        lint::{lint, Diagnostic},
+        store::ObjectStore as SarzakStore,
+    },
+};
+
+/// One of the two descendants being merged
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ours => write!(f, "ours"),
+            Self::Theirs => write!(f, "theirs"),
+        }
+    }
+}
+
+/// A change that could not be merged automatically
+///
+/// `kind` is the type of the instance, e.g. `attribute`, as in
+/// [`Diagnostic`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Conflict {
+    /// Both sides changed the same field to different values.
+    ///
+    /// The values are JSON. `field` is empty if the instance is not a struct,
+    /// e.g. a [`Relationship`](crate::v2::sarzak::types::Relationship).
+    Modified {
+        kind: &'static str,
+        id: Uuid,
+        field: String,
+        ours: String,
+        theirs: String,
+    },
+    /// One side deleted an instance that the other side modified.
+    Deleted {
+        kind: &'static str,
+        id: Uuid,
+        deleted_by: Side,
+    },
+    /// Both sides added an instance with the same id, and different contents.
+    Added { kind: &'static str, id: Uuid },
+    /// After merging, `id` refers to `target` via `field`, and `target` is gone.
+    Dangling {
+        kind: &'static str,
+        id: Uuid,
+        field: &'static str,
+        target: Uuid,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Modified {
+                kind,
+                id,
+                field,
+                ours,
+                theirs,
+            } => {
+                if field.is_empty() {
+                    write!(f, "{kind} {id} changed on both sides: ")?;
+                } else {
+                    write!(f, "{kind} {id} `{field}` changed on both sides: ")?;
+                }
+                write!(f, "ours {ours}, theirs {theirs}")
+            }
+            Self::Deleted {
+                kind,
+                id,
+                deleted_by,
+            } => write!(
+                f,
+                "{kind} {id} was deleted in {deleted_by}, and modified in the other"
+            ),
+            Self::Added { kind, id } => {
+                write!(f, "{kind} {id} was added differently on both sides")
+            }
+            Self::Dangling {
+                kind,
+                id,
+                field,
+                target,
+            } => write!(
+                f,
+                "{kind} {id} refers to {target} via `{field}`, which was deleted"
+            ),
+        }
+    }
+}
+
+/// The result of a merge
+///
+/// The domain is always usable, but if there are conflicts it's probably not
+/// what anyone wants.
+#[derive(Debug)]
+pub struct Merge {
+    pub domain: Domain,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Merge {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl Domain {
+    /// Merge two descendants of `base`
+    ///
+    /// The metadata is taken from `ours`.
+    pub fn merge(base: &Domain, ours: &Domain, theirs: &Domain) -> Merge {
+        let mut conflicts = Vec::new();
+
+        let sarzak = merge_sarzak(&base.sarzak, &ours.sarzak, &theirs.sarzak, &mut conflicts);
+        let merlin = merge_merlin(&base.merlin, &ours.merlin, &theirs.merlin, &mut conflicts);
+
+        // Only complain about references that are broken by the merge. If they
+        // were broken on one side already, that's not our problem.
+        let broken = |sarzak: &SarzakStore, merlin: &MerlinStore| -> BTreeSet<_> {
+            dangling(sarzak, merlin)
+                .into_iter()
+                .map(|(kind, id, field, target)| (id, field, target, kind))
+                .collect()
+        };
+        let before: BTreeSet<_> = broken(&ours.sarzak, &ours.merlin)
+            .union(&broken(&theirs.sarzak, &theirs.merlin))
+            .cloned()
+            .collect();
+        for (id, field, target, kind) in broken(&sarzak, &merlin).difference(&before) {
+            conflicts.push(Conflict::Dangling {
+                kind,
+                id: *id,
+                field,
+                target: *target,
+            });
+        }
+
+        Merge {
+            domain: Domain {
+                meta: ours.meta.clone(),
+                sarzak,
+                merlin,
+            },
+            conflicts,
+        }
+    }
+}
+
+/// Merge three versions of a table
+///
+/// Every instance that ends up in the merged table is returned.
+fn merge_table<T>(
+    kind: &'static str,
+    base: Vec<T>,
+    ours: Vec<T>,
+    theirs: Vec<T>,
+    id: fn(&T) -> Uuid,
+    conflicts: &mut Vec<Conflict>,
+) -> Vec<T>
+where
+    T: Clone + DeserializeOwned + PartialEq + Serialize,
+{
+    let by_id =
+        |table: Vec<T>| -> BTreeMap<Uuid, T> { table.into_iter().map(|t| (id(&t), t)).collect() };
+    let base = by_id(base);
+    let ours = by_id(ours);
+    let theirs = by_id(theirs);
+
+    let ids: BTreeSet<&Uuid> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect();
+
+    let mut merged = Vec::new();
+    for id in ids {
+        match (base.get(id), ours.get(id), theirs.get(id)) {
+            // Deleted on both sides
+            (Some(_), None, None) => {}
+            // Deleted on one side
+            (Some(base), None, Some(theirs)) => {
+                if base != theirs {
+                    conflicts.push(Conflict::Deleted {
+                        kind,
+                        id: *id,
+                        deleted_by: Side::Ours,
+                    });
+                    merged.push(theirs.clone());
+                }
+            }
+            (Some(base), Some(ours), None) => {
+                if base != ours {
+                    conflicts.push(Conflict::Deleted {
+                        kind,
+                        id: *id,
+                        deleted_by: Side::Theirs,
+                    });
+                    merged.push(ours.clone());
+                }
+            }
+            // Added on one side
+            (None, Some(new), None) | (None, None, Some(new)) => merged.push(new.clone()),
+            // Added on both sides
+            (None, Some(ours), Some(theirs)) => {
+                if ours != theirs {
+                    conflicts.push(Conflict::Added { kind, id: *id });
+                }
+                merged.push(ours.clone());
+            }
+            (Some(base), Some(ours), Some(theirs)) => {
+                if ours == theirs || theirs == base {
+                    merged.push(ours.clone());
+                } else if ours == base {
+                    merged.push(theirs.clone());
+                } else {
+                    merged.push(merge_fields(kind, id, base, ours, theirs, conflicts));
+                }
+            }
+            (None, None, None) => unreachable!(),
+        }
+    }
+
+    merged
+}
+
+/// Merge an instance that was changed on both sides
+fn merge_fields<T>(
+    kind: &'static str,
+    id: &Uuid,
+    base: &T,
+    ours: &T,
+    theirs: &T,
+    conflicts: &mut Vec<Conflict>,
+) -> T
+where
+    T: Clone + DeserializeOwned + Serialize,
+{
+    // Our types all serialize to JSON. Anything else is a bug.
+    let json = |t: &T| serde_json::to_value(t).expect("instance serializes to JSON");
+    let base = json(base);
+    let ours_json = json(ours);
+    let theirs = json(theirs);
+
+    let mut found = Vec::new();
+    let merged = merge_value("", &base, &ours_json, &theirs, &mut found);
+
+    for (field, ours, theirs) in found {
+        conflicts.push(Conflict::Modified {
+            kind,
+            id: *id,
+            field,
+            ours,
+            theirs,
+        });
+    }
+
+    // Should the fields come back together as something that isn't a T, then
+    // the instance really is in conflict, and we take ours.
+    serde_json::from_value(merged).unwrap_or_else(|_| ours.clone())
+}
+
+fn merge_value(
+    path: &str,
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    conflicts: &mut Vec<(String, String, String)>,
+) -> Value {
+    if ours == theirs || theirs == base {
+        return ours.clone();
+    }
+    if ours == base {
+        return theirs.clone();
+    }
+
+    match (base, ours, theirs) {
+        (Value::Object(b), Value::Object(o), Value::Object(t))
+            if b.keys().eq(o.keys()) && b.keys().eq(t.keys()) =>
+        {
+            let merged = b
+                .iter()
+                .map(|(key, b)| {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    let value = merge_value(&path, b, &o[key], &t[key], conflicts);
+                    (key.clone(), value)
+                })
+                .collect();
+            Value::Object(merged)
+        }
+        _ => {
+            conflicts.push((path.to_owned(), ours.to_string(), theirs.to_string()));
+            ours.clone()
+        }
+    }
+}
+
+/// Merge each table, and inter the results into a new store
+///
+/// Each table is given as `"kind" => Type, iter_type, inter_type, |x| id;`.
+macro_rules! merge_store {
+    ($store:ty, $base:expr, $ours:expr, $theirs:expr, $conflicts:expr,
+     $($kind:literal => $ty:ident, $iter:ident, $inter:ident, |$x:ident| $id:expr;)*) => {{
+        let mut merged = <$store>::new();
+        $(
+            let table = |store: &$store| -> Vec<$ty> {
+                store.$iter().map(|x| x.read().unwrap().clone()).collect()
+            };
+            for instance in merge_table(
+                $kind,
+                table($base),
+                table($ours),
+                table($theirs),
+                |$x: &$ty| $id,
+                $conflicts,
+            ) {
+                merged.$inter(Arc::new(RwLock::new(instance)));
+            }
+        )*
+        merged
+    }};
+}
+
+fn merge_sarzak(
+    base: &SarzakStore,
+    ours: &SarzakStore,
+    theirs: &SarzakStore,
+    conflicts: &mut Vec<Conflict>,
+) -> SarzakStore {
+    use crate::v2::sarzak::types::*;
+
+    merge_store!(SarzakStore, base, ours, theirs, conflicts,
+        "acknowledged event" => AcknowledgedEvent,
+            iter_acknowledged_event, inter_acknowledged_event, |x| x.id;
+        "an associative referent" => AnAssociativeReferent,
+            iter_an_associative_referent, inter_an_associative_referent, |x| x.id;
+        "argument" => Argument, iter_argument, inter_argument, |x| x.id;
+        "associative" => Associative, iter_associative, inter_associative, |x| x.id;
+        "associative referent" => AssociativeReferent,
+            iter_associative_referent, inter_associative_referent, |x| x.id;
+        "associative referrer" => AssociativeReferrer,
+            iter_associative_referrer, inter_associative_referrer, |x| x.id;
+        "attribute" => Attribute, iter_attribute, inter_attribute, |x| x.id;
+        "binary" => Binary, iter_binary, inter_binary, |x| x.id;
+        "cardinality" => Cardinality, iter_cardinality, inter_cardinality, |x| x.id();
+        "conditionality" => Conditionality, iter_conditionality, inter_conditionality, |x| x.id();
+        "enumeration" => Enumeration, iter_enumeration, inter_enumeration, |x| x.id;
+        "enumerator" => Enumerator, iter_enumerator, inter_enumerator, |x| x.id;
+        "event" => Event, iter_event, inter_event, |x| x.id;
+        "external" => External, iter_external, inter_external, |x| x.id;
+        "isa" => Isa, iter_isa, inter_isa, |x| x.id;
+        "next state" => NextState, iter_next_state, inter_next_state, |x| x.id;
+        "object" => Object, iter_object, inter_object, |x| x.id;
+        "operation" => Operation, iter_operation, inter_operation, |x| x.id;
+        "parameter" => Parameter, iter_parameter, inter_parameter, |x| x.id;
+        "referent" => Referent, iter_referent, inter_referent, |x| x.id;
+        "referrer" => Referrer, iter_referrer, inter_referrer, |x| x.id;
+        "relationship" => Relationship, iter_relationship, inter_relationship, |x| x.id();
+        "state" => State, iter_state, inter_state, |x| x.id;
+        "subtype" => Subtype, iter_subtype, inter_subtype, |x| x.id;
+        "supertype" => Supertype, iter_supertype, inter_supertype, |x| x.id;
+        "transition" => Transition, iter_transition, inter_transition, |x| x.id;
+        "ty" => Ty, iter_ty, inter_ty, |x| x.id();
+    )
+}
+
+fn merge_merlin(
+    base: &MerlinStore,
+    ours: &MerlinStore,
+    theirs: &MerlinStore,
+    conflicts: &mut Vec<Conflict>,
+) -> MerlinStore {
+    use crate::v2::merlin::types::*;
+
+    merge_store!(MerlinStore, base, ours, theirs, conflicts,
+        "anchor" => Anchor, iter_anchor, inter_anchor, |x| x.id;
+        "bisection" => Bisection, iter_bisection, inter_bisection, |x| x.id;
+        "x box" => XBox, iter_x_box, inter_x_box, |x| x.id;
+        "edge" => Edge, iter_edge, inter_edge, |x| x.id();
+        "glyph" => Glyph, iter_glyph, inter_glyph, |x| x.id;
+        "line" => Line, iter_line, inter_line, |x| x.id;
+        "line segment" => LineSegment, iter_line_segment, inter_line_segment, |x| x.id;
+        "line segment point" => LineSegmentPoint,
+            iter_line_segment_point, inter_line_segment_point, |x| x.id;
+        "point" => Point, iter_point, inter_point, |x| x.id;
+        "relationship name" => RelationshipName,
+            iter_relationship_name, inter_relationship_name, |x| x.id;
+        "relationship phrase" => RelationshipPhrase,
+            iter_relationship_phrase, inter_relationship_phrase, |x| x.id;
+    )
+}
+
+/// References to instances that don't exist
+///
+/// The sarzak references come from the linter. The merlin references are the
+/// ones that hold a drawing together, and tie it to the model.
+fn dangling(
+    sarzak: &SarzakStore,
+    merlin: &MerlinStore,
+) -> Vec<(&'static str, Uuid, &'static str, Uuid)> {
+    let mut dangling: Vec<_> = lint(sarzak)
+        .into_iter()
+        .filter_map(|d| match d {
+            Diagnostic::DanglingReference {
+                id,
+                kind,
+                field,
+                target,
+            } => Some((kind, id, field, target)),
+            _ => None,
+        })
+        .collect();
+
+    let mut check = |kind: &'static str, id: Uuid, field: &'static str, target: Uuid, ok: bool| {
+        if !ok {
+            dangling.push((kind, id, field, target));
+        }
+    };
+
+    for x_box in merlin.iter_x_box() {
+        let x_box = x_box.read().unwrap();
+        let ok = sarzak.exhume_object(&x_box.object).is_some();
+        check("x box", x_box.id, "object", x_box.object, ok);
+    }
+    for line in merlin.iter_line() {
+        let line = line.read().unwrap();
+        let ok = sarzak.exhume_relationship(&line.relationship).is_some();
+        check("line", line.id, "relationship", line.relationship, ok);
+    }
+    for anchor in merlin.iter_anchor() {
+        let anchor = anchor.read().unwrap();
+        let ok = merlin.exhume_x_box(&anchor.x_box).is_some();
+        check("anchor", anchor.id, "x_box", anchor.x_box, ok);
+        let ok = merlin.exhume_line(&anchor.line).is_some();
+        check("anchor", anchor.id, "line", anchor.line, ok);
+    }
+    for segment in merlin.iter_line_segment() {
+        let segment = segment.read().unwrap();
+        let ok = merlin.exhume_line(&segment.line).is_some();
+        check("line segment", segment.id, "line", segment.line, ok);
+    }
+    for glyph in merlin.iter_glyph() {
+        let glyph = glyph.read().unwrap();
+        let ok = merlin.exhume_line(&glyph.line).is_some();
+        check("glyph", glyph.id, "line", glyph.line, ok);
+    }
+
+    dangling
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::types::{Attribute, State, BOOLEAN, FLOAT, INTEGER, Z_STRING};
+
+    /// Load three copies of a domain from the same JSON directory
+    ///
+    /// Loading from the cuckoo model each time would give the drawing
+    /// different ids.
+    fn load(name: &str) -> (Domain, Domain, Domain) {
+        let scratch = crate::v2::domain::migrate::scratch_dir(&format!("merge-{name}")).unwrap();
+        let path = scratch.path().join("sarzak");
+        Domain::from_cuckoo("models/sarzak.json")
+            .unwrap()
+            .persist(&path)
+            .unwrap();
+
+        (
+            Domain::load(&path).unwrap(),
+            Domain::load(&path).unwrap(),
+            Domain::load(&path).unwrap(),
+        )
+    }
+
+    fn attribute(domain: &Domain, object: &str, name: &str) -> Arc<RwLock<Attribute>> {
+        let sarzak = domain.sarzak();
+        let obj_id = sarzak.exhume_object_id_by_name(object).unwrap();
+        sarzak
+            .iter_attribute()
+            .find(|a| {
+                let a = a.read().unwrap();
+                a.obj_id == obj_id && a.name == name
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_clean_merge() {
+        let (base, ours, theirs) = load("clean");
+
+        attribute(&ours, "Object", "name").write().unwrap().name = "title".to_owned();
+        attribute(&theirs, "Isa", "number").write().unwrap().ty = FLOAT;
+        // Both sides make the same change
+        for domain in [&ours, &theirs] {
+            attribute(domain, "State", "name").write().unwrap().ty = INTEGER;
+        }
+
+        let merge = Domain::merge(&base, &ours, &theirs);
+        assert!(merge.is_clean(), "{:?}", merge.conflicts);
+
+        let merged = &merge.domain;
+        assert!(merged
+            .sarzak()
+            .iter_attribute()
+            .any(|a| a.read().unwrap().name == "title"));
+        assert_eq!(attribute(merged, "Isa", "number").read().unwrap().ty, FLOAT);
+        assert_eq!(
+            attribute(merged, "State", "name").read().unwrap().ty,
+            INTEGER
+        );
+        assert!(base.diff(merged).changes.len() == 3);
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let (base, mut ours, mut theirs) = load("conflicts");
+
+        // Retype the same attribute differently
+        let id = attribute(&base, "Isa", "number").read().unwrap().id;
+        attribute(&ours, "Isa", "number").write().unwrap().ty = FLOAT;
+        attribute(&theirs, "Isa", "number").write().unwrap().ty = Z_STRING;
+
+        // Delete an object and its attributes on one side, and add an
+        // attribute to it on the other.
+        let state = base.sarzak().exhume_object_id_by_name("State").unwrap();
+        let gone: Vec<Uuid> = theirs
+            .sarzak()
+            .iter_attribute()
+            .filter(|a| a.read().unwrap().obj_id == state)
+            .map(|a| a.read().unwrap().id)
+            .collect();
+        let sarzak = theirs.sarzak_mut();
+        for id in gone {
+            sarzak.exorcise_attribute(&id);
+        }
+        sarzak.exorcise_object(&state);
+        sarzak.exorcise_ty(&state);
+
+        let added = Uuid::new_v4();
+        ours.sarzak_mut()
+            .inter_attribute(Arc::new(RwLock::new(Attribute {
+                id: added,
+                name: "entry".to_owned(),
+                obj_id: state,
+                ty: BOOLEAN,
+            })));
+        // And type an attribute of another object by it, and give it a state
+        // machine.
+        let typed = attribute(&ours, "Isa", "id");
+        typed.write().unwrap().ty = state;
+        let typed = typed.read().unwrap().id;
+        let sarzak = ours.sarzak_mut();
+        let object = sarzak.exhume_object(&state).unwrap();
+        let idle = State::new("idle".to_owned(), &object, sarzak);
+        let idle = idle.read().unwrap().id;
+
+        let merge = Domain::merge(&base, &ours, &theirs);
+
+        assert!(merge.conflicts.contains(&Conflict::Modified {
+            kind: "attribute",
+            id,
+            field: "ty".to_owned(),
+            ours: format!("\"{FLOAT}\""),
+            theirs: format!("\"{Z_STRING}\""),
+        }));
+        assert!(merge.conflicts.contains(&Conflict::Dangling {
+            kind: "attribute",
+            id: added,
+            field: "obj_id",
+            target: state,
+        }));
+        assert!(merge.conflicts.contains(&Conflict::Dangling {
+            kind: "attribute",
+            id: typed,
+            field: "ty",
+            target: state,
+        }));
+        assert!(merge.conflicts.contains(&Conflict::Dangling {
+            kind: "state",
+            id: idle,
+            field: "obj_id",
+            target: state,
+        }));
+        // Ours wins
+        assert_eq!(
+            merge
+                .domain
+                .sarzak()
+                .exhume_attribute(&id)
+                .unwrap()
+                .read()
+                .unwrap()
+                .ty,
+            FLOAT
+        );
+    }
+}
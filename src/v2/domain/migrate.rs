@@ -0,0 +1,260 @@
+//! Persisted Domain Migrations
+//!
+//! A domain persisted with [`Domain::persist`](super::Domain::persist) records
+//! the version of sarzak that wrote it in `metadata.json`. When the on-disk
+//! format of the stores changes, a [`Migration`] is registered here that takes
+//! a domain directory from the old format to the new one.
+//!
+//! Migrations are run by [`Domain::load`](super::Domain::load) against a copy
+//! of the directory, so the original is left alone until the domain is
+//! persisted again.
+use std::{
+    fs, io,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde_json::Value;
+
+use crate::VERSION;
+
+/// A version of sarzak, as `[major, minor, patch]`
+///
+/// Anything after the patch number, e.g. `-beta.1`, is ignored.
+pub type Version = [u64; 3];
+
+/// Parse a version string
+pub fn parse_version(version: &str) -> Option<Version> {
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(|p| p.parse::<u64>().ok());
+
+    let version = [parts.next()??, parts.next()??, parts.next()??];
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(version)
+}
+
+/// The version of this crate
+pub fn crate_version() -> Version {
+    parse_version(VERSION).expect("crate version is major.minor.patch")
+}
+
+/// A step from one on-disk format to the next
+///
+/// `version` is the first version of sarzak that writes the new format. The
+/// migration is run on domains written by anything older.
+#[derive(Clone, Debug)]
+pub struct Migration {
+    pub version: Version,
+    pub description: &'static str,
+    /// Rewrite the domain directory at the given path
+    pub migrate: fn(&Path) -> io::Result<()>,
+}
+
+/// An ordered set of migrations
+#[derive(Clone, Debug)]
+pub struct Migrations {
+    migrations: Vec<Migration>,
+}
+
+impl Default for Migrations {
+    /// The migrations for the formats that this crate has written
+    ///
+    /// There aren't any yet.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Migrations {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self {
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Add a migration
+    ///
+    /// Migrations are kept in version order, and migrations for the same
+    /// version run in the order they were registered.
+    pub fn register(&mut self, migration: Migration) -> &mut Self {
+        let index = self
+            .migrations
+            .partition_point(|m| m.version <= migration.version);
+        self.migrations.insert(index, migration);
+        self
+    }
+
+    /// The migrations that need to run on a domain written by `from`
+    pub fn pending(&self, from: Version) -> impl Iterator<Item = &Migration> {
+        self.migrations.iter().filter(move |m| m.version > from)
+    }
+
+    /// Run the pending migrations on the domain directory at `path`
+    ///
+    /// Returns the number of migrations that were run.
+    pub(super) fn run(&self, from: Version, path: &Path) -> io::Result<usize> {
+        let mut count = 0;
+        for migration in self.pending(from) {
+            log::debug!(
+                "migrating domain at {} to {:?}: {}",
+                path.display(),
+                migration.version,
+                migration.description
+            );
+            (migration.migrate)(path).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!(
+                        "migration to {:?} ({}) failed: {e}",
+                        migration.version, migration.description
+                    ),
+                )
+            })?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+/// Rewrite every instance in a table of a persisted store
+///
+/// `store` is the name of the store directory, e.g. `sarzak.json`, and `table`
+/// is the name of the table, e.g. `attribute`. Each instance is passed to `f`
+/// as JSON, and written back out. This is the usual building block for a
+/// migration.
+///
+/// Instances in the sarzak store are persisted as a tuple of the instance and
+/// a timestamp, so the instance is `value[0]`.
+pub fn rewrite_instances<F>(path: &Path, store: &str, table: &str, mut f: F) -> io::Result<()>
+where
+    F: FnMut(&mut Value),
+{
+    let path = path.join(store).join(table);
+    if !path.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |ext| ext != "json") {
+            continue;
+        }
+
+        let mut value: Value = serde_json::from_slice(&fs::read(&path)?)?;
+        f(&mut value);
+        fs::write(&path, serde_json::to_vec_pretty(&value)?)?;
+    }
+
+    Ok(())
+}
+
+/// Copy a domain directory somewhere that we can scribble on it
+pub(super) fn scratch_copy(path: &Path) -> io::Result<ScratchDir> {
+    let scratch = scratch_dir("migrate")?;
+    copy_dir(path, scratch.path())?;
+
+    Ok(scratch)
+}
+
+/// Make an empty temporary directory, that's removed when it's dropped
+pub(crate) fn scratch_dir(purpose: &str) -> io::Result<ScratchDir> {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let scratch = std::env::temp_dir().join(format!(
+        "sarzak-{purpose}-{}-{}",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    if scratch.exists() {
+        fs::remove_dir_all(&scratch)?;
+    }
+    fs::create_dir_all(&scratch)?;
+
+    Ok(ScratchDir(scratch))
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let to = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &to)?;
+        } else {
+            fs::copy(entry.path(), to)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A temporary directory that is removed when dropped
+pub(crate) struct ScratchDir(std::path::PathBuf);
+
+impl ScratchDir {
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("2.13.19"), Some([2, 13, 19]));
+        assert_eq!(parse_version("3.0.0-beta.1"), Some([3, 0, 0]));
+        assert_eq!(parse_version("2.13"), None);
+        assert_eq!(parse_version("2.x.1"), None);
+        assert!([2, 13, 19] < [2, 14, 0]);
+    }
+
+    #[test]
+    fn test_register_order() {
+        fn nop(_: &Path) -> io::Result<()> {
+            Ok(())
+        }
+
+        let mut migrations = Migrations::new();
+        migrations
+            .register(Migration {
+                version: [2, 0, 0],
+                description: "second",
+                migrate: nop,
+            })
+            .register(Migration {
+                version: [1, 0, 0],
+                description: "first",
+                migrate: nop,
+            })
+            .register(Migration {
+                version: [2, 0, 0],
+                description: "third",
+                migrate: nop,
+            });
+
+        let pending: Vec<_> = migrations
+            .pending([0, 1, 0])
+            .map(|m| m.description)
+            .collect();
+        assert_eq!(pending, ["first", "second", "third"]);
+
+        let pending: Vec<_> = migrations
+            .pending([1, 0, 0])
+            .map(|m| m.description)
+            .collect();
+        assert_eq!(pending, ["second", "third"]);
+    }
+}
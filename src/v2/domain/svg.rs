@@ -0,0 +1,399 @@
+//! SVG Export
+//!
+//! Draw the merlin diagram as an SVG document. Unlike the class diagrams in
+//! the `diagram` module, this reproduces the picture as it was drawn in the
+//! editor: boxes, lines, and glyphs are where merlin says they are.
+//!
+//! Object names and attributes come from the sarzak store, since merlin only
+//! knows about boxes.
+use std::{
+    fmt::Write,
+    sync::{Arc, RwLock},
+};
+
+use uuid::Uuid;
+
+use crate::v2::{
+    domain::{diagram::type_name, Domain},
+    merlin::{
+        store::ObjectStore as MerlinStore,
+        types::{Anchor, Edge, GlyphEnum, Line, PointEnum, XBox},
+    },
+    sarzak::store::ObjectStore as SarzakStore,
+};
+
+/// Space around the drawing
+const MARGIN: i64 = 40;
+const FONT_SIZE: i64 = 12;
+/// Vertical distance between lines of text in a box
+const LINE_HEIGHT: i64 = 16;
+const ARROW_LENGTH: f64 = 10.0;
+const ARROW_WIDTH: f64 = 5.0;
+
+impl Domain {
+    /// Render the merlin diagram as SVG
+    ///
+    /// The view box is fit to the drawing, rather than the paper, so that the
+    /// picture doesn't have acres of white space around it.
+    pub fn to_svg(&self) -> String {
+        let mut boxes: Vec<XBox> = self
+            .merlin
+            .iter_x_box()
+            .map(|x_box| x_box.read().unwrap().clone())
+            .collect();
+        boxes.sort_by_key(|x_box| (object_name(&x_box.object, &self.sarzak), x_box.id));
+
+        let mut lines: Vec<(i64, Line)> = self
+            .merlin
+            .iter_line()
+            .map(|line| {
+                let line = line.read().unwrap().clone();
+                (relationship_number(&line.relationship, &self.sarzak), line)
+            })
+            .collect();
+        lines.sort_by_key(|(number, line)| (*number, line.id));
+
+        let (min_x, min_y, max_x, max_y) = bounds(&boxes, &self.merlin);
+        let (width, height) = (max_x - min_x + 2 * MARGIN, max_y - min_y + 2 * MARGIN);
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="{} {} {width} {height}">"#,
+            min_x - MARGIN,
+            min_y - MARGIN,
+        )
+        .unwrap();
+        writeln!(out, "<title>{}</title>", escape(self.name())).unwrap();
+        writeln!(
+            out,
+            r#"<g font-family="monospace" font-size="{FONT_SIZE}" stroke="black" fill="none">"#
+        )
+        .unwrap();
+
+        for x_box in &boxes {
+            draw_box(x_box, &self.sarzak, &mut out);
+        }
+        for (_, line) in &lines {
+            draw_line(line, &self.merlin, &mut out);
+        }
+
+        writeln!(out, "</g>").unwrap();
+        writeln!(out, "</svg>").unwrap();
+
+        out
+    }
+}
+
+fn object_name(id: &Uuid, sarzak: &SarzakStore) -> String {
+    sarzak
+        .exhume_object(id)
+        .map(|obj| obj.read().unwrap().name.clone())
+        .unwrap_or_default()
+}
+
+fn relationship_number(id: &Uuid, sarzak: &SarzakStore) -> i64 {
+    let number = |id: &Uuid| {
+        sarzak
+            .exhume_binary(id)
+            .map(|b| b.read().unwrap().number)
+            .or_else(|| sarzak.exhume_isa(id).map(|i| i.read().unwrap().number))
+            .or_else(|| {
+                sarzak
+                    .exhume_associative(id)
+                    .map(|a| a.read().unwrap().number)
+            })
+    };
+
+    sarzak
+        .exhume_relationship(id)
+        .and_then(|rel| number(&rel.read().unwrap().id()))
+        .unwrap_or_default()
+}
+
+/// Find the extents of the drawing
+///
+/// Lines always end on boxes, so the boxes are enough, but for the
+/// inflection points of associative relationships.
+fn bounds(boxes: &[XBox], merlin: &MerlinStore) -> (i64, i64, i64, i64) {
+    let corners = boxes
+        .iter()
+        .flat_map(|b| [(b.x, b.y), (b.x + b.width, b.y + b.height)]);
+    let inflections = merlin.iter_point().filter_map(|p| {
+        let p = p.read().unwrap();
+        matches!(p.subtype, PointEnum::Inflection(_)).then_some((p.x, p.y))
+    });
+
+    corners
+        .chain(inflections)
+        .fold(None, |acc, (x, y)| match acc {
+            None => Some((x, y, x, y)),
+            Some((min_x, min_y, max_x, max_y)) => {
+                Some((min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)))
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Draw a box with the object name, and it's attributes below a rule
+fn draw_box(x_box: &XBox, sarzak: &SarzakStore, out: &mut String) {
+    let XBox {
+        x,
+        y,
+        width,
+        height,
+        ..
+    } = *x_box;
+
+    writeln!(
+        out,
+        r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="white"/>"#
+    )
+    .unwrap();
+
+    let name = object_name(&x_box.object, sarzak);
+    writeln!(
+        out,
+        r#"<text x="{}" y="{}" text-anchor="middle" font-weight="bold" fill="black" stroke="none">{}</text>"#,
+        x + width / 2,
+        y + LINE_HEIGHT,
+        escape(&name)
+    )
+    .unwrap();
+
+    let rule = y + LINE_HEIGHT + FONT_SIZE / 2;
+    writeln!(
+        out,
+        r#"<line x1="{x}" y1="{rule}" x2="{}" y2="{rule}"/>"#,
+        x + width
+    )
+    .unwrap();
+
+    let mut attributes: Vec<(String, String)> = sarzak
+        .iter_attribute()
+        .filter(|attr| attr.read().unwrap().obj_id == x_box.object)
+        .map(|attr| {
+            let attr = attr.read().unwrap();
+            (attr.name.clone(), type_name(&attr.ty, sarzak))
+        })
+        .collect();
+    attributes.sort();
+
+    for (i, (name, ty)) in attributes.iter().enumerate() {
+        writeln!(
+            out,
+            r#"<text x="{}" y="{}" fill="black" stroke="none">{}: {}</text>"#,
+            x + FONT_SIZE / 2,
+            rule + LINE_HEIGHT * (i as i64 + 1),
+            escape(name),
+            escape(ty)
+        )
+        .unwrap();
+    }
+}
+
+/// Where an anchor is, which way it faces, and what's drawn there
+struct End {
+    x: i64,
+    y: i64,
+    /// Unit vector pointing into the box
+    dir: (f64, f64),
+    glyph: GlyphEnum,
+    phrase: Option<(String, i64, i64)>,
+}
+
+impl End {
+    fn new(anchor: &Arc<RwLock<Anchor>>, merlin: &MerlinStore) -> Self {
+        let anchor = anchor.read().unwrap();
+        let point = anchor.r6_point(merlin)[0].clone();
+        let point = point.read().unwrap();
+        let glyph = anchor.r10_glyph(merlin)[0].read().unwrap().subtype.clone();
+        let edge = *anchor.r9_edge(merlin)[0].read().unwrap();
+        let dir = match edge {
+            Edge::Top(_) => (0.0, 1.0),
+            Edge::Bottom(_) => (0.0, -1.0),
+            Edge::Left(_) => (1.0, 0.0),
+            Edge::Right(_) => (-1.0, 0.0),
+        };
+        let phrase = anchor.r13c_relationship_phrase(merlin).first().map(|p| {
+            let p = p.read().unwrap();
+            (p.text.clone(), p.x, p.y)
+        });
+
+        Self {
+            x: point.x,
+            y: point.y,
+            dir,
+            glyph,
+            phrase,
+        }
+    }
+}
+
+/// Draw a relationship
+///
+/// There are three shapes. An Isa is drawn from the supertype to each
+/// subtype. An associative is a line between the two referents, with a dashed
+/// line from the associative object to the inflection point. Anything else is
+/// a line between the two ends.
+fn draw_line(line: &Line, merlin: &MerlinStore, out: &mut String) {
+    let ends: Vec<End> = line
+        .r3_anchor(merlin)
+        .iter()
+        .map(|anchor| End::new(anchor, merlin))
+        .collect();
+    let inflection = line
+        .r4_line_segment(merlin)
+        .iter()
+        .flat_map(|seg| seg.read().unwrap().r5_line_segment_point(merlin))
+        .map(|lsp| lsp.read().unwrap().r5_point(merlin)[0].clone())
+        .find(|p| matches!(p.read().unwrap().subtype, PointEnum::Inflection(_)))
+        .map(|p| {
+            let p = p.read().unwrap();
+            (p.x, p.y)
+        });
+
+    let supertype = ends
+        .iter()
+        .find(|end| matches!(end.glyph, GlyphEnum::ZSuper(_)));
+
+    let name_at = if let Some(sup) = supertype {
+        for end in ends
+            .iter()
+            .filter(|end| !matches!(end.glyph, GlyphEnum::ZSuper(_)))
+        {
+            segment((sup.x, sup.y), (end.x, end.y), false, out);
+        }
+        ends.iter()
+            .find(|end| !matches!(end.glyph, GlyphEnum::ZSuper(_)))
+            .map(|end| midpoint((sup.x, sup.y), (end.x, end.y)))
+    } else if let Some(inflection) = inflection {
+        let (named, middle): (Vec<&End>, Vec<&End>) =
+            ends.iter().partition(|end| end.phrase.is_some());
+        if let [a, b] = named[..] {
+            segment((a.x, a.y), (b.x, b.y), false, out);
+        }
+        for end in middle {
+            segment((end.x, end.y), inflection, true, out);
+        }
+        Some(inflection)
+    } else if let [a, b] = &ends[..] {
+        segment((a.x, a.y), (b.x, b.y), false, out);
+        Some(midpoint((a.x, a.y), (b.x, b.y)))
+    } else {
+        None
+    };
+
+    for end in &ends {
+        draw_glyph(end, out);
+        if let Some((text, dx, dy)) = &end.phrase {
+            label(end.x + dx, end.y + dy, text, out);
+        }
+    }
+
+    if let Some((x, y)) = name_at {
+        for name in line.r11_relationship_name(merlin) {
+            let name = name.read().unwrap();
+            label(x + name.x, y + name.y, &name.text, out);
+        }
+    }
+}
+
+fn midpoint(a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    ((a.0 + b.0) / 2, (a.1 + b.1) / 2)
+}
+
+fn segment(a: (i64, i64), b: (i64, i64), dashed: bool, out: &mut String) {
+    let dash = if dashed {
+        r#" stroke-dasharray="6 4""#
+    } else {
+        ""
+    };
+    writeln!(
+        out,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{dash}/>"#,
+        a.0, a.1, b.0, b.1
+    )
+    .unwrap();
+}
+
+fn label(x: i64, y: i64, text: &str, out: &mut String) {
+    writeln!(
+        out,
+        r#"<text x="{x}" y="{y}" fill="black" stroke="none">{}</text>"#,
+        escape(text)
+    )
+    .unwrap();
+}
+
+/// Draw the glyph at the end of a line
+///
+/// One is a single arrowhead, many is two, and a supertype is an open
+/// triangle. Subtypes don't get anything.
+fn draw_glyph(end: &End, out: &mut String) {
+    let tip = (end.x as f64, end.y as f64);
+    let back = |tip: (f64, f64), by: f64| (tip.0 - end.dir.0 * by, tip.1 - end.dir.1 * by);
+
+    match end.glyph {
+        GlyphEnum::One(_) => arrow(tip, end.dir, ARROW_LENGTH, ARROW_WIDTH, "black", out),
+        GlyphEnum::Many(_) => {
+            arrow(tip, end.dir, ARROW_LENGTH, ARROW_WIDTH, "black", out);
+            let tip = back(tip, ARROW_LENGTH);
+            arrow(tip, end.dir, ARROW_LENGTH, ARROW_WIDTH, "black", out);
+        }
+        GlyphEnum::ZSuper(_) => arrow(
+            tip,
+            end.dir,
+            ARROW_LENGTH * 1.5,
+            ARROW_WIDTH * 1.5,
+            "white",
+            out,
+        ),
+        GlyphEnum::Sub(_) => {}
+    }
+}
+
+fn arrow(tip: (f64, f64), dir: (f64, f64), length: f64, width: f64, fill: &str, out: &mut String) {
+    let base = (tip.0 - dir.0 * length, tip.1 - dir.1 * length);
+    // Perpendicular to the direction of the arrow
+    let (px, py) = (-dir.1 * width, dir.0 * width);
+    writeln!(
+        out,
+        r#"<polygon points="{},{} {},{} {},{}" fill="{fill}"/>"#,
+        tip.0,
+        tip.1,
+        base.0 + px,
+        base.1 + py,
+        base.0 - px,
+        base.1 - py
+    )
+    .unwrap();
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg() {
+        let domain = Domain::from_cuckoo("models/merlin.json").unwrap();
+        let svg = domain.to_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(
+            svg.matches("<rect ").count(),
+            domain.merlin().iter_x_box().count()
+        );
+        assert!(svg.contains(">Anchor</text>"));
+        assert!(svg.contains(">R1</text>"));
+        assert_eq!(svg, domain.to_svg());
+    }
+}
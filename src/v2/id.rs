@@ -0,0 +1,137 @@
+//! Instance Id Generation
+//!
+//! The generated constructors, e.g. `Object::new`, ask the store for the new
+//! instance's id. By default that's a random, version 4, UUID. Building the
+//! same model twice then gives two sets of ids, and the diffs, and the
+//! generated code, are full of noise.
+//!
+//! With [`IdStrategy::Derived`] the store derives a version 5 UUID from a
+//! namespace, usually the domain's `UUID_NS`, and the instance's natural key:
+//! an object's name, an attribute's object and name, a relationship's number.
+//! The same model built in the same order gets the same ids every time.
+use uuid::Uuid;
+
+/// How a store makes ids for new instances
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IdStrategy {
+    /// Random, version 4, UUIDs
+    #[default]
+    Random,
+    /// Version 5 UUIDs, derived from this namespace and the natural key
+    Derived(Uuid),
+}
+
+impl IdStrategy {
+    /// Make an id for an instance in `table`, with natural key `key`
+    ///
+    /// `taken` says if an id is already in use. Natural keys aren't always
+    /// unique, e.g. two referents with the same description on the same
+    /// object, so if the derived id is taken we count up until one isn't. That
+    /// keeps things deterministic so long as the instances are created in the
+    /// same order.
+    pub fn new_id<F>(&self, table: &str, key: &str, taken: F) -> Uuid
+    where
+        F: Fn(&Uuid) -> bool,
+    {
+        match self {
+            Self::Random => Uuid::new_v4(),
+            Self::Derived(namespace) => {
+                let name = format!("{table}::{key}");
+                let mut id = Uuid::new_v5(namespace, name.as_bytes());
+                let mut count = 1;
+                while taken(&id) {
+                    count += 1;
+                    id = Uuid::new_v5(namespace, format!("{name}#{count}").as_bytes());
+                }
+
+                id
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::{
+        store::ObjectStore, Attribute, Binary, Object, Referent, Referrer, CONDITIONAL, MANY, ONE,
+        UNCONDITIONAL, UUID_NS, Z_STRING,
+    };
+
+    /// Build a two object model, and return all of the ids
+    fn build(strategy: IdStrategy) -> Vec<Uuid> {
+        let mut store = ObjectStore::new();
+        store.set_id_strategy(strategy);
+
+        let dog = Object::new(
+            "".to_owned(),
+            "DOG".to_owned(),
+            "Dog".to_owned(),
+            &mut store,
+        );
+        let owner = Object::new(
+            "".to_owned(),
+            "OWN".to_owned(),
+            "Owner".to_owned(),
+            &mut store,
+        );
+        let string = store.exhume_ty(&Z_STRING).unwrap();
+        let name = Attribute::new("name".to_owned(), &dog, &string, &mut store);
+        let other = Attribute::new("name".to_owned(), &owner, &string, &mut store);
+
+        let one = store.exhume_cardinality(&ONE).unwrap();
+        let many = store.exhume_cardinality(&MANY).unwrap();
+        let conditional = store.exhume_conditionality(&CONDITIONAL).unwrap();
+        let unconditional = store.exhume_conditionality(&UNCONDITIONAL).unwrap();
+        let from = Referrer::new(
+            "".to_owned(),
+            "owner_id".to_owned(),
+            &many,
+            &conditional,
+            &dog,
+            &mut store,
+        );
+        let to = Referent::new("".to_owned(), &one, &unconditional, &owner, &mut store);
+        // Same key as `to`
+        let again = Referent::new("".to_owned(), &one, &unconditional, &owner, &mut store);
+        let binary = Binary::new(1, &from, &to, &mut store);
+
+        let ids = vec![
+            dog.read().unwrap().id,
+            owner.read().unwrap().id,
+            name.read().unwrap().id,
+            other.read().unwrap().id,
+            from.read().unwrap().id,
+            to.read().unwrap().id,
+            again.read().unwrap().id,
+            binary.read().unwrap().id,
+        ];
+
+        ids
+    }
+
+    #[test]
+    fn test_derived() {
+        let first = build(IdStrategy::Derived(UUID_NS));
+        assert_eq!(first, build(IdStrategy::Derived(UUID_NS)));
+        assert!(first.iter().all(|id| id.get_version_num() == 5));
+
+        // Same key, different instance
+        assert_ne!(first[5], first[6]);
+        // Same name, different object
+        assert_ne!(first[2], first[3]);
+
+        let other = build(IdStrategy::Derived(Uuid::new_v4()));
+        assert!(first.iter().zip(&other).all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_random() {
+        let first = build(IdStrategy::Random);
+        assert!(first.iter().all(|id| id.get_version_num() == 4));
+        assert!(first
+            .iter()
+            .zip(build(IdStrategy::Random))
+            .all(|(a, b)| *a != b));
+    }
+}
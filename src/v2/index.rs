@@ -0,0 +1,132 @@
+//! Relationship Indexes
+//!
+//! A store keeps one of these for each referential attribute that is
+//! navigated backwards, e.g. from a sarzak `Object` to its `Attribute`s, or
+//! from a merlin `Line` to its `LineSegment`s. Without them every backward
+//! navigation scans the whole table.
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A one to many index from a referenced id to the instances that refer to it
+///
+/// We also remember the key that each instance was filed under. Instances are
+/// often modified in place, and when they are inter'd again the old entry
+/// needs to go.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Index {
+    keys: HashMap<Uuid, Uuid>,
+    ids: HashMap<Uuid, HashSet<Uuid>>,
+}
+
+impl Index {
+    /// File instance `id` under `key`
+    pub(crate) fn insert(&mut self, id: Uuid, key: Uuid) {
+        if let Some(old) = self.keys.insert(id, key) {
+            if old == key {
+                return;
+            }
+            self.unfile(&id, &old);
+        }
+        self.ids.entry(key).or_default().insert(id);
+    }
+
+    /// Forget about instance `id`
+    pub(crate) fn remove(&mut self, id: &Uuid) {
+        if let Some(key) = self.keys.remove(id) {
+            self.unfile(id, &key);
+        }
+    }
+
+    /// The instances filed under `key`
+    pub(crate) fn get<'a>(&'a self, key: &Uuid) -> impl Iterator<Item = &'a Uuid> + 'a {
+        self.ids.get(key).into_iter().flatten()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keys.clear();
+        self.ids.clear();
+    }
+
+    fn unfile(&mut self, id: &Uuid, key: &Uuid) {
+        if let Some(ids) = self.ids.get_mut(key) {
+            ids.remove(id);
+            if ids.is_empty() {
+                self.ids.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::v2::{domain::Domain, sarzak::store::ObjectStore};
+
+    fn scanned(sarzak: &ObjectStore, obj_id: Uuid) -> Vec<Uuid> {
+        let mut ids: Vec<_> = sarzak
+            .iter_attribute()
+            .filter(|a| a.read().unwrap().obj_id == obj_id)
+            .map(|a| a.read().unwrap().id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    fn navigated(sarzak: &ObjectStore, obj_id: Uuid) -> Vec<Uuid> {
+        let object = sarzak.exhume_object(&obj_id).unwrap();
+        let mut ids: Vec<_> = object
+            .read()
+            .unwrap()
+            .r1_attribute(sarzak)
+            .iter()
+            .map(|a| a.read().unwrap().id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+
+        let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+        let state = sarzak.exhume_object_id_by_name("State").unwrap();
+
+        assert!(!navigated(sarzak, object).is_empty());
+        assert_eq!(navigated(sarzak, object), scanned(sarzak, object));
+
+        // Move an attribute, and inter it again
+        let attribute = sarzak.exhume_attribute_by_obj_id(&object)[0].clone();
+        let id = attribute.read().unwrap().id;
+        attribute.write().unwrap().obj_id = state;
+        sarzak.inter_attribute(attribute);
+        assert_eq!(navigated(sarzak, object), scanned(sarzak, object));
+        assert_eq!(navigated(sarzak, state), scanned(sarzak, state));
+        assert!(navigated(sarzak, state).contains(&id));
+
+        sarzak.exorcise_attribute(&id);
+        assert!(!navigated(sarzak, state).contains(&id));
+
+        sarzak.exorcise_object(&object);
+        assert!(sarzak.exhume_object_id_by_name("Object").is_none());
+    }
+
+    #[test]
+    fn test_model_indexes() {
+        let sarzak = ObjectStore::from_bincode(crate::v2::sarzak::MODEL).unwrap();
+
+        let object = sarzak.exhume_object_id_by_name("Object").unwrap();
+        assert!(!navigated(&sarzak, object).is_empty());
+        assert_eq!(navigated(&sarzak, object), scanned(&sarzak, object));
+
+        // The indexes come back after a round trip, too
+        let code = bincode::serialize(&sarzak).unwrap();
+        let sarzak = ObjectStore::from_bincode(&code).unwrap();
+        assert_eq!(navigated(&sarzak, object), scanned(&sarzak, object));
+
+        assert!(ObjectStore::from_bincode(&code[..code.len() / 2]).is_err());
+    }
+}
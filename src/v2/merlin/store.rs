@@ -28,15 +28,36 @@ use std::{
 };
 
 use rustc_hash::FxHashMap as HashMap;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
+use crate::v2::index::Index;
+use crate::v2::notify::{Change, ChangeKind, Subscribers, SubscriptionId};
+use crate::v2::transaction::{Journal, Transaction, Transactional};
+
 use crate::v2::merlin::types::{
     Anchor, Bisection, Edge, Glyph, Line, LineSegment, LineSegmentPoint, Point, RelationshipName,
     RelationshipPhrase, XBox, BOTTOM, LEFT, RIGHT, TOP,
 };
 
+/// The tables in the store, for [`Change`] notifications
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Table {
+    Anchor,
+    Bisection,
+    XBox,
+    Edge,
+    Glyph,
+    Line,
+    LineSegment,
+    LineSegmentPoint,
+    Point,
+    RelationshipName,
+    RelationshipPhrase,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self")]
 pub struct ObjectStore {
     anchor: Arc<RwLock<HashMap<Uuid, Arc<RwLock<Anchor>>>>>,
     bisection: Arc<RwLock<HashMap<Uuid, Arc<RwLock<Bisection>>>>>,
@@ -49,6 +70,49 @@ pub struct ObjectStore {
     point: Arc<RwLock<HashMap<Uuid, Arc<RwLock<Point>>>>>,
     relationship_name: Arc<RwLock<HashMap<Uuid, Arc<RwLock<RelationshipName>>>>>,
     relationship_phrase: Arc<RwLock<HashMap<Uuid, Arc<RwLock<RelationshipPhrase>>>>>,
+    // Indexes for navigating backwards across relationships. They aren't
+    // serialized, they are rebuilt from the tables when the store is loaded.
+    #[serde(skip)]
+    anchor_by_x_box: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    anchor_by_line: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    bisection_by_segment: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    x_box_by_object: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    glyph_by_line: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    line_by_relationship: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    line_segment_by_line: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    line_segment_point_by_segment: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    line_segment_point_by_point: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    relationship_name_by_line: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    relationship_phrase_by_line: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    subscribers: Subscribers<Table>,
+    #[serde(skip)]
+    journal: Journal<ObjectStore, Table>,
+}
+
+impl Serialize for ObjectStore {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ObjectStore::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectStore {
+    /// Deserialize the tables, and rebuild the indexes from them
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let store = ObjectStore::deserialize(deserializer)?;
+        store.reindex();
+        Ok(store)
+    }
 }
 
 impl ObjectStore {
@@ -65,6 +129,19 @@ impl ObjectStore {
             point: Arc::new(RwLock::new(HashMap::default())),
             relationship_name: Arc::new(RwLock::new(HashMap::default())),
             relationship_phrase: Arc::new(RwLock::new(HashMap::default())),
+            anchor_by_x_box: Arc::new(RwLock::new(Index::default())),
+            anchor_by_line: Arc::new(RwLock::new(Index::default())),
+            bisection_by_segment: Arc::new(RwLock::new(Index::default())),
+            x_box_by_object: Arc::new(RwLock::new(Index::default())),
+            glyph_by_line: Arc::new(RwLock::new(Index::default())),
+            line_by_relationship: Arc::new(RwLock::new(Index::default())),
+            line_segment_by_line: Arc::new(RwLock::new(Index::default())),
+            line_segment_point_by_segment: Arc::new(RwLock::new(Index::default())),
+            line_segment_point_by_point: Arc::new(RwLock::new(Index::default())),
+            relationship_name_by_line: Arc::new(RwLock::new(Index::default())),
+            relationship_phrase_by_line: Arc::new(RwLock::new(Index::default())),
+            subscribers: Subscribers::default(),
+            journal: Journal::default(),
         };
 
         // Initialize Singleton Subtypes
@@ -79,12 +156,64 @@ impl ObjectStore {
         store
     }
 
+    /// Start a [`Transaction`] on the store
+    ///
+    pub fn transaction(&mut self) -> Transaction<'_, Self> {
+        Transaction::new(self)
+    }
+
+    /// Call `callback` whenever an instance is inter'd or exorcised
+    ///
+    pub fn subscribe<F>(&self, callback: F) -> SubscriptionId
+    where
+        F: Fn(&Change<Table>) + Send + Sync + 'static,
+    {
+        self.subscribers.subscribe(callback)
+    }
+
+    /// Stop calling a subscriber
+    ///
+    /// Returns `false` if there was no such subscriber.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        self.subscribers.unsubscribe(id)
+    }
+
+    fn changed(&self, change: Change<Table>) {
+        if let Some(change) = self.journal.defer(change) {
+            self.subscribers.notify(change);
+        }
+    }
+
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"v2::merlin-object-store-methods"}}}
     /// Inter (insert) [`Anchor`] into the store.
     ///
     pub fn inter_anchor(&mut self, anchor: Arc<RwLock<Anchor>>) {
         let read = anchor.read().unwrap();
-        self.anchor.write().unwrap().insert(read.id, anchor.clone());
+        let kind = if self.anchor.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.anchor_by_x_box
+            .write()
+            .unwrap()
+            .insert(read.id, read.x_box);
+        self.anchor_by_line
+            .write()
+            .unwrap()
+            .insert(read.id, read.line);
+        let old = self.anchor.write().unwrap().insert(read.id, anchor.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_anchor(old),
+            None => drop(store.exorcise_anchor(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Anchor,
+            id,
+        });
     }
 
     /// Exhume (get) [`Anchor`] from the store.
@@ -100,11 +229,49 @@ impl ObjectStore {
     /// Exorcise (remove) [`Anchor`] from the store.
     ///
     pub fn exorcise_anchor(&mut self, id: &Uuid) -> Option<Arc<RwLock<Anchor>>> {
-        self.anchor
+        self.anchor_by_x_box.write().unwrap().remove(id);
+        self.anchor_by_line.write().unwrap().remove(id);
+        let removed = self
+            .anchor
             .write()
             .unwrap()
             .remove(id)
-            .map(|anchor| anchor.clone())
+            .map(|anchor| anchor.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_anchor(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Anchor,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Anchor`] instances that refer to `x_box`.
+    ///
+    pub fn exhume_anchor_by_x_box(&self, x_box: &Uuid) -> Vec<Arc<RwLock<Anchor>>> {
+        let table = self.anchor.read().unwrap();
+        self.anchor_by_x_box
+            .read()
+            .unwrap()
+            .get(x_box)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
+    }
+
+    /// Exhume the [`Anchor`] instances that refer to `line`.
+    ///
+    pub fn exhume_anchor_by_line(&self, line: &Uuid) -> Vec<Arc<RwLock<Anchor>>> {
+        let table = self.anchor.read().unwrap();
+        self.anchor_by_line
+            .read()
+            .unwrap()
+            .get(line)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Anchor>`.
@@ -125,10 +292,31 @@ impl ObjectStore {
     ///
     pub fn inter_bisection(&mut self, bisection: Arc<RwLock<Bisection>>) {
         let read = bisection.read().unwrap();
-        self.bisection
+        let kind = if self.bisection.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.bisection_by_segment
+            .write()
+            .unwrap()
+            .insert(read.id, read.segment);
+        let old = self
+            .bisection
             .write()
             .unwrap()
             .insert(read.id, bisection.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_bisection(old),
+            None => drop(store.exorcise_bisection(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Bisection,
+            id,
+        });
     }
 
     /// Exhume (get) [`Bisection`] from the store.
@@ -144,11 +332,36 @@ impl ObjectStore {
     /// Exorcise (remove) [`Bisection`] from the store.
     ///
     pub fn exorcise_bisection(&mut self, id: &Uuid) -> Option<Arc<RwLock<Bisection>>> {
-        self.bisection
+        self.bisection_by_segment.write().unwrap().remove(id);
+        let removed = self
+            .bisection
             .write()
             .unwrap()
             .remove(id)
-            .map(|bisection| bisection.clone())
+            .map(|bisection| bisection.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_bisection(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Bisection,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Bisection`] instances that refer to `segment`.
+    ///
+    pub fn exhume_bisection_by_segment(&self, segment: &Uuid) -> Vec<Arc<RwLock<Bisection>>> {
+        let table = self.bisection.read().unwrap();
+        self.bisection_by_segment
+            .read()
+            .unwrap()
+            .get(segment)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Bisection>`.
@@ -169,7 +382,27 @@ impl ObjectStore {
     ///
     pub fn inter_x_box(&mut self, x_box: Arc<RwLock<XBox>>) {
         let read = x_box.read().unwrap();
-        self.x_box.write().unwrap().insert(read.id, x_box.clone());
+        let kind = if self.x_box.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.x_box_by_object
+            .write()
+            .unwrap()
+            .insert(read.id, read.object);
+        let old = self.x_box.write().unwrap().insert(read.id, x_box.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_x_box(old),
+            None => drop(store.exorcise_x_box(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::XBox,
+            id,
+        });
     }
 
     /// Exhume (get) [`XBox`] from the store.
@@ -185,11 +418,35 @@ impl ObjectStore {
     /// Exorcise (remove) [`XBox`] from the store.
     ///
     pub fn exorcise_x_box(&mut self, id: &Uuid) -> Option<Arc<RwLock<XBox>>> {
-        self.x_box
+        self.x_box_by_object.write().unwrap().remove(id);
+        let removed = self
+            .x_box
             .write()
             .unwrap()
             .remove(id)
-            .map(|x_box| x_box.clone())
+            .map(|x_box| x_box.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_x_box(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::XBox,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`XBox`] instances that refer to `object`.
+    ///
+    pub fn exhume_x_box_by_object(&self, object: &Uuid) -> Vec<Arc<RwLock<XBox>>> {
+        let table = self.x_box.read().unwrap();
+        self.x_box_by_object
+            .read()
+            .unwrap()
+            .get(object)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, XBox>`.
@@ -210,7 +467,23 @@ impl ObjectStore {
     ///
     pub fn inter_edge(&mut self, edge: Arc<RwLock<Edge>>) {
         let read = edge.read().unwrap();
-        self.edge.write().unwrap().insert(read.id(), edge.clone());
+        let kind = if self.edge.read().unwrap().contains_key(&read.id()) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self.edge.write().unwrap().insert(read.id(), edge.clone());
+        let id = read.id();
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_edge(old),
+            None => drop(store.exorcise_edge(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Edge,
+            id,
+        });
     }
 
     /// Exhume (get) [`Edge`] from the store.
@@ -222,11 +495,22 @@ impl ObjectStore {
     /// Exorcise (remove) [`Edge`] from the store.
     ///
     pub fn exorcise_edge(&mut self, id: &Uuid) -> Option<Arc<RwLock<Edge>>> {
-        self.edge
+        let removed = self
+            .edge
             .write()
             .unwrap()
             .remove(id)
-            .map(|edge| edge.clone())
+            .map(|edge| edge.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_edge(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Edge,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Edge>`.
@@ -247,7 +531,27 @@ impl ObjectStore {
     ///
     pub fn inter_glyph(&mut self, glyph: Arc<RwLock<Glyph>>) {
         let read = glyph.read().unwrap();
-        self.glyph.write().unwrap().insert(read.id, glyph.clone());
+        let kind = if self.glyph.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.glyph_by_line
+            .write()
+            .unwrap()
+            .insert(read.id, read.line);
+        let old = self.glyph.write().unwrap().insert(read.id, glyph.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_glyph(old),
+            None => drop(store.exorcise_glyph(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Glyph,
+            id,
+        });
     }
 
     /// Exhume (get) [`Glyph`] from the store.
@@ -263,11 +567,35 @@ impl ObjectStore {
     /// Exorcise (remove) [`Glyph`] from the store.
     ///
     pub fn exorcise_glyph(&mut self, id: &Uuid) -> Option<Arc<RwLock<Glyph>>> {
-        self.glyph
+        self.glyph_by_line.write().unwrap().remove(id);
+        let removed = self
+            .glyph
             .write()
             .unwrap()
             .remove(id)
-            .map(|glyph| glyph.clone())
+            .map(|glyph| glyph.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_glyph(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Glyph,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Glyph`] instances that refer to `line`.
+    ///
+    pub fn exhume_glyph_by_line(&self, line: &Uuid) -> Vec<Arc<RwLock<Glyph>>> {
+        let table = self.glyph.read().unwrap();
+        self.glyph_by_line
+            .read()
+            .unwrap()
+            .get(line)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Glyph>`.
@@ -288,7 +616,27 @@ impl ObjectStore {
     ///
     pub fn inter_line(&mut self, line: Arc<RwLock<Line>>) {
         let read = line.read().unwrap();
-        self.line.write().unwrap().insert(read.id, line.clone());
+        let kind = if self.line.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.line_by_relationship
+            .write()
+            .unwrap()
+            .insert(read.id, read.relationship);
+        let old = self.line.write().unwrap().insert(read.id, line.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_line(old),
+            None => drop(store.exorcise_line(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Line,
+            id,
+        });
     }
 
     /// Exhume (get) [`Line`] from the store.
@@ -300,11 +648,35 @@ impl ObjectStore {
     /// Exorcise (remove) [`Line`] from the store.
     ///
     pub fn exorcise_line(&mut self, id: &Uuid) -> Option<Arc<RwLock<Line>>> {
-        self.line
+        self.line_by_relationship.write().unwrap().remove(id);
+        let removed = self
+            .line
             .write()
             .unwrap()
             .remove(id)
-            .map(|line| line.clone())
+            .map(|line| line.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_line(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Line,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Line`] instances that refer to `relationship`.
+    ///
+    pub fn exhume_line_by_relationship(&self, relationship: &Uuid) -> Vec<Arc<RwLock<Line>>> {
+        let table = self.line.read().unwrap();
+        self.line_by_relationship
+            .read()
+            .unwrap()
+            .get(relationship)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Line>`.
@@ -325,10 +697,31 @@ impl ObjectStore {
     ///
     pub fn inter_line_segment(&mut self, line_segment: Arc<RwLock<LineSegment>>) {
         let read = line_segment.read().unwrap();
-        self.line_segment
+        let kind = if self.line_segment.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.line_segment_by_line
+            .write()
+            .unwrap()
+            .insert(read.id, read.line);
+        let old = self
+            .line_segment
             .write()
             .unwrap()
             .insert(read.id, line_segment.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_line_segment(old),
+            None => drop(store.exorcise_line_segment(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::LineSegment,
+            id,
+        });
     }
 
     /// Exhume (get) [`LineSegment`] from the store.
@@ -344,11 +737,36 @@ impl ObjectStore {
     /// Exorcise (remove) [`LineSegment`] from the store.
     ///
     pub fn exorcise_line_segment(&mut self, id: &Uuid) -> Option<Arc<RwLock<LineSegment>>> {
-        self.line_segment
+        self.line_segment_by_line.write().unwrap().remove(id);
+        let removed = self
+            .line_segment
             .write()
             .unwrap()
             .remove(id)
-            .map(|line_segment| line_segment.clone())
+            .map(|line_segment| line_segment.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_line_segment(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::LineSegment,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`LineSegment`] instances that refer to `line`.
+    ///
+    pub fn exhume_line_segment_by_line(&self, line: &Uuid) -> Vec<Arc<RwLock<LineSegment>>> {
+        let table = self.line_segment.read().unwrap();
+        self.line_segment_by_line
+            .read()
+            .unwrap()
+            .get(line)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, LineSegment>`.
@@ -369,10 +787,40 @@ impl ObjectStore {
     ///
     pub fn inter_line_segment_point(&mut self, line_segment_point: Arc<RwLock<LineSegmentPoint>>) {
         let read = line_segment_point.read().unwrap();
-        self.line_segment_point
+        let kind = if self
+            .line_segment_point
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.line_segment_point_by_segment
+            .write()
+            .unwrap()
+            .insert(read.id, read.segment);
+        self.line_segment_point_by_point
+            .write()
+            .unwrap()
+            .insert(read.id, read.point);
+        let old = self
+            .line_segment_point
             .write()
             .unwrap()
             .insert(read.id, line_segment_point.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_line_segment_point(old),
+            None => drop(store.exorcise_line_segment_point(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::LineSegmentPoint,
+            id,
+        });
     }
 
     /// Exhume (get) [`LineSegmentPoint`] from the store.
@@ -391,11 +839,58 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<LineSegmentPoint>>> {
-        self.line_segment_point
+        self.line_segment_point_by_segment
+            .write()
+            .unwrap()
+            .remove(id);
+        self.line_segment_point_by_point.write().unwrap().remove(id);
+        let removed = self
+            .line_segment_point
             .write()
             .unwrap()
             .remove(id)
-            .map(|line_segment_point| line_segment_point.clone())
+            .map(|line_segment_point| line_segment_point.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_line_segment_point(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::LineSegmentPoint,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`LineSegmentPoint`] instances that refer to `segment`.
+    ///
+    pub fn exhume_line_segment_point_by_segment(
+        &self,
+        segment: &Uuid,
+    ) -> Vec<Arc<RwLock<LineSegmentPoint>>> {
+        let table = self.line_segment_point.read().unwrap();
+        self.line_segment_point_by_segment
+            .read()
+            .unwrap()
+            .get(segment)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
+    }
+
+    /// Exhume the [`LineSegmentPoint`] instances that refer to `point`.
+    ///
+    pub fn exhume_line_segment_point_by_point(
+        &self,
+        point: &Uuid,
+    ) -> Vec<Arc<RwLock<LineSegmentPoint>>> {
+        let table = self.line_segment_point.read().unwrap();
+        self.line_segment_point_by_point
+            .read()
+            .unwrap()
+            .get(point)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, LineSegmentPoint>`.
@@ -418,7 +913,23 @@ impl ObjectStore {
     ///
     pub fn inter_point(&mut self, point: Arc<RwLock<Point>>) {
         let read = point.read().unwrap();
-        self.point.write().unwrap().insert(read.id, point.clone());
+        let kind = if self.point.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self.point.write().unwrap().insert(read.id, point.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_point(old),
+            None => drop(store.exorcise_point(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Point,
+            id,
+        });
     }
 
     /// Exhume (get) [`Point`] from the store.
@@ -434,11 +945,22 @@ impl ObjectStore {
     /// Exorcise (remove) [`Point`] from the store.
     ///
     pub fn exorcise_point(&mut self, id: &Uuid) -> Option<Arc<RwLock<Point>>> {
-        self.point
+        let removed = self
+            .point
             .write()
             .unwrap()
             .remove(id)
-            .map(|point| point.clone())
+            .map(|point| point.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_point(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Point,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Point>`.
@@ -459,10 +981,36 @@ impl ObjectStore {
     ///
     pub fn inter_relationship_name(&mut self, relationship_name: Arc<RwLock<RelationshipName>>) {
         let read = relationship_name.read().unwrap();
-        self.relationship_name
+        let kind = if self
+            .relationship_name
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.relationship_name_by_line
+            .write()
+            .unwrap()
+            .insert(read.id, read.line);
+        let old = self
+            .relationship_name
             .write()
             .unwrap()
             .insert(read.id, relationship_name.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_relationship_name(old),
+            None => drop(store.exorcise_relationship_name(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::RelationshipName,
+            id,
+        });
     }
 
     /// Exhume (get) [`RelationshipName`] from the store.
@@ -481,11 +1029,39 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<RelationshipName>>> {
-        self.relationship_name
+        self.relationship_name_by_line.write().unwrap().remove(id);
+        let removed = self
+            .relationship_name
             .write()
             .unwrap()
             .remove(id)
-            .map(|relationship_name| relationship_name.clone())
+            .map(|relationship_name| relationship_name.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_relationship_name(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::RelationshipName,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`RelationshipName`] instances that refer to `line`.
+    ///
+    pub fn exhume_relationship_name_by_line(
+        &self,
+        line: &Uuid,
+    ) -> Vec<Arc<RwLock<RelationshipName>>> {
+        let table = self.relationship_name.read().unwrap();
+        self.relationship_name_by_line
+            .read()
+            .unwrap()
+            .get(line)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, RelationshipName>`.
@@ -511,10 +1087,36 @@ impl ObjectStore {
         relationship_phrase: Arc<RwLock<RelationshipPhrase>>,
     ) {
         let read = relationship_phrase.read().unwrap();
-        self.relationship_phrase
+        let kind = if self
+            .relationship_phrase
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.relationship_phrase_by_line
+            .write()
+            .unwrap()
+            .insert(read.id, read.line);
+        let old = self
+            .relationship_phrase
             .write()
             .unwrap()
             .insert(read.id, relationship_phrase.clone());
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some(old) => store.inter_relationship_phrase(old),
+            None => drop(store.exorcise_relationship_phrase(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::RelationshipPhrase,
+            id,
+        });
     }
 
     /// Exhume (get) [`RelationshipPhrase`] from the store.
@@ -533,11 +1135,39 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<RelationshipPhrase>>> {
-        self.relationship_phrase
+        self.relationship_phrase_by_line.write().unwrap().remove(id);
+        let removed = self
+            .relationship_phrase
             .write()
             .unwrap()
             .remove(id)
-            .map(|relationship_phrase| relationship_phrase.clone())
+            .map(|relationship_phrase| relationship_phrase.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_relationship_phrase(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::RelationshipPhrase,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`RelationshipPhrase`] instances that refer to `line`.
+    ///
+    pub fn exhume_relationship_phrase_by_line(
+        &self,
+        line: &Uuid,
+    ) -> Vec<Arc<RwLock<RelationshipPhrase>>> {
+        let table = self.relationship_phrase.read().unwrap();
+        self.relationship_phrase_by_line
+            .read()
+            .unwrap()
+            .get(line)
+            .filter_map(|id| table.get(id).cloned())
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, RelationshipPhrase>`.
@@ -556,6 +1186,92 @@ impl ObjectStore {
         (0..len).map(move |i| values[i].clone())
     }
 
+    /// Rebuild the relationship indexes from the tables
+    fn reindex(&self) {
+        self.anchor_by_x_box.write().unwrap().clear();
+        self.anchor_by_line.write().unwrap().clear();
+        for anchor in self.anchor.read().unwrap().values() {
+            let read = anchor.read().unwrap();
+            self.anchor_by_x_box
+                .write()
+                .unwrap()
+                .insert(read.id, read.x_box);
+            self.anchor_by_line
+                .write()
+                .unwrap()
+                .insert(read.id, read.line);
+        }
+        self.bisection_by_segment.write().unwrap().clear();
+        for bisection in self.bisection.read().unwrap().values() {
+            let read = bisection.read().unwrap();
+            self.bisection_by_segment
+                .write()
+                .unwrap()
+                .insert(read.id, read.segment);
+        }
+        self.x_box_by_object.write().unwrap().clear();
+        for x_box in self.x_box.read().unwrap().values() {
+            let read = x_box.read().unwrap();
+            self.x_box_by_object
+                .write()
+                .unwrap()
+                .insert(read.id, read.object);
+        }
+        self.glyph_by_line.write().unwrap().clear();
+        for glyph in self.glyph.read().unwrap().values() {
+            let read = glyph.read().unwrap();
+            self.glyph_by_line
+                .write()
+                .unwrap()
+                .insert(read.id, read.line);
+        }
+        self.line_by_relationship.write().unwrap().clear();
+        for line in self.line.read().unwrap().values() {
+            let read = line.read().unwrap();
+            self.line_by_relationship
+                .write()
+                .unwrap()
+                .insert(read.id, read.relationship);
+        }
+        self.line_segment_by_line.write().unwrap().clear();
+        for line_segment in self.line_segment.read().unwrap().values() {
+            let read = line_segment.read().unwrap();
+            self.line_segment_by_line
+                .write()
+                .unwrap()
+                .insert(read.id, read.line);
+        }
+        self.line_segment_point_by_segment.write().unwrap().clear();
+        self.line_segment_point_by_point.write().unwrap().clear();
+        for line_segment_point in self.line_segment_point.read().unwrap().values() {
+            let read = line_segment_point.read().unwrap();
+            self.line_segment_point_by_segment
+                .write()
+                .unwrap()
+                .insert(read.id, read.segment);
+            self.line_segment_point_by_point
+                .write()
+                .unwrap()
+                .insert(read.id, read.point);
+        }
+        self.relationship_name_by_line.write().unwrap().clear();
+        for relationship_name in self.relationship_name.read().unwrap().values() {
+            let read = relationship_name.read().unwrap();
+            self.relationship_name_by_line
+                .write()
+                .unwrap()
+                .insert(read.id, read.line);
+        }
+        self.relationship_phrase_by_line.write().unwrap().clear();
+        for relationship_phrase in self.relationship_phrase.read().unwrap().values() {
+            let read = relationship_phrase.read().unwrap();
+            self.relationship_phrase_by_line
+                .write()
+                .unwrap()
+                .insert(read.id, read.line);
+        }
+    }
+
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"v2::merlin-object-store-persistence"}}}
@@ -574,7 +1290,8 @@ impl ObjectStore {
     ///
     /// The store is persisted as a directory of JSON files. The intention
     /// is that this directory can be checked into version control.
-    /// In fact, I intend to add automagic git integration as an option.
+    /// [`Domain::persist_git`](crate::v2::domain::Domain::persist_git) will
+    /// also stage and commit the changes.
     pub fn persist<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
         let path = path.as_ref();
         fs::create_dir_all(path)?;
@@ -734,7 +1451,8 @@ impl ObjectStore {
     ///
     /// The store is persisted as a directory of JSON files. The intention
     /// is that this directory can be checked into version control.
-    /// In fact, I intend to add automagic git integration as an option.
+    /// [`Domain::load_at_commit`](crate::v2::domain::Domain::load_at_commit)
+    /// loads a domain from a local commit.
     pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let path = path.as_ref();
         let path = path.join("merlin.json");
@@ -744,7 +1462,7 @@ impl ObjectStore {
         // Load Anchor.
         {
             let path = path.join("anchor");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -762,7 +1480,7 @@ impl ObjectStore {
         // Load Bisection.
         {
             let path = path.join("bisection");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -780,7 +1498,7 @@ impl ObjectStore {
         // Load Box.
         {
             let path = path.join("x_box");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -798,7 +1516,7 @@ impl ObjectStore {
         // Load Edge.
         {
             let path = path.join("edge");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -816,7 +1534,7 @@ impl ObjectStore {
         // Load Glyph.
         {
             let path = path.join("glyph");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -834,7 +1552,7 @@ impl ObjectStore {
         // Load Line.
         {
             let path = path.join("line");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -852,7 +1570,7 @@ impl ObjectStore {
         // Load Line Segment.
         {
             let path = path.join("line_segment");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -870,7 +1588,7 @@ impl ObjectStore {
         // Load Line Segment Point.
         {
             let path = path.join("line_segment_point");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -888,7 +1606,7 @@ impl ObjectStore {
         // Load Point.
         {
             let path = path.join("point");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -906,7 +1624,7 @@ impl ObjectStore {
         // Load Relationship Name.
         {
             let path = path.join("relationship_name");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -924,7 +1642,7 @@ impl ObjectStore {
         // Load Relationship Phrase.
         {
             let path = path.join("relationship_phrase");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -939,9 +1657,42 @@ impl ObjectStore {
             }
         }
 
+        store.reindex();
+
         Ok(store)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
+
+impl Transactional for ObjectStore {
+    fn begin(&mut self) {
+        self.journal.begin();
+    }
+
+    fn commit(&mut self) {
+        for change in self.journal.commit() {
+            self.subscribers.notify(change);
+        }
+    }
+
+    fn rollback(&mut self) {
+        let journal = self.journal.clone();
+        journal.rollback(self);
+    }
+}
+
+/// Read a table's directory
+///
+/// A table with no instances may not have a directory at all, since git
+/// doesn't keep empty directories.
+fn read_table(path: &Path) -> io::Result<impl Iterator<Item = io::Result<fs::DirEntry>>> {
+    let entries = if path.is_dir() {
+        Some(fs::read_dir(path)?)
+    } else {
+        None
+    };
+
+    Ok(entries.into_iter().flatten())
+}
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
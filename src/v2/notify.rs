@@ -0,0 +1,186 @@
+//! Store Change Notifications
+//!
+//! The stores call back to subscribers whenever an instance is inter'd or
+//! exorcised, so that editors can refresh their views, and compilers can
+//! invalidate their caches, without comparing whole stores.
+//!
+//! Each store has a `Table` enum that says which type of instance changed.
+//! Subscribers are called after the change is made, with no store locks held,
+//! so they are free to look at the store. They are shared between clones of a
+//! store, just like the tables are.
+//!
+//! Changes made in a [`Transaction`](crate::v2::transaction::Transaction) are
+//! held back until it commits.
+//!
+//! Changes made through an instance's lock don't go through the store, and
+//! there is nothing to notify. Inter the instance again to let everyone know.
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use uuid::Uuid;
+
+/// What happened to an instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The instance was inter'd, and wasn't in the store
+    Inserted,
+    /// The instance was inter'd, and replaced one with the same id
+    Updated,
+    /// The instance was exorcised
+    Removed,
+}
+
+/// A change to a store
+///
+/// `T` is the store's `Table` enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Change<T> {
+    pub kind: ChangeKind,
+    pub table: T,
+    pub id: Uuid,
+}
+
+/// Returned by `subscribe`, to pass to `unsubscribe`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(usize);
+
+type Callback<T> = Arc<dyn Fn(&Change<T>) + Send + Sync>;
+
+/// The subscribers to a store
+pub(crate) struct Subscribers<T> {
+    inner: Arc<RwLock<(usize, Vec<(usize, Callback<T>)>)>>,
+}
+
+impl<T> Default for Subscribers<T> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new((0, Vec::new()))),
+        }
+    }
+}
+
+impl<T> Clone for Subscribers<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Subscribers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Subscribers({})", self.inner.read().unwrap().1.len())
+    }
+}
+
+impl<T> Subscribers<T> {
+    pub(crate) fn subscribe<F>(&self, callback: F) -> SubscriptionId
+    where
+        F: Fn(&Change<T>) + Send + Sync + 'static,
+    {
+        let mut inner = self.inner.write().unwrap();
+        let id = inner.0;
+        inner.0 += 1;
+        inner.1.push((id, Arc::new(callback)));
+
+        SubscriptionId(id)
+    }
+
+    pub(crate) fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut inner = self.inner.write().unwrap();
+        let count = inner.1.len();
+        inner.1.retain(|(sub, _)| *sub != id.0);
+
+        inner.1.len() != count
+    }
+
+    pub(crate) fn notify(&self, change: Change<T>) {
+        // Let go of the lock before calling out, so that subscribers can
+        // subscribe and unsubscribe.
+        let callbacks: Vec<Callback<T>> = self
+            .inner
+            .read()
+            .unwrap()
+            .1
+            .iter()
+            .map(|(_, callback)| callback.clone())
+            .collect();
+
+        for callback in callbacks {
+            callback(&change);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::v2::sarzak::{
+        store::{ObjectStore, Table},
+        types::Object,
+    };
+
+    #[test]
+    fn test_notifications() {
+        let mut store = ObjectStore::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let sub = {
+            let seen = seen.clone();
+            store.subscribe(move |change| seen.lock().unwrap().push(*change))
+        };
+
+        let object = Object::new("".to_owned(), "F".to_owned(), "Foo".to_owned(), &mut store);
+        let id = object.read().unwrap().id;
+        store.inter_object(object);
+        store.exorcise_object(&id);
+        // Nothing to remove
+        store.exorcise_object(&id);
+
+        let change = |kind| Change {
+            kind,
+            table: Table::Object,
+            id,
+        };
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                change(ChangeKind::Inserted),
+                change(ChangeKind::Updated),
+                change(ChangeKind::Removed)
+            ]
+        );
+
+        // A rolled back transaction reports nothing, and a committed one
+        // reports each instance once.
+        seen.lock().unwrap().clear();
+        let mut tx = store.transaction();
+        Object::new("".to_owned(), "F".to_owned(), "Foo".to_owned(), &mut tx);
+        tx.rollback();
+        assert!(seen.lock().unwrap().is_empty());
+
+        let mut tx = store.transaction();
+        let object = Object::new("".to_owned(), "F".to_owned(), "Foo".to_owned(), &mut tx);
+        let id = object.read().unwrap().id;
+        tx.inter_object(object);
+        assert!(seen.lock().unwrap().is_empty());
+        tx.commit();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [Change {
+                id,
+                ..change(ChangeKind::Inserted)
+            }]
+        );
+
+        assert!(store.unsubscribe(sub));
+        assert!(!store.unsubscribe(sub));
+        seen.lock().unwrap().clear();
+        Object::new("".to_owned(), "F".to_owned(), "Foo".to_owned(), &mut store);
+        assert!(seen.lock().unwrap().is_empty());
+    }
+}
@@ -3,6 +3,9 @@
 use ::uuid::{uuid, Uuid};
 
 pub mod from;
+pub mod lint;
+pub mod machine;
+pub mod state_model;
 pub mod store;
 pub mod types;
 
@@ -0,0 +1,660 @@
+//! Model Validation
+//!
+//! Nothing stops one from building an [`ObjectStore`] that doesn't make sense.
+//! A referrer may point at an object that isn't there, two relationships may
+//! share a number, etc. This module looks for those problems.
+//!
+//! Each problem is reported as a [`Diagnostic`], which carries the id of the
+//! offending instance, so that it may be found in the store.
+use std::fmt;
+
+use heck::ToUpperCamelCase;
+use rustc_hash::FxHashMap as HashMap;
+use uuid::Uuid;
+
+use crate::v2::sarzak::{
+    store::ObjectStore,
+    types::{Relationship, TransitionEnum, Ty},
+};
+
+/// A problem found in a model
+///
+/// The `id` is always the id of the instance that has the problem.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Diagnostic {
+    /// `id` refers to `target` via `field`, and `target` doesn't exist.
+    ///
+    /// `kind` is the type of the instance that holds the reference.
+    DanglingReference {
+        id: Uuid,
+        kind: &'static str,
+        field: &'static str,
+        target: Uuid,
+    },
+    /// More than one relationship is using the same number.
+    DuplicateRelationshipNumber { id: Uuid, number: i64, other: Uuid },
+    /// An Isa relationship with no subtypes.
+    EmptyIsa { id: Uuid, number: i64 },
+    /// A referential attribute has the same name as an attribute on the
+    /// referring object.
+    ReferentialAttributeCollision {
+        id: Uuid,
+        name: String,
+        attribute: Uuid,
+    },
+    /// More than one object has the same name.
+    ///
+    /// Names are compared as they are used in generated code, so `foo_bar`
+    /// and `FooBar` are duplicates.
+    DuplicateObjectName { id: Uuid, name: String, other: Uuid },
+    /// More than one object has the same key letters.
+    DuplicateKeyLetters {
+        id: Uuid,
+        key_letters: String,
+        other: Uuid,
+    },
+}
+
+impl Diagnostic {
+    /// Return the id of the offending instance
+    ///
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::DanglingReference { id, .. } => *id,
+            Self::DuplicateRelationshipNumber { id, .. } => *id,
+            Self::EmptyIsa { id, .. } => *id,
+            Self::ReferentialAttributeCollision { id, .. } => *id,
+            Self::DuplicateObjectName { id, .. } => *id,
+            Self::DuplicateKeyLetters { id, .. } => *id,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DanglingReference {
+                id,
+                kind,
+                field,
+                target,
+            } => write!(f, "{kind} {id}: `{field}` refers to missing {target}"),
+            Self::DuplicateRelationshipNumber { id, number, other } => {
+                write!(f, "relationship {id}: R{number} is also used by {other}")
+            }
+            Self::EmptyIsa { id, number } => write!(f, "isa {id}: R{number} has no subtypes"),
+            Self::ReferentialAttributeCollision {
+                id,
+                name,
+                attribute,
+            } => write!(
+                f,
+                "{id}: referential attribute `{name}` collides with attribute {attribute}"
+            ),
+            Self::DuplicateObjectName { id, name, other } => {
+                write!(f, "object {id}: name `{name}` is also used by {other}")
+            }
+            Self::DuplicateKeyLetters {
+                id,
+                key_letters,
+                other,
+            } => write!(
+                f,
+                "object {id}: key letters `{key_letters}` are also used by {other}"
+            ),
+        }
+    }
+}
+
+/// Validate a model
+///
+/// Returns a list of everything that we found wrong. An empty list means that
+/// the model is well formed, as far as we can tell.
+///
+/// The diagnostics are sorted by the id of the offending instance, so that the
+/// output is stable.
+pub fn lint(store: &ObjectStore) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    dangling_references(store, &mut diagnostics);
+    relationship_numbers(store, &mut diagnostics);
+    empty_isas(store, &mut diagnostics);
+    referential_attributes(store, &mut diagnostics);
+    object_names(store, &mut diagnostics);
+
+    diagnostics.sort_by_key(|d| (d.id(), d.to_string()));
+    diagnostics
+}
+
+fn dangling_references(store: &ObjectStore, diagnostics: &mut Vec<Diagnostic>) {
+    // Each reference is a field name, the id it refers to, and whether that id
+    // was found.
+    let mut check = |kind: &'static str, id: Uuid, refs: &[(&'static str, Uuid, bool)]| {
+        for (field, target, present) in refs {
+            if !present {
+                diagnostics.push(Diagnostic::DanglingReference {
+                    id,
+                    kind,
+                    field,
+                    target: *target,
+                });
+            }
+        }
+    };
+    let object = |id: &Uuid| store.exhume_object(id).is_some();
+    let cardinality = |id: &Uuid| store.exhume_cardinality(id).is_some();
+    let conditionality = |id: &Uuid| store.exhume_conditionality(id).is_some();
+
+    for attr in store.iter_attribute() {
+        let a = attr.read().unwrap();
+        check(
+            "attribute",
+            a.id,
+            &[
+                ("obj_id", a.obj_id, object(&a.obj_id)),
+                ("ty", a.ty, store.exhume_ty(&a.ty).is_some()),
+            ],
+        );
+    }
+
+    // An object's or an enumeration's type has its id.
+    for ty in store.iter_ty() {
+        let ty = *ty.read().unwrap();
+        match ty {
+            Ty::Object(id) => check("ty", ty.id(), &[("object", id, object(&id))]),
+            Ty::Enumeration(id) => check(
+                "ty",
+                ty.id(),
+                &[("enumeration", id, store.exhume_enumeration(&id).is_some())],
+            ),
+            _ => {}
+        }
+    }
+
+    for referrer in store.iter_referrer() {
+        let r = referrer.read().unwrap();
+        check(
+            "referrer",
+            r.id,
+            &[
+                ("obj_id", r.obj_id, object(&r.obj_id)),
+                ("cardinality", r.cardinality, cardinality(&r.cardinality)),
+                (
+                    "conditionality",
+                    r.conditionality,
+                    conditionality(&r.conditionality),
+                ),
+            ],
+        );
+    }
+
+    for referent in store.iter_referent() {
+        let r = referent.read().unwrap();
+        check(
+            "referent",
+            r.id,
+            &[
+                ("obj_id", r.obj_id, object(&r.obj_id)),
+                ("cardinality", r.cardinality, cardinality(&r.cardinality)),
+                (
+                    "conditionality",
+                    r.conditionality,
+                    conditionality(&r.conditionality),
+                ),
+            ],
+        );
+    }
+
+    for referrer in store.iter_associative_referrer() {
+        let r = referrer.read().unwrap();
+        check(
+            "associative referrer",
+            r.id,
+            &[
+                ("obj_id", r.obj_id, object(&r.obj_id)),
+                ("cardinality", r.cardinality, cardinality(&r.cardinality)),
+            ],
+        );
+    }
+
+    for referent in store.iter_associative_referent() {
+        let r = referent.read().unwrap();
+        check(
+            "associative referent",
+            r.id,
+            &[
+                ("obj_id", r.obj_id, object(&r.obj_id)),
+                ("cardinality", r.cardinality, cardinality(&r.cardinality)),
+                (
+                    "conditionality",
+                    r.conditionality,
+                    conditionality(&r.conditionality),
+                ),
+            ],
+        );
+    }
+
+    for an in store.iter_an_associative_referent() {
+        let a = an.read().unwrap();
+        check(
+            "an associative referent",
+            a.id,
+            &[
+                (
+                    "associative",
+                    a.associative,
+                    store.exhume_associative(&a.associative).is_some(),
+                ),
+                (
+                    "referent",
+                    a.referent,
+                    store.exhume_associative_referent(&a.referent).is_some(),
+                ),
+            ],
+        );
+    }
+
+    for binary in store.iter_binary() {
+        let b = binary.read().unwrap();
+        check(
+            "binary",
+            b.id,
+            &[
+                ("from", b.from, store.exhume_referrer(&b.from).is_some()),
+                ("to", b.to, store.exhume_referent(&b.to).is_some()),
+            ],
+        );
+    }
+
+    for assoc in store.iter_associative() {
+        let a = assoc.read().unwrap();
+        let present = store.exhume_associative_referrer(&a.from).is_some();
+        check("associative", a.id, &[("from", a.from, present)]);
+    }
+
+    for isa in store.iter_isa() {
+        let i = isa.read().unwrap();
+        let present = store.exhume_supertype(&i.supertype).is_some();
+        check("isa", i.id, &[("supertype", i.supertype, present)]);
+    }
+
+    for supertype in store.iter_supertype() {
+        let s = supertype.read().unwrap();
+        check(
+            "supertype",
+            s.id,
+            &[("obj_id", s.obj_id, object(&s.obj_id))],
+        );
+    }
+
+    for subtype in store.iter_subtype() {
+        let s = subtype.read().unwrap();
+        check(
+            "subtype",
+            s.id,
+            &[
+                ("obj_id", s.obj_id, object(&s.obj_id)),
+                ("isa", s.isa, store.exhume_isa(&s.isa).is_some()),
+            ],
+        );
+    }
+
+    for parameter in store.iter_parameter() {
+        let p = parameter.read().unwrap();
+        check(
+            "parameter",
+            p.id,
+            &[
+                ("event", p.event, store.exhume_event(&p.event).is_some()),
+                ("ty", p.ty, store.exhume_ty(&p.ty).is_some()),
+            ],
+        );
+    }
+
+    for enumerator in store.iter_enumerator() {
+        let e = enumerator.read().unwrap();
+        check(
+            "enumerator",
+            e.id,
+            &[(
+                "enumeration",
+                e.enumeration,
+                store.exhume_enumeration(&e.enumeration).is_some(),
+            )],
+        );
+    }
+
+    for operation in store.iter_operation() {
+        let o = operation.read().unwrap();
+        let mut refs = vec![("obj_id", o.obj_id, object(&o.obj_id))];
+        if let Some(ret) = o.ret {
+            refs.push(("ret", ret, store.exhume_ty(&ret).is_some()));
+        }
+        check("operation", o.id, &refs);
+    }
+
+    for argument in store.iter_argument() {
+        let a = argument.read().unwrap();
+        check(
+            "argument",
+            a.id,
+            &[
+                (
+                    "operation",
+                    a.operation,
+                    store.exhume_operation(&a.operation).is_some(),
+                ),
+                ("ty", a.ty, store.exhume_ty(&a.ty).is_some()),
+            ],
+        );
+    }
+
+    for state in store.iter_state() {
+        let s = state.read().unwrap();
+        check("state", s.id, &[("obj_id", s.obj_id, object(&s.obj_id))]);
+    }
+
+    for event in store.iter_event() {
+        let e = event.read().unwrap();
+        check("event", e.id, &[("obj_id", e.obj_id, object(&e.obj_id))]);
+    }
+
+    for acknowledged_event in store.iter_acknowledged_event() {
+        let a = acknowledged_event.read().unwrap();
+        check(
+            "acknowledged event",
+            a.id,
+            &[
+                (
+                    "state_id",
+                    a.state_id,
+                    store.exhume_state(&a.state_id).is_some(),
+                ),
+                (
+                    "event_id",
+                    a.event_id,
+                    store.exhume_event(&a.event_id).is_some(),
+                ),
+            ],
+        );
+    }
+
+    for transition in store.iter_transition() {
+        let t = transition.read().unwrap();
+        let mut refs = vec![
+            ("from", t.from, store.exhume_state(&t.from).is_some()),
+            ("event", t.event, store.exhume_event(&t.event).is_some()),
+        ];
+        if let TransitionEnum::NextState(id) = t.subtype {
+            refs.push(("next state", id, store.exhume_next_state(&id).is_some()));
+        }
+        check("transition", t.id, &refs);
+    }
+
+    for next_state in store.iter_next_state() {
+        let n = next_state.read().unwrap();
+        check(
+            "next state",
+            n.id,
+            &[("to", n.to, store.exhume_state(&n.to).is_some())],
+        );
+    }
+
+    for rel in store.iter_relationship() {
+        let rel = *rel.read().unwrap();
+        let (field, present) = match rel {
+            Relationship::Binary(ref id) => ("binary", store.exhume_binary(id).is_some()),
+            Relationship::Isa(ref id) => ("isa", store.exhume_isa(id).is_some()),
+            Relationship::Associative(ref id) => {
+                ("associative", store.exhume_associative(id).is_some())
+            }
+        };
+        check("relationship", rel.id(), &[(field, rel.id(), present)]);
+    }
+}
+
+fn relationship_numbers(store: &ObjectStore, diagnostics: &mut Vec<Diagnostic>) {
+    let binary = store.iter_binary().map(|b| {
+        let b = b.read().unwrap();
+        (b.number, b.id)
+    });
+    let isa = store.iter_isa().map(|i| {
+        let i = i.read().unwrap();
+        (i.number, i.id)
+    });
+    let associative = store.iter_associative().map(|a| {
+        let a = a.read().unwrap();
+        (a.number, a.id)
+    });
+
+    let mut numbers: Vec<(i64, Uuid)> = binary.chain(isa).chain(associative).collect();
+    numbers.sort();
+
+    // The first one to use a number gets it, everyone else is a duplicate.
+    for pair in numbers.windows(2) {
+        let ((first, other), (second, id)) = (pair[0], pair[1]);
+        if first == second {
+            diagnostics.push(Diagnostic::DuplicateRelationshipNumber {
+                id,
+                number: second,
+                other,
+            });
+        }
+    }
+}
+
+fn empty_isas(store: &ObjectStore, diagnostics: &mut Vec<Diagnostic>) {
+    for isa in store.iter_isa() {
+        let isa = isa.read().unwrap();
+        if store.exhume_subtype_by_isa(&isa.id).is_empty() {
+            diagnostics.push(Diagnostic::EmptyIsa {
+                id: isa.id,
+                number: isa.number,
+            });
+        }
+    }
+}
+
+fn referential_attributes(store: &ObjectStore, diagnostics: &mut Vec<Diagnostic>) {
+    let attribute_named = |obj_id: &Uuid, name: &str| {
+        store
+            .exhume_attribute_by_obj_id(obj_id)
+            .into_iter()
+            .find_map(|attr| {
+                let attr = attr.read().unwrap();
+                (attr.name == name).then_some(attr.id)
+            })
+    };
+
+    for referrer in store.iter_referrer() {
+        let referrer = referrer.read().unwrap();
+        if let Some(attribute) = attribute_named(&referrer.obj_id, &referrer.referential_attribute)
+        {
+            diagnostics.push(Diagnostic::ReferentialAttributeCollision {
+                id: referrer.id,
+                name: referrer.referential_attribute.clone(),
+                attribute,
+            });
+        }
+    }
+
+    for an in store.iter_an_associative_referent() {
+        let an = an.read().unwrap();
+        let Some(assoc) = store.exhume_associative(&an.associative) else {
+            continue;
+        };
+        let Some(referrer) = store.exhume_associative_referrer(&assoc.read().unwrap().from) else {
+            continue;
+        };
+        let obj_id = referrer.read().unwrap().obj_id;
+        if let Some(attribute) = attribute_named(&obj_id, &an.referential_attribute) {
+            diagnostics.push(Diagnostic::ReferentialAttributeCollision {
+                id: an.id,
+                name: an.referential_attribute.clone(),
+                attribute,
+            });
+        }
+    }
+}
+
+fn object_names(store: &ObjectStore, diagnostics: &mut Vec<Diagnostic>) {
+    let mut objects: Vec<_> = store
+        .iter_object()
+        .map(|obj| obj.read().unwrap().clone())
+        .collect();
+    objects.sort_by_key(|obj| obj.id);
+
+    let mut names: HashMap<String, Uuid> = HashMap::default();
+    let mut key_letters: HashMap<String, Uuid> = HashMap::default();
+    for obj in objects {
+        if let Some(other) = names.get(&obj.name.to_upper_camel_case()) {
+            diagnostics.push(Diagnostic::DuplicateObjectName {
+                id: obj.id,
+                name: obj.name.clone(),
+                other: *other,
+            });
+        } else {
+            names.insert(obj.name.to_upper_camel_case(), obj.id);
+        }
+
+        if let Some(other) = key_letters.get(&obj.key_letters) {
+            diagnostics.push(Diagnostic::DuplicateKeyLetters {
+                id: obj.id,
+                key_letters: obj.key_letters.clone(),
+                other: *other,
+            });
+        } else {
+            key_letters.insert(obj.key_letters.clone(), obj.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::v2::sarzak::types::{
+        AcknowledgedEvent, Attribute, Binary, Event, Isa, Object, Referent, Referrer, State,
+        Supertype, CONDITIONAL, ONE, Z_UUID,
+    };
+
+    fn object(name: &str, key_letters: &str, store: &mut ObjectStore) -> Uuid {
+        let obj = Object::new(
+            "".to_owned(),
+            key_letters.to_owned(),
+            name.to_owned(),
+            store,
+        );
+        let id = obj.read().unwrap().id;
+
+        id
+    }
+
+    #[test]
+    fn test_clean_store() {
+        let mut store = ObjectStore::new();
+        object("Foo", "FOO", &mut store);
+        object("Bar", "BAR", &mut store);
+
+        assert!(lint(&store).is_empty());
+    }
+
+    #[test]
+    fn test_lint() {
+        let mut store = ObjectStore::new();
+        let foo = object("Foo", "FOO", &mut store);
+        object("foo", "FOO", &mut store);
+        let missing = Uuid::new_v4();
+
+        let referrer = Referrer::new(
+            "".to_owned(),
+            "id".to_owned(),
+            &store.exhume_cardinality(&ONE).unwrap(),
+            &store.exhume_conditionality(&CONDITIONAL).unwrap(),
+            &store.exhume_object(&foo).unwrap(),
+            &mut store,
+        );
+        let collides = Attribute::new(
+            "id".to_owned(),
+            &store.exhume_object(&foo).unwrap(),
+            &store.exhume_ty(&Z_UUID).unwrap(),
+            &mut store,
+        );
+        let referent = Arc::new(RwLock::new(Referent {
+            description: "".to_owned(),
+            id: Uuid::new_v4(),
+            cardinality: ONE,
+            conditionality: CONDITIONAL,
+            obj_id: missing,
+        }));
+        store.inter_referent(referent.clone());
+        let binary = Binary::new(1, &referrer, &referent, &mut store);
+
+        let supertype = Supertype::new(&store.exhume_object(&foo).unwrap(), &mut store);
+        let isa = Isa::new(1, &supertype, &mut store);
+
+        // The type of, and the state machine of, an object that isn't there
+        let gone = Arc::new(RwLock::new(Object {
+            description: "".to_owned(),
+            id: missing,
+            key_letters: "GONE".to_owned(),
+            name: "Gone".to_owned(),
+        }));
+        Ty::new_object(&gone, &mut store);
+        let state = State::new("idle".to_owned(), &gone, &mut store);
+        let event = Event::new("start".to_owned(), &gone, &mut store);
+        let acknowledged = AcknowledgedEvent::new(&event, &state, &mut store);
+        let event = event.read().unwrap().id;
+        store.exorcise_event(&event);
+
+        let diagnostics = lint(&store);
+        let referent = referent.read().unwrap().id;
+        let referrer = referrer.read().unwrap().id;
+        let (binary, isa) = (binary.read().unwrap().id, isa.read().unwrap().id);
+        let collides = collides.read().unwrap().id;
+
+        assert!(diagnostics.contains(&Diagnostic::DanglingReference {
+            id: referent,
+            kind: "referent",
+            field: "obj_id",
+            target: missing,
+        }));
+        assert!(diagnostics.contains(&Diagnostic::DanglingReference {
+            id: missing,
+            kind: "ty",
+            field: "object",
+            target: missing,
+        }));
+        assert!(diagnostics.contains(&Diagnostic::DanglingReference {
+            id: state.read().unwrap().id,
+            kind: "state",
+            field: "obj_id",
+            target: missing,
+        }));
+        assert!(diagnostics.contains(&Diagnostic::DanglingReference {
+            id: acknowledged.read().unwrap().id,
+            kind: "acknowledged event",
+            field: "event_id",
+            target: event,
+        }));
+        assert!(
+            diagnostics.contains(&Diagnostic::ReferentialAttributeCollision {
+                id: referrer,
+                name: "id".to_owned(),
+                attribute: collides,
+            })
+        );
+        assert!(diagnostics.iter().any(
+            |d| matches!(d, Diagnostic::DuplicateRelationshipNumber { id, other, number: 1 }
+                if [*id, *other] == [binary.max(isa), binary.min(isa)])
+        ));
+        assert!(diagnostics.contains(&Diagnostic::EmptyIsa { id: isa, number: 1 }));
+        assert!(diagnostics
+            .iter()
+            .any(|d| matches!(d, Diagnostic::DuplicateObjectName { .. })));
+        assert!(diagnostics
+            .iter()
+            .any(|d| matches!(d, Diagnostic::DuplicateKeyLetters { .. })));
+    }
+}
@@ -0,0 +1,539 @@
+//! State Machine Executor
+//!
+//! The [`Transition`]s in a store make up each object's state transition
+//! table. An [`Executor`] runs those tables: it keeps the current state of each
+//! instance, queues the events that are sent to them, and dispatches the events
+//! one at a time.
+//!
+//! Dispatch is deterministic. Given the same instances and the same sends, the
+//! same steps happen in the same order:
+//!
+//!  - events that an instance sends to itself, from a state's action, are
+//!    dispatched before any other event,
+//!  - other events are dispatched in the order they were sent, and
+//!  - delayed events wait on a logical clock, which only moves with
+//!    [`Executor::advance`]. When they fall due they join the queue in the
+//!    order that they fall due, and in the order that they were sent if they
+//!    fall due together.
+//!
+//! ```ignore
+//! let mut executor = Executor::new(&store);
+//! executor.create(oven, idle)?;
+//! executor.send(oven, start)?;
+//! executor.run(|step, outbox| {
+//!     if step.outcome == Outcome::Entered(cooking) {
+//!         outbox.send_delayed(step.instance, done, Duration::from_secs(60));
+//!     }
+//! })?;
+//! ```
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, VecDeque},
+    fmt,
+    time::Duration,
+};
+
+use rustc_hash::FxHashMap as HashMap;
+use snafu::prelude::*;
+use uuid::Uuid;
+
+use crate::v2::sarzak::{store::ObjectStore as SarzakStore, types::TransitionEnum};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum MachineError {
+    #[snafu(display("{state} is not a state"))]
+    UnknownState { state: Uuid },
+    #[snafu(display("{event} is not an event"))]
+    UnknownEvent { event: Uuid },
+    #[snafu(display("there is no instance {instance}"))]
+    UnknownInstance { instance: Uuid },
+    #[snafu(display("instance {instance} already exists"))]
+    DuplicateInstance { instance: Uuid },
+    #[snafu(display("event {event} is not for instance {instance}'s object"))]
+    WrongObject { instance: Uuid, event: Uuid },
+    #[snafu(display("event {event} can't happen to instance {instance} in state {state}"))]
+    CantHappen {
+        instance: Uuid,
+        state: Uuid,
+        event: Uuid,
+    },
+}
+
+pub type Result<T, E = MachineError> = std::result::Result<T, E>;
+
+/// An event on its way to an instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Signal {
+    pub instance: Uuid,
+    pub event: Uuid,
+}
+
+/// What an event did
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The instance moved to this state
+    Entered(Uuid),
+    /// The event was ignored, and the instance stayed put
+    Ignored,
+}
+
+/// A dispatched event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub instance: Uuid,
+    pub event: Uuid,
+    /// The state the instance was in when the event arrived
+    pub from: Uuid,
+    pub outcome: Outcome,
+}
+
+/// Why [`Executor::run`] stopped early, and the steps it took first
+#[derive(Debug)]
+pub struct Halted {
+    /// The steps that were taken before the error, in order
+    pub steps: Vec<Step>,
+    pub error: MachineError,
+}
+
+impl fmt::Display for Halted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, after {} steps", self.error, self.steps.len())
+    }
+}
+
+impl std::error::Error for Halted {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Events sent by an action
+///
+/// Handed to the action passed to [`Executor::run`]. The events are queued once
+/// the action returns.
+#[derive(Debug)]
+pub struct Outbox {
+    instance: Uuid,
+    now: Duration,
+    sent: Vec<(Signal, Option<Duration>)>,
+}
+
+impl Outbox {
+    /// Send an event to the instance that the action is running for
+    ///
+    /// It's dispatched before any event that isn't self-directed.
+    pub fn send_self(&mut self, event: Uuid) {
+        let instance = self.instance;
+        self.send(instance, event);
+    }
+
+    pub fn send(&mut self, instance: Uuid, event: Uuid) {
+        self.sent.push((Signal { instance, event }, None));
+    }
+
+    pub fn send_delayed(&mut self, instance: Uuid, event: Uuid, delay: Duration) {
+        self.sent
+            .push((Signal { instance, event }, Some(self.now + delay)));
+    }
+}
+
+/// Runs the state machines in a store
+#[derive(Clone, Debug, Default)]
+pub struct Executor {
+    /// (from, event) -> what happens, for the events that may arrive
+    cells: HashMap<(Uuid, Uuid), Outcome>,
+    /// The object of each state
+    states: HashMap<Uuid, Uuid>,
+    /// The object of each event
+    events: HashMap<Uuid, Uuid>,
+    /// The current state of each instance, and its object
+    instances: BTreeMap<Uuid, (Uuid, Uuid)>,
+    self_directed: VecDeque<Signal>,
+    queue: VecDeque<Signal>,
+    /// Delayed events, by when they fall due, and then by when they were sent
+    delayed: BinaryHeap<Reverse<(Duration, u64, Signal)>>,
+    sequence: u64,
+    now: Duration,
+}
+
+impl Executor {
+    /// Build the transition tables from the store
+    ///
+    /// The store isn't looked at again, so changes to it aren't seen.
+    pub fn new(store: &SarzakStore) -> Self {
+        let mut executor = Self::default();
+        for state in store.iter_state() {
+            let state = state.read().unwrap();
+            executor.states.insert(state.id, state.obj_id);
+        }
+        for event in store.iter_event() {
+            let event = event.read().unwrap();
+            executor.events.insert(event.id, event.obj_id);
+        }
+        for transition in store.iter_transition() {
+            let transition = transition.read().unwrap();
+            let outcome = match transition.subtype {
+                TransitionEnum::NextState(id) => match store.exhume_next_state(&id) {
+                    Some(next) => Outcome::Entered(next.read().unwrap().to),
+                    None => continue,
+                },
+                TransitionEnum::Ignored(_) => Outcome::Ignored,
+                // Can't happen is the same as no cell at all.
+                TransitionEnum::CantHappen(_) => continue,
+            };
+            executor
+                .cells
+                .insert((transition.from, transition.event), outcome);
+        }
+
+        executor
+    }
+
+    /// Start an instance in `state`
+    pub fn create(&mut self, instance: Uuid, state: Uuid) -> Result<()> {
+        let obj_id = *self
+            .states
+            .get(&state)
+            .context(UnknownStateSnafu { state })?;
+        ensure!(
+            !self.instances.contains_key(&instance),
+            DuplicateInstanceSnafu { instance }
+        );
+        self.instances.insert(instance, (state, obj_id));
+
+        Ok(())
+    }
+
+    /// Stop an instance, and drop any events that are waiting for it
+    ///
+    /// Returns the state it was in.
+    pub fn delete(&mut self, instance: &Uuid) -> Option<Uuid> {
+        let (state, _) = self.instances.remove(instance)?;
+        self.self_directed.retain(|s| s.instance != *instance);
+        self.queue.retain(|s| s.instance != *instance);
+        self.delayed
+            .retain(|Reverse((_, _, s))| s.instance != *instance);
+
+        Some(state)
+    }
+
+    pub fn current_state(&self, instance: &Uuid) -> Option<Uuid> {
+        self.instances.get(instance).map(|(state, _)| *state)
+    }
+
+    /// The logical time, which starts at zero
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// The number of events waiting to be dispatched, delayed ones included
+    pub fn pending(&self) -> usize {
+        self.self_directed.len() + self.queue.len() + self.delayed.len()
+    }
+
+    /// Queue an event for an instance
+    pub fn send(&mut self, instance: Uuid, event: Uuid) -> Result<()> {
+        let signal = self.signal(instance, event)?;
+        self.queue.push_back(signal);
+
+        Ok(())
+    }
+
+    /// Queue an event for an instance, once `delay` has passed
+    pub fn send_delayed(&mut self, instance: Uuid, event: Uuid, delay: Duration) -> Result<()> {
+        let signal = self.signal(instance, event)?;
+        self.delay(signal, self.now + delay);
+
+        Ok(())
+    }
+
+    /// Move the clock on, and queue the delayed events that fall due
+    pub fn advance(&mut self, by: Duration) {
+        self.now += by;
+        while let Some(Reverse((due, _, _))) = self.delayed.peek() {
+            if *due > self.now {
+                break;
+            }
+            let Reverse((_, _, signal)) = self.delayed.pop().unwrap();
+            self.queue.push_back(signal);
+        }
+    }
+
+    /// Dispatch the next event, if there is one
+    ///
+    /// An event that can't happen is an error, and it's gone.
+    pub fn step(&mut self) -> Option<Result<Step>> {
+        let signal = self
+            .self_directed
+            .pop_front()
+            .or_else(|| self.queue.pop_front())?;
+
+        Some(self.dispatch(signal))
+    }
+
+    /// Dispatch events until there are none left that are due
+    ///
+    /// `action` is called after each step, and the events that it sends are
+    /// queued. Stops at the first error, which comes back with the steps that
+    /// were taken before it. If any of the events that an action sends are no
+    /// good, none of them are queued.
+    pub fn run<F>(&mut self, mut action: F) -> Result<Vec<Step>, Halted>
+    where
+        F: FnMut(&Step, &mut Outbox),
+    {
+        let mut steps = Vec::new();
+        while let Some(step) = self.step() {
+            let step = match step {
+                Ok(step) => step,
+                Err(error) => return Err(Halted { steps, error }),
+            };
+            let mut outbox = Outbox {
+                instance: step.instance,
+                now: self.now,
+                sent: Vec::new(),
+            };
+            action(&step, &mut outbox);
+            steps.push(step);
+
+            let sent = outbox
+                .sent
+                .into_iter()
+                .map(|(signal, due)| Ok((self.signal(signal.instance, signal.event)?, due)))
+                .collect::<Result<Vec<_>>>();
+            let sent = match sent {
+                Ok(sent) => sent,
+                Err(error) => return Err(Halted { steps, error }),
+            };
+            for (signal, due) in sent {
+                match due {
+                    Some(due) => self.delay(signal, due),
+                    None if signal.instance == step.instance => {
+                        self.self_directed.push_back(signal)
+                    }
+                    None => self.queue.push_back(signal),
+                }
+            }
+        }
+
+        Ok(steps)
+    }
+
+    fn signal(&self, instance: Uuid, event: Uuid) -> Result<Signal> {
+        let (_, obj_id) = self
+            .instances
+            .get(&instance)
+            .context(UnknownInstanceSnafu { instance })?;
+        let event_obj = self
+            .events
+            .get(&event)
+            .context(UnknownEventSnafu { event })?;
+        ensure!(event_obj == obj_id, WrongObjectSnafu { instance, event });
+
+        Ok(Signal { instance, event })
+    }
+
+    fn delay(&mut self, signal: Signal, due: Duration) {
+        self.sequence += 1;
+        self.delayed.push(Reverse((due, self.sequence, signal)));
+    }
+
+    fn dispatch(&mut self, signal: Signal) -> Result<Step> {
+        let Signal { instance, event } = signal;
+        let (state, _) = self
+            .instances
+            .get_mut(&instance)
+            .context(UnknownInstanceSnafu { instance })?;
+        let from = *state;
+
+        let outcome = match self.cells.get(&(from, event)) {
+            Some(outcome) => *outcome,
+            None => {
+                return CantHappenSnafu {
+                    instance,
+                    state: from,
+                    event,
+                }
+                .fail()
+            }
+        };
+        if let Outcome::Entered(to) = outcome {
+            *state = to;
+        }
+
+        Ok(Step {
+            instance,
+            event,
+            from,
+            outcome,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::types::{Event, NextState, Object, State, Transition};
+
+    struct Oven {
+        store: SarzakStore,
+        idle: Uuid,
+        cooking: Uuid,
+        done: Uuid,
+        start: Uuid,
+        tick: Uuid,
+        finish: Uuid,
+        open: Uuid,
+    }
+
+    /// idle --start--> cooking --tick--> cooking --finish--> done
+    ///
+    /// `tick` is ignored when idle, `open` can't happen while cooking.
+    fn oven() -> Oven {
+        let mut store = SarzakStore::new();
+        let oven = Object::new(
+            "".to_owned(),
+            "OVEN".to_owned(),
+            "Oven".to_owned(),
+            &mut store,
+        );
+        let idle = State::new("idle".to_owned(), &oven, &mut store);
+        let cooking = State::new("cooking".to_owned(), &oven, &mut store);
+        let done = State::new("done".to_owned(), &oven, &mut store);
+        let start = Event::new("start".to_owned(), &oven, &mut store);
+        let tick = Event::new("tick".to_owned(), &oven, &mut store);
+        let finish = Event::new("finish".to_owned(), &oven, &mut store);
+        let open = Event::new("open".to_owned(), &oven, &mut store);
+
+        Transition::new_next_state(
+            &NextState::new(&cooking, &mut store),
+            &start,
+            &idle,
+            &mut store,
+        );
+        Transition::new_ignored(&tick, &idle, &mut store);
+        Transition::new_next_state(
+            &NextState::new(&cooking, &mut store),
+            &tick,
+            &cooking,
+            &mut store,
+        );
+        Transition::new_next_state(
+            &NextState::new(&done, &mut store),
+            &finish,
+            &cooking,
+            &mut store,
+        );
+        Transition::new_cant_happen(&open, &cooking, &mut store);
+
+        let id = |x: &std::sync::Arc<std::sync::RwLock<State>>| x.read().unwrap().id;
+        let ev = |x: &std::sync::Arc<std::sync::RwLock<Event>>| x.read().unwrap().id;
+        Oven {
+            idle: id(&idle),
+            cooking: id(&cooking),
+            done: id(&done),
+            start: ev(&start),
+            tick: ev(&tick),
+            finish: ev(&finish),
+            open: ev(&open),
+            store,
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let oven = oven();
+        let mut executor = Executor::new(&oven.store);
+        let a = Uuid::new_v4();
+        executor.create(a, oven.idle).unwrap();
+        assert!(executor.create(a, oven.idle).is_err());
+
+        executor.send(a, oven.tick).unwrap();
+        executor.send(a, oven.start).unwrap();
+        let steps = executor.run(|_, _| {}).unwrap();
+        assert_eq!(steps[0].outcome, Outcome::Ignored);
+        assert_eq!(steps[1].outcome, Outcome::Entered(oven.cooking));
+        assert_eq!(executor.current_state(&a), Some(oven.cooking));
+
+        // Explicitly can't happen, and missing from the table
+        executor.send(a, oven.open).unwrap();
+        executor.send(a, oven.start).unwrap();
+        let halted = executor.run(|_, _| {}).unwrap_err();
+        assert!(halted.steps.is_empty());
+        assert!(matches!(
+            halted.error,
+            MachineError::CantHappen { state, .. } if state == oven.cooking
+        ));
+        assert!(matches!(
+            executor.step(),
+            Some(Err(MachineError::CantHappen { .. }))
+        ));
+        assert_eq!(executor.current_state(&a), Some(oven.cooking));
+
+        assert!(matches!(
+            executor.send(Uuid::new_v4(), oven.start),
+            Err(MachineError::UnknownInstance { .. })
+        ));
+        assert_eq!(executor.delete(&a), Some(oven.cooking));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let oven = oven();
+        let mut executor = Executor::new(&oven.store);
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        executor.create(a, oven.idle).unwrap();
+        executor.create(b, oven.idle).unwrap();
+
+        // a's self-directed tick jumps ahead of b's start, which was sent first.
+        executor.send(a, oven.start).unwrap();
+        executor.send(b, oven.start).unwrap();
+        let steps = executor
+            .run(|step, outbox| {
+                if step.instance == a && step.event == oven.start {
+                    outbox.send_self(oven.tick);
+                    outbox.send_delayed(a, oven.finish, Duration::from_secs(10));
+                    outbox.send_delayed(b, oven.finish, Duration::from_secs(5));
+                }
+            })
+            .unwrap();
+        let order: Vec<_> = steps.iter().map(|s| (s.instance, s.event)).collect();
+        assert_eq!(order, [(a, oven.start), (a, oven.tick), (b, oven.start)]);
+        assert_eq!(executor.pending(), 2);
+
+        executor.advance(Duration::from_secs(5));
+        let steps = executor.run(|_, _| {}).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(executor.current_state(&b), Some(oven.done));
+        assert_eq!(executor.current_state(&a), Some(oven.cooking));
+
+        executor.advance(Duration::from_secs(5));
+        executor.run(|_, _| {}).unwrap();
+        assert_eq!(executor.current_state(&a), Some(oven.done));
+        assert_eq!(executor.now(), Duration::from_secs(10));
+        assert_eq!(executor.pending(), 0);
+    }
+
+    #[test]
+    fn test_bad_outbox() {
+        let oven = oven();
+        let mut executor = Executor::new(&oven.store);
+        let a = Uuid::new_v4();
+        executor.create(a, oven.idle).unwrap();
+
+        // The good send isn't queued either, and the step still counts.
+        executor.send(a, oven.start).unwrap();
+        let halted = executor
+            .run(|_, outbox| {
+                outbox.send_self(oven.tick);
+                outbox.send(Uuid::new_v4(), oven.tick);
+            })
+            .unwrap_err();
+        assert_eq!(halted.steps.len(), 1);
+        assert_eq!(halted.steps[0].outcome, Outcome::Entered(oven.cooking));
+        assert!(matches!(halted.error, MachineError::UnknownInstance { .. }));
+        assert_eq!(executor.pending(), 0);
+        assert_eq!(executor.current_state(&a), Some(oven.cooking));
+    }
+}
@@ -0,0 +1,457 @@
+//! State Model Analysis
+//!
+//! Tooling over the states, events and [`Transition`]s in a store. [`analyze`]
+//! looks for the mistakes that are easy to make in a state model, and the
+//! exports render an object's state model for people to look at:
+//!
+//!  - [`transition_table`], as Markdown or CSV, with a row per state and a
+//!    column per event, and
+//!  - [`mermaid`], as a Mermaid `stateDiagram`.
+//!
+//! States and events are always listed by name, so the output is stable.
+//!
+//! [`Transition`]: super::types::Transition
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt,
+};
+
+use heck::ToSnakeCase;
+use uuid::Uuid;
+
+use crate::v2::sarzak::{
+    store::ObjectStore,
+    types::{Event, State, TransitionEnum},
+};
+
+/// Something odd about a state model
+///
+/// The `id` is always the id of the state or event that has the problem.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Finding {
+    /// No instance can get to the state.
+    ///
+    /// Instances start in the states that no other state leads to. A state
+    /// is unreachable if none of those lead to it.
+    UnreachableState { id: Uuid, name: String },
+    /// Once in the state, an instance never leaves it.
+    NoExit { id: Uuid, name: String },
+    /// No state acknowledges the event (R20).
+    UnacknowledgedEvent { id: Uuid, name: String },
+    /// More than one event on the same object has the same name.
+    DuplicateEventName { id: Uuid, name: String, other: Uuid },
+}
+
+impl Finding {
+    /// Return the id of the offending state or event
+    ///
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::UnreachableState { id, .. } => *id,
+            Self::NoExit { id, .. } => *id,
+            Self::UnacknowledgedEvent { id, .. } => *id,
+            Self::DuplicateEventName { id, .. } => *id,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnreachableState { id, name } => {
+                write!(f, "state {id}: `{name}` can't be reached")
+            }
+            Self::NoExit { id, name } => write!(f, "state {id}: `{name}` has no exit"),
+            Self::UnacknowledgedEvent { id, name } => {
+                write!(f, "event {id}: `{name}` isn't acknowledged by any state")
+            }
+            Self::DuplicateEventName { id, name, other } => {
+                write!(f, "event {id}: name `{name}` is also used by {other}")
+            }
+        }
+    }
+}
+
+/// How to write a transition table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    Markdown,
+    Csv,
+}
+
+/// Analyze every state model in the store
+///
+/// The findings are sorted by the id of the offending state or event, so that
+/// the output is stable.
+pub fn analyze(store: &ObjectStore) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for obj_id in objects_with_states(store) {
+        let model = StateModel::new(store, &obj_id);
+        model.reachability(&mut findings);
+        model.exits(&mut findings);
+    }
+    acknowledged_events(store, &mut findings);
+    event_names(store, &mut findings);
+
+    findings.sort_by_key(|f| (f.id(), f.to_string()));
+    findings
+}
+
+/// The objects that have states, sorted by name
+///
+pub fn objects_with_states(store: &ObjectStore) -> Vec<Uuid> {
+    let mut objects: Vec<(String, Uuid)> = store
+        .iter_object()
+        .filter_map(|o| {
+            let o = o.read().unwrap();
+            if store.exhume_state_by_obj_id(&o.id).is_empty() {
+                None
+            } else {
+                Some((o.name.clone(), o.id))
+            }
+        })
+        .collect();
+    objects.sort();
+
+    objects.into_iter().map(|(_, id)| id).collect()
+}
+
+/// The state transition table of an object
+///
+/// Each cell is the name of the state that the event leads to, `ignored`, or
+/// `can't happen`. A cell that isn't in the model is left empty, and is
+/// treated as can't happen.
+pub fn transition_table(store: &ObjectStore, obj_id: &Uuid, format: TableFormat) -> String {
+    let model = StateModel::new(store, obj_id);
+
+    let mut rows = vec![std::iter::once("State".to_owned())
+        .chain(model.events.iter().map(|e| e.name.clone()))
+        .collect::<Vec<_>>()];
+    for state in &model.states {
+        let mut row = vec![state.name.clone()];
+        for event in &model.events {
+            row.push(match model.cells.get(&(state.id, event.id)) {
+                Some(Cell::Enters(to)) => model.name(to),
+                Some(Cell::Ignored) => "ignored".to_owned(),
+                Some(Cell::CantHappen) => "can't happen".to_owned(),
+                None => String::new(),
+            });
+        }
+        rows.push(row);
+    }
+
+    let mut table = String::new();
+    match format {
+        TableFormat::Markdown => {
+            let line = |cells: &[String]| format!("| {} |\n", cells.join(" | "));
+            table.push_str(&line(&rows[0]));
+            table.push_str(&line(&vec!["---".to_owned(); rows[0].len()]));
+            for row in &rows[1..] {
+                table.push_str(&line(row));
+            }
+        }
+        TableFormat::Csv => {
+            for row in &rows {
+                let cells: Vec<_> = row.iter().map(|c| csv_cell(c)).collect();
+                table.push_str(&cells.join(","));
+                table.push('\n');
+            }
+        }
+    }
+
+    table
+}
+
+/// A Mermaid `stateDiagram` of an object's state model
+///
+/// Only the transitions that move an instance are drawn. The states that
+/// instances start in are entered from `[*]`.
+pub fn mermaid(store: &ObjectStore, obj_id: &Uuid) -> String {
+    let model = StateModel::new(store, obj_id);
+    let ids: BTreeMap<Uuid, String> = model
+        .states
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.id, format!("{}_{i}", s.name.to_snake_case())))
+        .collect();
+
+    let mut diagram = "stateDiagram-v2\n".to_owned();
+    for state in &model.states {
+        diagram.push_str(&format!(
+            "    state \"{}\" as {}\n",
+            state.name.replace('"', "'"),
+            ids[&state.id]
+        ));
+    }
+    for start in model.starts() {
+        diagram.push_str(&format!("    [*] --> {}\n", ids[&start]));
+    }
+    for state in &model.states {
+        for event in &model.events {
+            // A transition to another object's state is a lint problem.
+            if let Some(Cell::Enters(to)) = model.cells.get(&(state.id, event.id)) {
+                if let Some(to) = ids.get(to) {
+                    diagram.push_str(&format!(
+                        "    {} --> {to} : {}\n",
+                        ids[&state.id], event.name
+                    ));
+                }
+            }
+        }
+    }
+
+    diagram
+}
+
+/// An object's states, events and transitions, sorted by name
+struct StateModel {
+    states: Vec<State>,
+    events: Vec<Event>,
+    /// (from, event) -> what happens
+    cells: BTreeMap<(Uuid, Uuid), Cell>,
+}
+
+/// A cell in the transition table
+enum Cell {
+    Enters(Uuid),
+    Ignored,
+    CantHappen,
+}
+
+impl StateModel {
+    fn new(store: &ObjectStore, obj_id: &Uuid) -> Self {
+        let mut states: Vec<State> = store
+            .exhume_state_by_obj_id(obj_id)
+            .into_iter()
+            .map(|s| s.read().unwrap().clone())
+            .collect();
+        states.sort_by(|a, b| (&a.name, a.id).cmp(&(&b.name, b.id)));
+        let mut events: Vec<Event> = store
+            .exhume_event_by_obj_id(obj_id)
+            .into_iter()
+            .map(|e| e.read().unwrap().clone())
+            .collect();
+        events.sort_by(|a, b| (&a.name, a.id).cmp(&(&b.name, b.id)));
+
+        let cells = states
+            .iter()
+            .flat_map(|s| s.r28_transition(store))
+            .filter_map(|t| {
+                let t = t.read().unwrap();
+                let cell = match t.subtype {
+                    TransitionEnum::NextState(id) => {
+                        Cell::Enters(store.exhume_next_state(&id)?.read().unwrap().to)
+                    }
+                    TransitionEnum::Ignored(_) => Cell::Ignored,
+                    TransitionEnum::CantHappen(_) => Cell::CantHappen,
+                };
+                Some(((t.from, t.event), cell))
+            })
+            .collect();
+
+        Self {
+            states,
+            events,
+            cells,
+        }
+    }
+
+    fn name(&self, state: &Uuid) -> String {
+        self.states
+            .iter()
+            .find(|s| s.id == *state)
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| state.to_string())
+    }
+
+    /// (from, to) for each transition between two different states
+    fn edges(&self) -> impl Iterator<Item = (Uuid, Uuid)> + '_ {
+        self.cells
+            .iter()
+            .filter_map(|((from, _), cell)| match cell {
+                Cell::Enters(to) if to != from => Some((*from, *to)),
+                _ => None,
+            })
+    }
+
+    /// The states that no other state leads to, in name order
+    fn starts(&self) -> Vec<Uuid> {
+        let entered: BTreeSet<Uuid> = self.edges().map(|(_, to)| to).collect();
+        self.states
+            .iter()
+            .map(|s| s.id)
+            .filter(|id| !entered.contains(id))
+            .collect()
+    }
+
+    fn reachability(&self, findings: &mut Vec<Finding>) {
+        let mut reached: BTreeSet<Uuid> = BTreeSet::new();
+        let mut queue: VecDeque<Uuid> = self.starts().into();
+        while let Some(state) = queue.pop_front() {
+            if reached.insert(state) {
+                queue.extend(self.edges().filter(|(from, _)| *from == state).map(|e| e.1));
+            }
+        }
+
+        for state in &self.states {
+            if !reached.contains(&state.id) {
+                findings.push(Finding::UnreachableState {
+                    id: state.id,
+                    name: state.name.clone(),
+                });
+            }
+        }
+    }
+
+    fn exits(&self, findings: &mut Vec<Finding>) {
+        let exits: BTreeSet<Uuid> = self.edges().map(|(from, _)| from).collect();
+        for state in &self.states {
+            if !exits.contains(&state.id) {
+                findings.push(Finding::NoExit {
+                    id: state.id,
+                    name: state.name.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn acknowledged_events(store: &ObjectStore, findings: &mut Vec<Finding>) {
+    for event in store.iter_event() {
+        let event = event.read().unwrap();
+        if store
+            .exhume_acknowledged_event_by_event_id(&event.id)
+            .is_empty()
+        {
+            findings.push(Finding::UnacknowledgedEvent {
+                id: event.id,
+                name: event.name.clone(),
+            });
+        }
+    }
+}
+
+fn event_names(store: &ObjectStore, findings: &mut Vec<Finding>) {
+    // Sorted by id, so that the first event to use a name keeps it.
+    let mut events: Vec<(Uuid, Uuid, String)> = store
+        .iter_event()
+        .map(|e| {
+            let e = e.read().unwrap();
+            (e.id, e.obj_id, e.name.clone())
+        })
+        .collect();
+    events.sort();
+
+    let mut names: BTreeMap<(Uuid, String), Uuid> = BTreeMap::new();
+    for (id, obj_id, name) in events {
+        match names.get(&(obj_id, name.clone())) {
+            Some(other) => findings.push(Finding::DuplicateEventName {
+                id,
+                name,
+                other: *other,
+            }),
+            None => {
+                names.insert((obj_id, name), id);
+            }
+        }
+    }
+}
+
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::sarzak::types::{AcknowledgedEvent, NextState, Object, Transition};
+
+    /// idle --start--> cooking --finish--> done, a lost state, and a cycle that
+    /// nothing leads to
+    fn oven(store: &mut ObjectStore) -> Uuid {
+        let oven = Object::new("".to_owned(), "OVEN".to_owned(), "Oven".to_owned(), store);
+        let idle = State::new("idle".to_owned(), &oven, store);
+        let cooking = State::new("cooking".to_owned(), &oven, store);
+        let done = State::new("done".to_owned(), &oven, store);
+        let lost = State::new("lost".to_owned(), &oven, store);
+        let limbo = State::new("limbo".to_owned(), &oven, store);
+        let purgatory = State::new("purgatory".to_owned(), &oven, store);
+        let start = Event::new("start".to_owned(), &oven, store);
+        let finish = Event::new("finish".to_owned(), &oven, store);
+        Event::new("start".to_owned(), &oven, store);
+
+        Transition::new_next_state(&NextState::new(&cooking, store), &start, &idle, store);
+        Transition::new_ignored(&finish, &idle, store);
+        Transition::new_next_state(&NextState::new(&done, store), &finish, &cooking, store);
+        Transition::new_cant_happen(&start, &cooking, store);
+        Transition::new_next_state(&NextState::new(&lost, store), &finish, &lost, store);
+        Transition::new_next_state(&NextState::new(&purgatory, store), &finish, &limbo, store);
+        Transition::new_next_state(&NextState::new(&limbo, store), &start, &purgatory, store);
+        AcknowledgedEvent::new(&start, &idle, store);
+        AcknowledgedEvent::new(&finish, &cooking, store);
+
+        let id = oven.read().unwrap().id;
+        id
+    }
+
+    #[test]
+    fn test_analyze() {
+        let mut store = ObjectStore::new();
+        oven(&mut store);
+
+        let mut findings: Vec<String> = analyze(&store)
+            .into_iter()
+            .map(|f| match f {
+                Finding::UnreachableState { name, .. } => format!("unreachable {name}"),
+                Finding::NoExit { name, .. } => format!("no exit {name}"),
+                Finding::UnacknowledgedEvent { name, .. } => format!("unacknowledged {name}"),
+                Finding::DuplicateEventName { name, .. } => format!("duplicate {name}"),
+            })
+            .collect();
+        findings.sort();
+
+        // The lost state loops back on itself, so it's both a start, and a dead end.
+        assert_eq!(
+            findings,
+            [
+                "duplicate start",
+                "no exit done",
+                "no exit lost",
+                "unacknowledged start",
+                "unreachable limbo",
+                "unreachable purgatory"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export() {
+        let mut store = ObjectStore::new();
+        let oven = oven(&mut store);
+
+        let markdown = transition_table(&store, &oven, TableFormat::Markdown);
+        let mut lines = markdown.lines();
+        assert_eq!(lines.next(), Some("| State | finish | start | start |"));
+        assert_eq!(lines.next(), Some("| --- | --- | --- | --- |"));
+        assert!(markdown.contains("| cooking | done | "));
+        assert!(markdown.contains("can't happen"));
+        assert!(markdown.contains("| idle | ignored | "));
+
+        let csv = transition_table(&store, &oven, TableFormat::Csv);
+        assert_eq!(csv.lines().count(), 7);
+        assert!(csv.starts_with("State,finish,start,start\n"));
+
+        let diagram = mermaid(&store, &oven);
+        assert!(diagram.starts_with("stateDiagram-v2\n"));
+        assert!(diagram.contains("    state \"cooking\" as cooking_0\n"));
+        assert!(diagram.contains("    [*] --> idle_2\n"));
+        assert!(diagram.contains("    cooking_0 --> done_1 : finish\n"));
+        assert!(diagram.contains("    lost_4 --> lost_4 : finish\n"));
+        assert!(!diagram.contains("[*] --> limbo"));
+    }
+}
@@ -9,6 +9,7 @@
 //!
 //! * [`AcknowledgedEvent`]
 //! * [`AnAssociativeReferent`]
+//! * [`Argument`]
 //! * [`Associative`]
 //! * [`AssociativeReferent`]
 //! * [`AssociativeReferrer`]
@@ -16,16 +17,22 @@
 //! * [`Binary`]
 //! * [`Cardinality`]
 //! * [`Conditionality`]
+//! * [`Enumeration`]
+//! * [`Enumerator`]
 //! * [`Event`]
 //! * [`External`]
 //! * [`Isa`]
+//! * [`NextState`]
 //! * [`Object`]
+//! * [`Operation`]
+//! * [`Parameter`]
 //! * [`Referent`]
 //! * [`Referrer`]
 //! * [`Relationship`]
 //! * [`State`]
 //! * [`Subtype`]
 //! * [`Supertype`]
+//! * [`Transition`]
 //! * [`Ty`]
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"v2::sarzak-object-store-definition"}}}
 use std::sync::Arc;
@@ -33,27 +40,67 @@ use std::sync::RwLock;
 use std::{
     fs,
     io::{self, prelude::*},
-    path::Path,
+    path::{Path, PathBuf},
     time::SystemTime,
 };
 
 use heck::ToUpperCamelCase;
-use rustc_hash::FxHashMap as HashMap;
-use serde::{Deserialize, Serialize};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
+use crate::v2::id::IdStrategy;
+use crate::v2::notify::{Change, ChangeKind, Subscribers, SubscriptionId};
+use crate::v2::transaction::{Journal, Transaction, Transactional};
+
+use crate::v2::index::Index;
 use crate::v2::sarzak::types::{
-    AcknowledgedEvent, AnAssociativeReferent, Associative, AssociativeReferent,
-    AssociativeReferrer, Attribute, Binary, Cardinality, Conditionality, Event, External, Isa,
-    Object, Referent, Referrer, Relationship, State, Subtype, Supertype, Ty, BOOLEAN, CONDITIONAL,
-    FLOAT, INTEGER, MANY, ONE, UNCONDITIONAL, Z_STRING, Z_UUID,
+    AcknowledgedEvent, AnAssociativeReferent, Argument, Associative, AssociativeReferent,
+    AssociativeReferrer, Attribute, Binary, Cardinality, Conditionality, Enumeration, Enumerator,
+    Event, External, Isa, NextState, Object, Operation, Parameter, Referent, Referrer,
+    Relationship, State, Subtype, Supertype, Transition, Ty, BOOLEAN, CONDITIONAL, FLOAT, INTEGER,
+    MANY, ONE, UNCONDITIONAL, Z_STRING, Z_UUID,
 };
 
+/// The tables in the store, for [`Change`] notifications
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Table {
+    AcknowledgedEvent,
+    AnAssociativeReferent,
+    Argument,
+    Associative,
+    AssociativeReferent,
+    AssociativeReferrer,
+    Attribute,
+    Binary,
+    Cardinality,
+    Conditionality,
+    Enumeration,
+    Enumerator,
+    Event,
+    External,
+    Isa,
+    NextState,
+    Object,
+    Operation,
+    Parameter,
+    Referent,
+    Referrer,
+    Relationship,
+    State,
+    Subtype,
+    Supertype,
+    Transition,
+    Ty,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self")]
 pub struct ObjectStore {
     acknowledged_event: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<AcknowledgedEvent>>, SystemTime)>>>,
     an_associative_referent:
         Arc<RwLock<HashMap<Uuid, (Arc<RwLock<AnAssociativeReferent>>, SystemTime)>>>,
+    argument: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Argument>>, SystemTime)>>>,
     associative: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Associative>>, SystemTime)>>>,
     associative_referent:
         Arc<RwLock<HashMap<Uuid, (Arc<RwLock<AssociativeReferent>>, SystemTime)>>>,
@@ -63,18 +110,125 @@ pub struct ObjectStore {
     binary: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Binary>>, SystemTime)>>>,
     cardinality: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Cardinality>>, SystemTime)>>>,
     conditionality: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Conditionality>>, SystemTime)>>>,
+    enumeration: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Enumeration>>, SystemTime)>>>,
+    enumerator: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Enumerator>>, SystemTime)>>>,
     event: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Event>>, SystemTime)>>>,
     external: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<External>>, SystemTime)>>>,
     isa: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Isa>>, SystemTime)>>>,
+    next_state: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<NextState>>, SystemTime)>>>,
     object: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Object>>, SystemTime)>>>,
     object_id_by_name: Arc<RwLock<HashMap<String, (Uuid, SystemTime)>>>,
+    operation: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Operation>>, SystemTime)>>>,
+    parameter: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Parameter>>, SystemTime)>>>,
     referent: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Referent>>, SystemTime)>>>,
     referrer: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Referrer>>, SystemTime)>>>,
     relationship: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Relationship>>, SystemTime)>>>,
     state: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<State>>, SystemTime)>>>,
     subtype: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Subtype>>, SystemTime)>>>,
     supertype: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Supertype>>, SystemTime)>>>,
+    transition: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Transition>>, SystemTime)>>>,
     ty: Arc<RwLock<HashMap<Uuid, (Arc<RwLock<Ty>>, SystemTime)>>>,
+    // Indexes for navigating backwards across relationships. They aren't
+    // serialized, they are rebuilt from the tables when the store is loaded.
+    #[serde(skip)]
+    acknowledged_event_by_event_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    acknowledged_event_by_state_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    an_associative_referent_by_associative: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    an_associative_referent_by_referent: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    argument_by_operation: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    argument_by_ty: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    associative_by_from: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    associative_referent_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    associative_referent_by_cardinality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    associative_referent_by_conditionality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    associative_referrer_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    associative_referrer_by_cardinality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    attribute_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    attribute_by_ty: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    binary_by_from: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    binary_by_to: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    enumerator_by_enumeration: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    event_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    isa_by_supertype: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    next_state_by_to: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    operation_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    operation_by_ret: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    parameter_by_event: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    parameter_by_ty: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    referent_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    referent_by_cardinality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    referent_by_conditionality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    referrer_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    referrer_by_cardinality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    referrer_by_conditionality: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    state_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    subtype_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    subtype_by_isa: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    supertype_by_obj_id: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    transition_by_from: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    transition_by_event: Arc<RwLock<Index>>,
+    #[serde(skip)]
+    subscribers: Subscribers<Table>,
+    #[serde(skip)]
+    journal: Journal<ObjectStore, Table>,
+    // The instances that were inter'd or exorcised since the store was last
+    // loaded or persisted, and where that was.
+    #[serde(skip)]
+    dirty: Arc<RwLock<HashSet<(Table, Uuid)>>>,
+    #[serde(skip)]
+    persisted: Arc<RwLock<Option<PathBuf>>>,
+    #[serde(skip)]
+    id_strategy: IdStrategy,
+}
+
+impl Serialize for ObjectStore {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ObjectStore::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectStore {
+    /// Deserialize the tables, and rebuild the indexes from them
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let store = ObjectStore::deserialize(deserializer)?;
+        store.reindex();
+        Ok(store)
+    }
 }
 
 impl ObjectStore {
@@ -82,6 +236,7 @@ impl ObjectStore {
         let mut store = Self {
             acknowledged_event: Arc::new(RwLock::new(HashMap::default())),
             an_associative_referent: Arc::new(RwLock::new(HashMap::default())),
+            argument: Arc::new(RwLock::new(HashMap::default())),
             associative: Arc::new(RwLock::new(HashMap::default())),
             associative_referent: Arc::new(RwLock::new(HashMap::default())),
             associative_referrer: Arc::new(RwLock::new(HashMap::default())),
@@ -89,18 +244,65 @@ impl ObjectStore {
             binary: Arc::new(RwLock::new(HashMap::default())),
             cardinality: Arc::new(RwLock::new(HashMap::default())),
             conditionality: Arc::new(RwLock::new(HashMap::default())),
+            enumeration: Arc::new(RwLock::new(HashMap::default())),
+            enumerator: Arc::new(RwLock::new(HashMap::default())),
             event: Arc::new(RwLock::new(HashMap::default())),
             external: Arc::new(RwLock::new(HashMap::default())),
             isa: Arc::new(RwLock::new(HashMap::default())),
+            next_state: Arc::new(RwLock::new(HashMap::default())),
             object: Arc::new(RwLock::new(HashMap::default())),
             object_id_by_name: Arc::new(RwLock::new(HashMap::default())),
+            operation: Arc::new(RwLock::new(HashMap::default())),
+            parameter: Arc::new(RwLock::new(HashMap::default())),
             referent: Arc::new(RwLock::new(HashMap::default())),
             referrer: Arc::new(RwLock::new(HashMap::default())),
             relationship: Arc::new(RwLock::new(HashMap::default())),
             state: Arc::new(RwLock::new(HashMap::default())),
             subtype: Arc::new(RwLock::new(HashMap::default())),
             supertype: Arc::new(RwLock::new(HashMap::default())),
+            transition: Arc::new(RwLock::new(HashMap::default())),
             ty: Arc::new(RwLock::new(HashMap::default())),
+            acknowledged_event_by_event_id: Arc::new(RwLock::new(Index::default())),
+            acknowledged_event_by_state_id: Arc::new(RwLock::new(Index::default())),
+            an_associative_referent_by_associative: Arc::new(RwLock::new(Index::default())),
+            an_associative_referent_by_referent: Arc::new(RwLock::new(Index::default())),
+            argument_by_operation: Arc::new(RwLock::new(Index::default())),
+            argument_by_ty: Arc::new(RwLock::new(Index::default())),
+            associative_by_from: Arc::new(RwLock::new(Index::default())),
+            associative_referent_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            associative_referent_by_cardinality: Arc::new(RwLock::new(Index::default())),
+            associative_referent_by_conditionality: Arc::new(RwLock::new(Index::default())),
+            associative_referrer_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            associative_referrer_by_cardinality: Arc::new(RwLock::new(Index::default())),
+            attribute_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            attribute_by_ty: Arc::new(RwLock::new(Index::default())),
+            binary_by_from: Arc::new(RwLock::new(Index::default())),
+            binary_by_to: Arc::new(RwLock::new(Index::default())),
+            enumerator_by_enumeration: Arc::new(RwLock::new(Index::default())),
+            event_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            isa_by_supertype: Arc::new(RwLock::new(Index::default())),
+            next_state_by_to: Arc::new(RwLock::new(Index::default())),
+            operation_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            operation_by_ret: Arc::new(RwLock::new(Index::default())),
+            parameter_by_event: Arc::new(RwLock::new(Index::default())),
+            parameter_by_ty: Arc::new(RwLock::new(Index::default())),
+            referent_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            referent_by_cardinality: Arc::new(RwLock::new(Index::default())),
+            referent_by_conditionality: Arc::new(RwLock::new(Index::default())),
+            referrer_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            referrer_by_cardinality: Arc::new(RwLock::new(Index::default())),
+            referrer_by_conditionality: Arc::new(RwLock::new(Index::default())),
+            state_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            subtype_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            subtype_by_isa: Arc::new(RwLock::new(Index::default())),
+            supertype_by_obj_id: Arc::new(RwLock::new(Index::default())),
+            transition_by_from: Arc::new(RwLock::new(Index::default())),
+            transition_by_event: Arc::new(RwLock::new(Index::default())),
+            subscribers: Subscribers::default(),
+            journal: Journal::default(),
+            dirty: Arc::new(RwLock::new(HashSet::default())),
+            persisted: Arc::new(RwLock::new(None)),
+            id_strategy: IdStrategy::default(),
         };
 
         // Initialize Singleton Subtypes
@@ -124,15 +326,141 @@ impl ObjectStore {
         store
     }
 
+    /// Start a [`Transaction`] on the store
+    ///
+    pub fn transaction(&mut self) -> Transaction<'_, Self> {
+        Transaction::new(self)
+    }
+
+    /// Call `callback` whenever an instance is inter'd or exorcised
+    ///
+    pub fn subscribe<F>(&self, callback: F) -> SubscriptionId
+    where
+        F: Fn(&Change<Table>) + Send + Sync + 'static,
+    {
+        self.subscribers.subscribe(callback)
+    }
+
+    /// Stop calling a subscriber
+    ///
+    /// Returns `false` if there was no such subscriber.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        self.subscribers.unsubscribe(id)
+    }
+
+    /// How ids are made for new instances
+    ///
+    pub fn id_strategy(&self) -> IdStrategy {
+        self.id_strategy
+    }
+
+    /// Change how ids are made for new instances
+    ///
+    /// Instances that are already in the store keep their ids.
+    pub fn set_id_strategy(&mut self, strategy: IdStrategy) {
+        self.id_strategy = strategy;
+    }
+
+    /// Make an id for a new instance in `table`
+    ///
+    /// `key` is the instance's natural key, and is only used by
+    /// [`IdStrategy::Derived`].
+    pub fn new_id(&self, table: Table, key: &str) -> Uuid {
+        self.id_strategy
+            .new_id(&format!("{table:?}"), key, |id| self.contains(table, id))
+    }
+
+    /// Is there an instance with this id in `table`?
+    ///
+    pub fn contains(&self, table: Table, id: &Uuid) -> bool {
+        match table {
+            Table::AcknowledgedEvent => self.acknowledged_event.read().unwrap().contains_key(id),
+            Table::AnAssociativeReferent => self
+                .an_associative_referent
+                .read()
+                .unwrap()
+                .contains_key(id),
+            Table::Argument => self.argument.read().unwrap().contains_key(id),
+            Table::Associative => self.associative.read().unwrap().contains_key(id),
+            Table::AssociativeReferent => {
+                self.associative_referent.read().unwrap().contains_key(id)
+            }
+            Table::AssociativeReferrer => {
+                self.associative_referrer.read().unwrap().contains_key(id)
+            }
+            Table::Attribute => self.attribute.read().unwrap().contains_key(id),
+            Table::Binary => self.binary.read().unwrap().contains_key(id),
+            Table::Cardinality => self.cardinality.read().unwrap().contains_key(id),
+            Table::Conditionality => self.conditionality.read().unwrap().contains_key(id),
+            Table::Enumeration => self.enumeration.read().unwrap().contains_key(id),
+            Table::Enumerator => self.enumerator.read().unwrap().contains_key(id),
+            Table::Event => self.event.read().unwrap().contains_key(id),
+            Table::External => self.external.read().unwrap().contains_key(id),
+            Table::Isa => self.isa.read().unwrap().contains_key(id),
+            Table::NextState => self.next_state.read().unwrap().contains_key(id),
+            Table::Object => self.object.read().unwrap().contains_key(id),
+            Table::Operation => self.operation.read().unwrap().contains_key(id),
+            Table::Parameter => self.parameter.read().unwrap().contains_key(id),
+            Table::Referent => self.referent.read().unwrap().contains_key(id),
+            Table::Referrer => self.referrer.read().unwrap().contains_key(id),
+            Table::Relationship => self.relationship.read().unwrap().contains_key(id),
+            Table::State => self.state.read().unwrap().contains_key(id),
+            Table::Subtype => self.subtype.read().unwrap().contains_key(id),
+            Table::Supertype => self.supertype.read().unwrap().contains_key(id),
+            Table::Transition => self.transition.read().unwrap().contains_key(id),
+            Table::Ty => self.ty.read().unwrap().contains_key(id),
+        }
+    }
+
+    fn changed(&self, change: Change<Table>) {
+        self.dirty
+            .write()
+            .unwrap()
+            .insert((change.table, change.id));
+        if let Some(change) = self.journal.defer(change) {
+            self.subscribers.notify(change);
+        }
+    }
+
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"v2::sarzak-object-store-methods"}}}
     /// Inter (insert) [`AcknowledgedEvent`] into the store.
     ///
     pub fn inter_acknowledged_event(&mut self, acknowledged_event: Arc<RwLock<AcknowledgedEvent>>) {
         let read = acknowledged_event.read().unwrap();
-        self.acknowledged_event
+        let kind = if self
+            .acknowledged_event
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.acknowledged_event_by_event_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.event_id);
+        self.acknowledged_event_by_state_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.state_id);
+        let old = self
+            .acknowledged_event
             .write()
             .unwrap()
             .insert(read.id, (acknowledged_event.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_acknowledged_event(old),
+            None => drop(store.exorcise_acknowledged_event(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::AcknowledgedEvent,
+            id,
+        });
     }
 
     /// Exhume (get) [`AcknowledgedEvent`] from the store.
@@ -151,11 +479,69 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<AcknowledgedEvent>>> {
-        self.acknowledged_event
+        self.acknowledged_event_by_event_id
+            .write()
+            .unwrap()
+            .remove(id);
+        self.acknowledged_event_by_state_id
+            .write()
+            .unwrap()
+            .remove(id);
+        let removed = self
+            .acknowledged_event
             .write()
             .unwrap()
             .remove(id)
-            .map(|acknowledged_event| acknowledged_event.0.clone())
+            .map(|acknowledged_event| acknowledged_event.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_acknowledged_event(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::AcknowledgedEvent,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`AcknowledgedEvent`] instances that refer to `event_id`.
+    ///
+    pub fn exhume_acknowledged_event_by_event_id(
+        &self,
+        event_id: &Uuid,
+    ) -> Vec<Arc<RwLock<AcknowledgedEvent>>> {
+        let table = self.acknowledged_event.read().unwrap();
+        self.acknowledged_event_by_event_id
+            .read()
+            .unwrap()
+            .get(event_id)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|acknowledged_event| acknowledged_event.0.clone())
+            })
+            .collect()
+    }
+
+    /// Exhume the [`AcknowledgedEvent`] instances that refer to `state_id`.
+    ///
+    pub fn exhume_acknowledged_event_by_state_id(
+        &self,
+        state_id: &Uuid,
+    ) -> Vec<Arc<RwLock<AcknowledgedEvent>>> {
+        let table = self.acknowledged_event.read().unwrap();
+        self.acknowledged_event_by_state_id
+            .read()
+            .unwrap()
+            .get(state_id)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|acknowledged_event| acknowledged_event.0.clone())
+            })
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, AcknowledgedEvent>`.
@@ -195,10 +581,39 @@ impl ObjectStore {
         an_associative_referent: Arc<RwLock<AnAssociativeReferent>>,
     ) {
         let read = an_associative_referent.read().unwrap();
-        self.an_associative_referent.write().unwrap().insert(
+        let kind = if self
+            .an_associative_referent
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.an_associative_referent_by_associative
+            .write()
+            .unwrap()
+            .insert(read.id, read.associative);
+        self.an_associative_referent_by_referent
+            .write()
+            .unwrap()
+            .insert(read.id, read.referent);
+        let old = self.an_associative_referent.write().unwrap().insert(
             read.id,
             (an_associative_referent.clone(), SystemTime::now()),
         );
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_an_associative_referent(old),
+            None => drop(store.exorcise_an_associative_referent(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::AnAssociativeReferent,
+            id,
+        });
     }
 
     /// Exhume (get) [`AnAssociativeReferent`] from the store.
@@ -220,11 +635,69 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<AnAssociativeReferent>>> {
-        self.an_associative_referent
+        self.an_associative_referent_by_associative
+            .write()
+            .unwrap()
+            .remove(id);
+        self.an_associative_referent_by_referent
+            .write()
+            .unwrap()
+            .remove(id);
+        let removed = self
+            .an_associative_referent
             .write()
             .unwrap()
             .remove(id)
-            .map(|an_associative_referent| an_associative_referent.0.clone())
+            .map(|an_associative_referent| an_associative_referent.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_an_associative_referent(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::AnAssociativeReferent,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`AnAssociativeReferent`] instances that refer to `associative`.
+    ///
+    pub fn exhume_an_associative_referent_by_associative(
+        &self,
+        associative: &Uuid,
+    ) -> Vec<Arc<RwLock<AnAssociativeReferent>>> {
+        let table = self.an_associative_referent.read().unwrap();
+        self.an_associative_referent_by_associative
+            .read()
+            .unwrap()
+            .get(associative)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|an_associative_referent| an_associative_referent.0.clone())
+            })
+            .collect()
+    }
+
+    /// Exhume the [`AnAssociativeReferent`] instances that refer to `referent`.
+    ///
+    pub fn exhume_an_associative_referent_by_referent(
+        &self,
+        referent: &Uuid,
+    ) -> Vec<Arc<RwLock<AnAssociativeReferent>>> {
+        let table = self.an_associative_referent.read().unwrap();
+        self.an_associative_referent_by_referent
+            .read()
+            .unwrap()
+            .get(referent)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|an_associative_referent| an_associative_referent.0.clone())
+            })
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, AnAssociativeReferent>`.
@@ -257,14 +730,153 @@ impl ObjectStore {
             .unwrap_or(SystemTime::now())
     }
 
+    /// Inter (insert) [`Argument`] into the store.
+    ///
+    pub fn inter_argument(&mut self, argument: Arc<RwLock<Argument>>) {
+        let read = argument.read().unwrap();
+        let kind = if self.argument.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.argument_by_operation
+            .write()
+            .unwrap()
+            .insert(read.id, read.operation);
+        self.argument_by_ty
+            .write()
+            .unwrap()
+            .insert(read.id, read.ty);
+        let old = self
+            .argument
+            .write()
+            .unwrap()
+            .insert(read.id, (argument.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_argument(old),
+            None => drop(store.exorcise_argument(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Argument,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`Argument`] from the store.
+    ///
+    pub fn exhume_argument(&self, id: &Uuid) -> Option<Arc<RwLock<Argument>>> {
+        self.argument
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|argument| argument.0.clone())
+    }
+
+    /// Exorcise (remove) [`Argument`] from the store.
+    ///
+    pub fn exorcise_argument(&mut self, id: &Uuid) -> Option<Arc<RwLock<Argument>>> {
+        self.argument_by_operation.write().unwrap().remove(id);
+        self.argument_by_ty.write().unwrap().remove(id);
+        let removed = self
+            .argument
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|argument| argument.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_argument(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Argument,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Argument`] instances that refer to `operation`.
+    ///
+    pub fn exhume_argument_by_operation(&self, operation: &Uuid) -> Vec<Arc<RwLock<Argument>>> {
+        let table = self.argument.read().unwrap();
+        self.argument_by_operation
+            .read()
+            .unwrap()
+            .get(operation)
+            .filter_map(|id| table.get(id).map(|argument| argument.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Argument`] instances that refer to `ty`.
+    ///
+    pub fn exhume_argument_by_ty(&self, ty: &Uuid) -> Vec<Arc<RwLock<Argument>>> {
+        let table = self.argument.read().unwrap();
+        self.argument_by_ty
+            .read()
+            .unwrap()
+            .get(ty)
+            .filter_map(|id| table.get(id).map(|argument| argument.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Argument>`.
+    ///
+    pub fn iter_argument(&self) -> impl Iterator<Item = Arc<RwLock<Argument>>> + '_ {
+        let values: Vec<Arc<RwLock<Argument>>> = self
+            .argument
+            .read()
+            .unwrap()
+            .values()
+            .map(|argument| argument.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Argument.
+    ///
+    pub fn argument_timestamp(&self, argument: &Argument) -> SystemTime {
+        self.argument
+            .read()
+            .unwrap()
+            .get(&argument.id)
+            .map(|argument| argument.1)
+            .unwrap_or(SystemTime::now())
+    }
+
     /// Inter (insert) [`Associative`] into the store.
     ///
     pub fn inter_associative(&mut self, associative: Arc<RwLock<Associative>>) {
         let read = associative.read().unwrap();
-        self.associative
+        let kind = if self.associative.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.associative_by_from
+            .write()
+            .unwrap()
+            .insert(read.id, read.from);
+        let old = self
+            .associative
             .write()
             .unwrap()
             .insert(read.id, (associative.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_associative(old),
+            None => drop(store.exorcise_associative(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Associative,
+            id,
+        });
     }
 
     /// Exhume (get) [`Associative`] from the store.
@@ -280,11 +892,36 @@ impl ObjectStore {
     /// Exorcise (remove) [`Associative`] from the store.
     ///
     pub fn exorcise_associative(&mut self, id: &Uuid) -> Option<Arc<RwLock<Associative>>> {
-        self.associative
+        self.associative_by_from.write().unwrap().remove(id);
+        let removed = self
+            .associative
             .write()
             .unwrap()
             .remove(id)
-            .map(|associative| associative.0.clone())
+            .map(|associative| associative.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_associative(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Associative,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Associative`] instances that refer to `from`.
+    ///
+    pub fn exhume_associative_by_from(&self, from: &Uuid) -> Vec<Arc<RwLock<Associative>>> {
+        let table = self.associative.read().unwrap();
+        self.associative_by_from
+            .read()
+            .unwrap()
+            .get(from)
+            .filter_map(|id| table.get(id).map(|associative| associative.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Associative>`.
@@ -319,10 +956,44 @@ impl ObjectStore {
         associative_referent: Arc<RwLock<AssociativeReferent>>,
     ) {
         let read = associative_referent.read().unwrap();
-        self.associative_referent
+        let kind = if self
+            .associative_referent
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.associative_referent_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        self.associative_referent_by_cardinality
+            .write()
+            .unwrap()
+            .insert(read.id, read.cardinality);
+        self.associative_referent_by_conditionality
+            .write()
+            .unwrap()
+            .insert(read.id, read.conditionality);
+        let old = self
+            .associative_referent
             .write()
             .unwrap()
             .insert(read.id, (associative_referent.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_associative_referent(old),
+            None => drop(store.exorcise_associative_referent(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::AssociativeReferent,
+            id,
+        });
     }
 
     /// Exhume (get) [`AssociativeReferent`] from the store.
@@ -344,11 +1015,92 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<AssociativeReferent>>> {
-        self.associative_referent
+        self.associative_referent_by_obj_id
+            .write()
+            .unwrap()
+            .remove(id);
+        self.associative_referent_by_cardinality
+            .write()
+            .unwrap()
+            .remove(id);
+        self.associative_referent_by_conditionality
+            .write()
+            .unwrap()
+            .remove(id);
+        let removed = self
+            .associative_referent
             .write()
             .unwrap()
             .remove(id)
-            .map(|associative_referent| associative_referent.0.clone())
+            .map(|associative_referent| associative_referent.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_associative_referent(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::AssociativeReferent,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`AssociativeReferent`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_associative_referent_by_obj_id(
+        &self,
+        obj_id: &Uuid,
+    ) -> Vec<Arc<RwLock<AssociativeReferent>>> {
+        let table = self.associative_referent.read().unwrap();
+        self.associative_referent_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|associative_referent| associative_referent.0.clone())
+            })
+            .collect()
+    }
+
+    /// Exhume the [`AssociativeReferent`] instances that refer to `cardinality`.
+    ///
+    pub fn exhume_associative_referent_by_cardinality(
+        &self,
+        cardinality: &Uuid,
+    ) -> Vec<Arc<RwLock<AssociativeReferent>>> {
+        let table = self.associative_referent.read().unwrap();
+        self.associative_referent_by_cardinality
+            .read()
+            .unwrap()
+            .get(cardinality)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|associative_referent| associative_referent.0.clone())
+            })
+            .collect()
+    }
+
+    /// Exhume the [`AssociativeReferent`] instances that refer to `conditionality`.
+    ///
+    pub fn exhume_associative_referent_by_conditionality(
+        &self,
+        conditionality: &Uuid,
+    ) -> Vec<Arc<RwLock<AssociativeReferent>>> {
+        let table = self.associative_referent.read().unwrap();
+        self.associative_referent_by_conditionality
+            .read()
+            .unwrap()
+            .get(conditionality)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|associative_referent| associative_referent.0.clone())
+            })
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, AssociativeReferent>`.
@@ -388,10 +1140,40 @@ impl ObjectStore {
         associative_referrer: Arc<RwLock<AssociativeReferrer>>,
     ) {
         let read = associative_referrer.read().unwrap();
-        self.associative_referrer
+        let kind = if self
+            .associative_referrer
+            .read()
+            .unwrap()
+            .contains_key(&read.id)
+        {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.associative_referrer_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        self.associative_referrer_by_cardinality
+            .write()
+            .unwrap()
+            .insert(read.id, read.cardinality);
+        let old = self
+            .associative_referrer
             .write()
             .unwrap()
             .insert(read.id, (associative_referrer.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_associative_referrer(old),
+            None => drop(store.exorcise_associative_referrer(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::AssociativeReferrer,
+            id,
+        });
     }
 
     /// Exhume (get) [`AssociativeReferrer`] from the store.
@@ -413,11 +1195,69 @@ impl ObjectStore {
         &mut self,
         id: &Uuid,
     ) -> Option<Arc<RwLock<AssociativeReferrer>>> {
-        self.associative_referrer
+        self.associative_referrer_by_obj_id
+            .write()
+            .unwrap()
+            .remove(id);
+        self.associative_referrer_by_cardinality
+            .write()
+            .unwrap()
+            .remove(id);
+        let removed = self
+            .associative_referrer
             .write()
             .unwrap()
             .remove(id)
-            .map(|associative_referrer| associative_referrer.0.clone())
+            .map(|associative_referrer| associative_referrer.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_associative_referrer(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::AssociativeReferrer,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`AssociativeReferrer`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_associative_referrer_by_obj_id(
+        &self,
+        obj_id: &Uuid,
+    ) -> Vec<Arc<RwLock<AssociativeReferrer>>> {
+        let table = self.associative_referrer.read().unwrap();
+        self.associative_referrer_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|associative_referrer| associative_referrer.0.clone())
+            })
+            .collect()
+    }
+
+    /// Exhume the [`AssociativeReferrer`] instances that refer to `cardinality`.
+    ///
+    pub fn exhume_associative_referrer_by_cardinality(
+        &self,
+        cardinality: &Uuid,
+    ) -> Vec<Arc<RwLock<AssociativeReferrer>>> {
+        let table = self.associative_referrer.read().unwrap();
+        self.associative_referrer_by_cardinality
+            .read()
+            .unwrap()
+            .get(cardinality)
+            .filter_map(|id| {
+                table
+                    .get(id)
+                    .map(|associative_referrer| associative_referrer.0.clone())
+            })
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, AssociativeReferrer>`.
@@ -454,10 +1294,35 @@ impl ObjectStore {
     ///
     pub fn inter_attribute(&mut self, attribute: Arc<RwLock<Attribute>>) {
         let read = attribute.read().unwrap();
-        self.attribute
+        let kind = if self.attribute.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.attribute_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        self.attribute_by_ty
+            .write()
+            .unwrap()
+            .insert(read.id, read.ty);
+        let old = self
+            .attribute
             .write()
             .unwrap()
             .insert(read.id, (attribute.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_attribute(old),
+            None => drop(store.exorcise_attribute(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Attribute,
+            id,
+        });
     }
 
     /// Exhume (get) [`Attribute`] from the store.
@@ -473,21 +1338,59 @@ impl ObjectStore {
     /// Exorcise (remove) [`Attribute`] from the store.
     ///
     pub fn exorcise_attribute(&mut self, id: &Uuid) -> Option<Arc<RwLock<Attribute>>> {
-        self.attribute
+        self.attribute_by_obj_id.write().unwrap().remove(id);
+        self.attribute_by_ty.write().unwrap().remove(id);
+        let removed = self
+            .attribute
             .write()
             .unwrap()
             .remove(id)
-            .map(|attribute| attribute.0.clone())
+            .map(|attribute| attribute.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_attribute(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Attribute,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
-    /// Get an iterator over the internal `HashMap<&Uuid, Attribute>`.
+    /// Exhume the [`Attribute`] instances that refer to `obj_id`.
     ///
-    pub fn iter_attribute(&self) -> impl Iterator<Item = Arc<RwLock<Attribute>>> + '_ {
-        let values: Vec<Arc<RwLock<Attribute>>> = self
-            .attribute
+    pub fn exhume_attribute_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Attribute>>> {
+        let table = self.attribute.read().unwrap();
+        self.attribute_by_obj_id
             .read()
             .unwrap()
-            .values()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|attribute| attribute.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Attribute`] instances that refer to `ty`.
+    ///
+    pub fn exhume_attribute_by_ty(&self, ty: &Uuid) -> Vec<Arc<RwLock<Attribute>>> {
+        let table = self.attribute.read().unwrap();
+        self.attribute_by_ty
+            .read()
+            .unwrap()
+            .get(ty)
+            .filter_map(|id| table.get(id).map(|attribute| attribute.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Attribute>`.
+    ///
+    pub fn iter_attribute(&self) -> impl Iterator<Item = Arc<RwLock<Attribute>>> + '_ {
+        let values: Vec<Arc<RwLock<Attribute>>> = self
+            .attribute
+            .read()
+            .unwrap()
+            .values()
             .map(|attribute| attribute.0.clone())
             .collect();
         let len = values.len();
@@ -509,10 +1412,32 @@ impl ObjectStore {
     ///
     pub fn inter_binary(&mut self, binary: Arc<RwLock<Binary>>) {
         let read = binary.read().unwrap();
-        self.binary
+        let kind = if self.binary.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.binary_by_from
+            .write()
+            .unwrap()
+            .insert(read.id, read.from);
+        self.binary_by_to.write().unwrap().insert(read.id, read.to);
+        let old = self
+            .binary
             .write()
             .unwrap()
             .insert(read.id, (binary.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_binary(old),
+            None => drop(store.exorcise_binary(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Binary,
+            id,
+        });
     }
 
     /// Exhume (get) [`Binary`] from the store.
@@ -528,11 +1453,49 @@ impl ObjectStore {
     /// Exorcise (remove) [`Binary`] from the store.
     ///
     pub fn exorcise_binary(&mut self, id: &Uuid) -> Option<Arc<RwLock<Binary>>> {
-        self.binary
+        self.binary_by_from.write().unwrap().remove(id);
+        self.binary_by_to.write().unwrap().remove(id);
+        let removed = self
+            .binary
             .write()
             .unwrap()
             .remove(id)
-            .map(|binary| binary.0.clone())
+            .map(|binary| binary.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_binary(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Binary,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Binary`] instances that refer to `from`.
+    ///
+    pub fn exhume_binary_by_from(&self, from: &Uuid) -> Vec<Arc<RwLock<Binary>>> {
+        let table = self.binary.read().unwrap();
+        self.binary_by_from
+            .read()
+            .unwrap()
+            .get(from)
+            .filter_map(|id| table.get(id).map(|binary| binary.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Binary`] instances that refer to `to`.
+    ///
+    pub fn exhume_binary_by_to(&self, to: &Uuid) -> Vec<Arc<RwLock<Binary>>> {
+        let table = self.binary.read().unwrap();
+        self.binary_by_to
+            .read()
+            .unwrap()
+            .get(to)
+            .filter_map(|id| table.get(id).map(|binary| binary.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Binary>`.
@@ -564,10 +1527,27 @@ impl ObjectStore {
     ///
     pub fn inter_cardinality(&mut self, cardinality: Arc<RwLock<Cardinality>>) {
         let read = cardinality.read().unwrap();
-        self.cardinality
+        let kind = if self.cardinality.read().unwrap().contains_key(&read.id()) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self
+            .cardinality
             .write()
             .unwrap()
             .insert(read.id(), (cardinality.clone(), SystemTime::now()));
+        let id = read.id();
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_cardinality(old),
+            None => drop(store.exorcise_cardinality(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Cardinality,
+            id,
+        });
     }
 
     /// Exhume (get) [`Cardinality`] from the store.
@@ -583,11 +1563,23 @@ impl ObjectStore {
     /// Exorcise (remove) [`Cardinality`] from the store.
     ///
     pub fn exorcise_cardinality(&mut self, id: &Uuid) -> Option<Arc<RwLock<Cardinality>>> {
-        self.cardinality
+        let removed = self
+            .cardinality
             .write()
             .unwrap()
             .remove(id)
-            .map(|cardinality| cardinality.0.clone())
+            .map(|cardinality| cardinality.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_cardinality(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Cardinality,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Cardinality>`.
@@ -619,10 +1611,27 @@ impl ObjectStore {
     ///
     pub fn inter_conditionality(&mut self, conditionality: Arc<RwLock<Conditionality>>) {
         let read = conditionality.read().unwrap();
-        self.conditionality
+        let kind = if self.conditionality.read().unwrap().contains_key(&read.id()) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self
+            .conditionality
             .write()
             .unwrap()
             .insert(read.id(), (conditionality.clone(), SystemTime::now()));
+        let id = read.id();
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_conditionality(old),
+            None => drop(store.exorcise_conditionality(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Conditionality,
+            id,
+        });
     }
 
     /// Exhume (get) [`Conditionality`] from the store.
@@ -638,11 +1647,23 @@ impl ObjectStore {
     /// Exorcise (remove) [`Conditionality`] from the store.
     ///
     pub fn exorcise_conditionality(&mut self, id: &Uuid) -> Option<Arc<RwLock<Conditionality>>> {
-        self.conditionality
+        let removed = self
+            .conditionality
             .write()
             .unwrap()
             .remove(id)
-            .map(|conditionality| conditionality.0.clone())
+            .map(|conditionality| conditionality.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_conditionality(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Conditionality,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Conditionality>`.
@@ -670,14 +1691,223 @@ impl ObjectStore {
             .unwrap_or(SystemTime::now())
     }
 
+    /// Inter (insert) [`Enumeration`] into the store.
+    ///
+    pub fn inter_enumeration(&mut self, enumeration: Arc<RwLock<Enumeration>>) {
+        let read = enumeration.read().unwrap();
+        let kind = if self.enumeration.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self
+            .enumeration
+            .write()
+            .unwrap()
+            .insert(read.id, (enumeration.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_enumeration(old),
+            None => drop(store.exorcise_enumeration(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Enumeration,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`Enumeration`] from the store.
+    ///
+    pub fn exhume_enumeration(&self, id: &Uuid) -> Option<Arc<RwLock<Enumeration>>> {
+        self.enumeration
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|enumeration| enumeration.0.clone())
+    }
+
+    /// Exorcise (remove) [`Enumeration`] from the store.
+    ///
+    pub fn exorcise_enumeration(&mut self, id: &Uuid) -> Option<Arc<RwLock<Enumeration>>> {
+        let removed = self
+            .enumeration
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|enumeration| enumeration.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_enumeration(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Enumeration,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Enumeration>`.
+    ///
+    pub fn iter_enumeration(&self) -> impl Iterator<Item = Arc<RwLock<Enumeration>>> + '_ {
+        let values: Vec<Arc<RwLock<Enumeration>>> = self
+            .enumeration
+            .read()
+            .unwrap()
+            .values()
+            .map(|enumeration| enumeration.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Enumeration.
+    ///
+    pub fn enumeration_timestamp(&self, enumeration: &Enumeration) -> SystemTime {
+        self.enumeration
+            .read()
+            .unwrap()
+            .get(&enumeration.id)
+            .map(|enumeration| enumeration.1)
+            .unwrap_or(SystemTime::now())
+    }
+
+    /// Inter (insert) [`Enumerator`] into the store.
+    ///
+    pub fn inter_enumerator(&mut self, enumerator: Arc<RwLock<Enumerator>>) {
+        let read = enumerator.read().unwrap();
+        let kind = if self.enumerator.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.enumerator_by_enumeration
+            .write()
+            .unwrap()
+            .insert(read.id, read.enumeration);
+        let old = self
+            .enumerator
+            .write()
+            .unwrap()
+            .insert(read.id, (enumerator.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_enumerator(old),
+            None => drop(store.exorcise_enumerator(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Enumerator,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`Enumerator`] from the store.
+    ///
+    pub fn exhume_enumerator(&self, id: &Uuid) -> Option<Arc<RwLock<Enumerator>>> {
+        self.enumerator
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|enumerator| enumerator.0.clone())
+    }
+
+    /// Exorcise (remove) [`Enumerator`] from the store.
+    ///
+    pub fn exorcise_enumerator(&mut self, id: &Uuid) -> Option<Arc<RwLock<Enumerator>>> {
+        self.enumerator_by_enumeration.write().unwrap().remove(id);
+        let removed = self
+            .enumerator
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|enumerator| enumerator.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_enumerator(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Enumerator,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Enumerator`] instances that refer to `enumeration`.
+    ///
+    pub fn exhume_enumerator_by_enumeration(
+        &self,
+        enumeration: &Uuid,
+    ) -> Vec<Arc<RwLock<Enumerator>>> {
+        let table = self.enumerator.read().unwrap();
+        self.enumerator_by_enumeration
+            .read()
+            .unwrap()
+            .get(enumeration)
+            .filter_map(|id| table.get(id).map(|enumerator| enumerator.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Enumerator>`.
+    ///
+    pub fn iter_enumerator(&self) -> impl Iterator<Item = Arc<RwLock<Enumerator>>> + '_ {
+        let values: Vec<Arc<RwLock<Enumerator>>> = self
+            .enumerator
+            .read()
+            .unwrap()
+            .values()
+            .map(|enumerator| enumerator.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Enumerator.
+    ///
+    pub fn enumerator_timestamp(&self, enumerator: &Enumerator) -> SystemTime {
+        self.enumerator
+            .read()
+            .unwrap()
+            .get(&enumerator.id)
+            .map(|enumerator| enumerator.1)
+            .unwrap_or(SystemTime::now())
+    }
+
     /// Inter (insert) [`Event`] into the store.
     ///
     pub fn inter_event(&mut self, event: Arc<RwLock<Event>>) {
         let read = event.read().unwrap();
-        self.event
+        let kind = if self.event.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.event_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        let old = self
+            .event
             .write()
             .unwrap()
             .insert(read.id, (event.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_event(old),
+            None => drop(store.exorcise_event(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Event,
+            id,
+        });
     }
 
     /// Exhume (get) [`Event`] from the store.
@@ -693,11 +1923,35 @@ impl ObjectStore {
     /// Exorcise (remove) [`Event`] from the store.
     ///
     pub fn exorcise_event(&mut self, id: &Uuid) -> Option<Arc<RwLock<Event>>> {
-        self.event
+        self.event_by_obj_id.write().unwrap().remove(id);
+        let removed = self
+            .event
             .write()
             .unwrap()
             .remove(id)
-            .map(|event| event.0.clone())
+            .map(|event| event.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_event(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Event,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Event`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_event_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Event>>> {
+        let table = self.event.read().unwrap();
+        self.event_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|event| event.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Event>`.
@@ -729,10 +1983,27 @@ impl ObjectStore {
     ///
     pub fn inter_external(&mut self, external: Arc<RwLock<External>>) {
         let read = external.read().unwrap();
-        self.external
+        let kind = if self.external.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self
+            .external
             .write()
             .unwrap()
             .insert(read.id, (external.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_external(old),
+            None => drop(store.exorcise_external(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::External,
+            id,
+        });
     }
 
     /// Exhume (get) [`External`] from the store.
@@ -748,11 +2019,23 @@ impl ObjectStore {
     /// Exorcise (remove) [`External`] from the store.
     ///
     pub fn exorcise_external(&mut self, id: &Uuid) -> Option<Arc<RwLock<External>>> {
-        self.external
+        let removed = self
+            .external
             .write()
             .unwrap()
             .remove(id)
-            .map(|external| external.0.clone())
+            .map(|external| external.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_external(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::External,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, External>`.
@@ -784,10 +2067,31 @@ impl ObjectStore {
     ///
     pub fn inter_isa(&mut self, isa: Arc<RwLock<Isa>>) {
         let read = isa.read().unwrap();
-        self.isa
+        let kind = if self.isa.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.isa_by_supertype
+            .write()
+            .unwrap()
+            .insert(read.id, read.supertype);
+        let old = self
+            .isa
             .write()
             .unwrap()
             .insert(read.id, (isa.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_isa(old),
+            None => drop(store.exorcise_isa(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Isa,
+            id,
+        });
     }
 
     /// Exhume (get) [`Isa`] from the store.
@@ -799,11 +2103,35 @@ impl ObjectStore {
     /// Exorcise (remove) [`Isa`] from the store.
     ///
     pub fn exorcise_isa(&mut self, id: &Uuid) -> Option<Arc<RwLock<Isa>>> {
-        self.isa
+        self.isa_by_supertype.write().unwrap().remove(id);
+        let removed = self
+            .isa
             .write()
             .unwrap()
             .remove(id)
-            .map(|isa| isa.0.clone())
+            .map(|isa| isa.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_isa(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Isa,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Isa`] instances that refer to `supertype`.
+    ///
+    pub fn exhume_isa_by_supertype(&self, supertype: &Uuid) -> Vec<Arc<RwLock<Isa>>> {
+        let table = self.isa.read().unwrap();
+        self.isa_by_supertype
+            .read()
+            .unwrap()
+            .get(supertype)
+            .filter_map(|id| table.get(id).map(|isa| isa.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Isa>`.
@@ -831,16 +2159,136 @@ impl ObjectStore {
             .unwrap_or(SystemTime::now())
     }
 
+    /// Inter (insert) [`NextState`] into the store.
+    ///
+    pub fn inter_next_state(&mut self, next_state: Arc<RwLock<NextState>>) {
+        let read = next_state.read().unwrap();
+        let kind = if self.next_state.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.next_state_by_to
+            .write()
+            .unwrap()
+            .insert(read.id, read.to);
+        let old = self
+            .next_state
+            .write()
+            .unwrap()
+            .insert(read.id, (next_state.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_next_state(old),
+            None => drop(store.exorcise_next_state(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::NextState,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`NextState`] from the store.
+    ///
+    pub fn exhume_next_state(&self, id: &Uuid) -> Option<Arc<RwLock<NextState>>> {
+        self.next_state
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|next_state| next_state.0.clone())
+    }
+
+    /// Exorcise (remove) [`NextState`] from the store.
+    ///
+    pub fn exorcise_next_state(&mut self, id: &Uuid) -> Option<Arc<RwLock<NextState>>> {
+        self.next_state_by_to.write().unwrap().remove(id);
+        let removed = self
+            .next_state
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|next_state| next_state.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_next_state(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::NextState,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`NextState`] instances that refer to `to`.
+    ///
+    pub fn exhume_next_state_by_to(&self, to: &Uuid) -> Vec<Arc<RwLock<NextState>>> {
+        let table = self.next_state.read().unwrap();
+        self.next_state_by_to
+            .read()
+            .unwrap()
+            .get(to)
+            .filter_map(|id| table.get(id).map(|next_state| next_state.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, NextState>`.
+    ///
+    pub fn iter_next_state(&self) -> impl Iterator<Item = Arc<RwLock<NextState>>> + '_ {
+        let values: Vec<Arc<RwLock<NextState>>> = self
+            .next_state
+            .read()
+            .unwrap()
+            .values()
+            .map(|next_state| next_state.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for NextState.
+    ///
+    pub fn next_state_timestamp(&self, next_state: &NextState) -> SystemTime {
+        self.next_state
+            .read()
+            .unwrap()
+            .get(&next_state.id)
+            .map(|next_state| next_state.1)
+            .unwrap_or(SystemTime::now())
+    }
+
     /// Inter (insert) [`Object`] into the store.
     ///
     pub fn inter_object(&mut self, object: Arc<RwLock<Object>>) {
         let read = object.read().unwrap();
+        let kind = if self.object.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
         let value = (object.clone(), SystemTime::now());
         self.object_id_by_name
             .write()
             .unwrap()
             .insert(read.name.to_upper_camel_case(), (read.id, value.1));
-        self.object.write().unwrap().insert(read.id, value);
+        let old = self.object.write().unwrap().insert(read.id, value);
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => {
+                store.exorcise_object(&id);
+                store.inter_object(old);
+            }
+            None => drop(store.exorcise_object(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Object,
+            id,
+        });
     }
 
     /// Exhume (get) [`Object`] from the store.
@@ -856,11 +2304,27 @@ impl ObjectStore {
     /// Exorcise (remove) [`Object`] from the store.
     ///
     pub fn exorcise_object(&mut self, id: &Uuid) -> Option<Arc<RwLock<Object>>> {
-        self.object
+        self.object_id_by_name
+            .write()
+            .unwrap()
+            .retain(|_, (object_id, _)| object_id != id);
+        let removed = self
+            .object
             .write()
             .unwrap()
             .remove(id)
-            .map(|object| object.0.clone())
+            .map(|object| object.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_object(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Object,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Exhume [`Object`] id from the store by name.
@@ -898,54 +2362,373 @@ impl ObjectStore {
             .unwrap_or(SystemTime::now())
     }
 
-    /// Inter (insert) [`Referent`] into the store.
+    /// Inter (insert) [`Operation`] into the store.
     ///
-    pub fn inter_referent(&mut self, referent: Arc<RwLock<Referent>>) {
-        let read = referent.read().unwrap();
-        self.referent
+    pub fn inter_operation(&mut self, operation: Arc<RwLock<Operation>>) {
+        let read = operation.read().unwrap();
+        let kind = if self.operation.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.operation_by_obj_id
             .write()
             .unwrap()
-            .insert(read.id, (referent.clone(), SystemTime::now()));
+            .insert(read.id, read.obj_id);
+        match read.ret {
+            Some(ret) => self.operation_by_ret.write().unwrap().insert(read.id, ret),
+            None => self.operation_by_ret.write().unwrap().remove(&read.id),
+        }
+        let old = self
+            .operation
+            .write()
+            .unwrap()
+            .insert(read.id, (operation.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_operation(old),
+            None => drop(store.exorcise_operation(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Operation,
+            id,
+        });
     }
 
-    /// Exhume (get) [`Referent`] from the store.
+    /// Exhume (get) [`Operation`] from the store.
     ///
-    pub fn exhume_referent(&self, id: &Uuid) -> Option<Arc<RwLock<Referent>>> {
-        self.referent
+    pub fn exhume_operation(&self, id: &Uuid) -> Option<Arc<RwLock<Operation>>> {
+        self.operation
             .read()
             .unwrap()
             .get(id)
-            .map(|referent| referent.0.clone())
+            .map(|operation| operation.0.clone())
     }
 
-    /// Exorcise (remove) [`Referent`] from the store.
+    /// Exorcise (remove) [`Operation`] from the store.
     ///
-    pub fn exorcise_referent(&mut self, id: &Uuid) -> Option<Arc<RwLock<Referent>>> {
-        self.referent
+    pub fn exorcise_operation(&mut self, id: &Uuid) -> Option<Arc<RwLock<Operation>>> {
+        self.operation_by_obj_id.write().unwrap().remove(id);
+        self.operation_by_ret.write().unwrap().remove(id);
+        let removed = self
+            .operation
             .write()
             .unwrap()
             .remove(id)
-            .map(|referent| referent.0.clone())
+            .map(|operation| operation.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_operation(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Operation,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
-    /// Get an iterator over the internal `HashMap<&Uuid, Referent>`.
+    /// Exhume the [`Operation`] instances that refer to `obj_id`.
     ///
-    pub fn iter_referent(&self) -> impl Iterator<Item = Arc<RwLock<Referent>>> + '_ {
-        let values: Vec<Arc<RwLock<Referent>>> = self
-            .referent
+    pub fn exhume_operation_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Operation>>> {
+        let table = self.operation.read().unwrap();
+        self.operation_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|operation| operation.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Operation`] instances that return `ret`.
+    ///
+    pub fn exhume_operation_by_ret(&self, ret: &Uuid) -> Vec<Arc<RwLock<Operation>>> {
+        let table = self.operation.read().unwrap();
+        self.operation_by_ret
+            .read()
+            .unwrap()
+            .get(ret)
+            .filter_map(|id| table.get(id).map(|operation| operation.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Operation>`.
+    ///
+    pub fn iter_operation(&self) -> impl Iterator<Item = Arc<RwLock<Operation>>> + '_ {
+        let values: Vec<Arc<RwLock<Operation>>> = self
+            .operation
             .read()
             .unwrap()
             .values()
-            .map(|referent| referent.0.clone())
+            .map(|operation| operation.0.clone())
             .collect();
         let len = values.len();
         (0..len).map(move |i| values[i].clone())
     }
 
-    /// Get the timestamp for Referent.
+    /// Get the timestamp for Operation.
     ///
-    pub fn referent_timestamp(&self, referent: &Referent) -> SystemTime {
-        self.referent
+    pub fn operation_timestamp(&self, operation: &Operation) -> SystemTime {
+        self.operation
+            .read()
+            .unwrap()
+            .get(&operation.id)
+            .map(|operation| operation.1)
+            .unwrap_or(SystemTime::now())
+    }
+
+    /// Inter (insert) [`Parameter`] into the store.
+    ///
+    pub fn inter_parameter(&mut self, parameter: Arc<RwLock<Parameter>>) {
+        let read = parameter.read().unwrap();
+        let kind = if self.parameter.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.parameter_by_event
+            .write()
+            .unwrap()
+            .insert(read.id, read.event);
+        self.parameter_by_ty
+            .write()
+            .unwrap()
+            .insert(read.id, read.ty);
+        let old = self
+            .parameter
+            .write()
+            .unwrap()
+            .insert(read.id, (parameter.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_parameter(old),
+            None => drop(store.exorcise_parameter(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Parameter,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`Parameter`] from the store.
+    ///
+    pub fn exhume_parameter(&self, id: &Uuid) -> Option<Arc<RwLock<Parameter>>> {
+        self.parameter
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|parameter| parameter.0.clone())
+    }
+
+    /// Exorcise (remove) [`Parameter`] from the store.
+    ///
+    pub fn exorcise_parameter(&mut self, id: &Uuid) -> Option<Arc<RwLock<Parameter>>> {
+        self.parameter_by_event.write().unwrap().remove(id);
+        self.parameter_by_ty.write().unwrap().remove(id);
+        let removed = self
+            .parameter
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|parameter| parameter.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_parameter(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Parameter,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Parameter`] instances that refer to `event`.
+    ///
+    pub fn exhume_parameter_by_event(&self, event: &Uuid) -> Vec<Arc<RwLock<Parameter>>> {
+        let table = self.parameter.read().unwrap();
+        self.parameter_by_event
+            .read()
+            .unwrap()
+            .get(event)
+            .filter_map(|id| table.get(id).map(|parameter| parameter.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Parameter`] instances that refer to `ty`.
+    ///
+    pub fn exhume_parameter_by_ty(&self, ty: &Uuid) -> Vec<Arc<RwLock<Parameter>>> {
+        let table = self.parameter.read().unwrap();
+        self.parameter_by_ty
+            .read()
+            .unwrap()
+            .get(ty)
+            .filter_map(|id| table.get(id).map(|parameter| parameter.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Parameter>`.
+    ///
+    pub fn iter_parameter(&self) -> impl Iterator<Item = Arc<RwLock<Parameter>>> + '_ {
+        let values: Vec<Arc<RwLock<Parameter>>> = self
+            .parameter
+            .read()
+            .unwrap()
+            .values()
+            .map(|parameter| parameter.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Parameter.
+    ///
+    pub fn parameter_timestamp(&self, parameter: &Parameter) -> SystemTime {
+        self.parameter
+            .read()
+            .unwrap()
+            .get(&parameter.id)
+            .map(|parameter| parameter.1)
+            .unwrap_or(SystemTime::now())
+    }
+
+    /// Inter (insert) [`Referent`] into the store.
+    ///
+    pub fn inter_referent(&mut self, referent: Arc<RwLock<Referent>>) {
+        let read = referent.read().unwrap();
+        let kind = if self.referent.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.referent_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        self.referent_by_cardinality
+            .write()
+            .unwrap()
+            .insert(read.id, read.cardinality);
+        self.referent_by_conditionality
+            .write()
+            .unwrap()
+            .insert(read.id, read.conditionality);
+        let old = self
+            .referent
+            .write()
+            .unwrap()
+            .insert(read.id, (referent.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_referent(old),
+            None => drop(store.exorcise_referent(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Referent,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`Referent`] from the store.
+    ///
+    pub fn exhume_referent(&self, id: &Uuid) -> Option<Arc<RwLock<Referent>>> {
+        self.referent
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|referent| referent.0.clone())
+    }
+
+    /// Exorcise (remove) [`Referent`] from the store.
+    ///
+    pub fn exorcise_referent(&mut self, id: &Uuid) -> Option<Arc<RwLock<Referent>>> {
+        self.referent_by_obj_id.write().unwrap().remove(id);
+        self.referent_by_cardinality.write().unwrap().remove(id);
+        self.referent_by_conditionality.write().unwrap().remove(id);
+        let removed = self
+            .referent
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|referent| referent.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_referent(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Referent,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Referent`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_referent_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Referent>>> {
+        let table = self.referent.read().unwrap();
+        self.referent_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|referent| referent.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Referent`] instances that refer to `cardinality`.
+    ///
+    pub fn exhume_referent_by_cardinality(&self, cardinality: &Uuid) -> Vec<Arc<RwLock<Referent>>> {
+        let table = self.referent.read().unwrap();
+        self.referent_by_cardinality
+            .read()
+            .unwrap()
+            .get(cardinality)
+            .filter_map(|id| table.get(id).map(|referent| referent.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Referent`] instances that refer to `conditionality`.
+    ///
+    pub fn exhume_referent_by_conditionality(
+        &self,
+        conditionality: &Uuid,
+    ) -> Vec<Arc<RwLock<Referent>>> {
+        let table = self.referent.read().unwrap();
+        self.referent_by_conditionality
+            .read()
+            .unwrap()
+            .get(conditionality)
+            .filter_map(|id| table.get(id).map(|referent| referent.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Referent>`.
+    ///
+    pub fn iter_referent(&self) -> impl Iterator<Item = Arc<RwLock<Referent>>> + '_ {
+        let values: Vec<Arc<RwLock<Referent>>> = self
+            .referent
+            .read()
+            .unwrap()
+            .values()
+            .map(|referent| referent.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Referent.
+    ///
+    pub fn referent_timestamp(&self, referent: &Referent) -> SystemTime {
+        self.referent
             .read()
             .unwrap()
             .get(&referent.id)
@@ -957,10 +2740,39 @@ impl ObjectStore {
     ///
     pub fn inter_referrer(&mut self, referrer: Arc<RwLock<Referrer>>) {
         let read = referrer.read().unwrap();
-        self.referrer
+        let kind = if self.referrer.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.referrer_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        self.referrer_by_cardinality
+            .write()
+            .unwrap()
+            .insert(read.id, read.cardinality);
+        self.referrer_by_conditionality
+            .write()
+            .unwrap()
+            .insert(read.id, read.conditionality);
+        let old = self
+            .referrer
             .write()
             .unwrap()
             .insert(read.id, (referrer.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_referrer(old),
+            None => drop(store.exorcise_referrer(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Referrer,
+            id,
+        });
     }
 
     /// Exhume (get) [`Referrer`] from the store.
@@ -976,11 +2788,65 @@ impl ObjectStore {
     /// Exorcise (remove) [`Referrer`] from the store.
     ///
     pub fn exorcise_referrer(&mut self, id: &Uuid) -> Option<Arc<RwLock<Referrer>>> {
-        self.referrer
+        self.referrer_by_obj_id.write().unwrap().remove(id);
+        self.referrer_by_cardinality.write().unwrap().remove(id);
+        self.referrer_by_conditionality.write().unwrap().remove(id);
+        let removed = self
+            .referrer
             .write()
             .unwrap()
             .remove(id)
-            .map(|referrer| referrer.0.clone())
+            .map(|referrer| referrer.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_referrer(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Referrer,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Referrer`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_referrer_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Referrer>>> {
+        let table = self.referrer.read().unwrap();
+        self.referrer_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|referrer| referrer.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Referrer`] instances that refer to `cardinality`.
+    ///
+    pub fn exhume_referrer_by_cardinality(&self, cardinality: &Uuid) -> Vec<Arc<RwLock<Referrer>>> {
+        let table = self.referrer.read().unwrap();
+        self.referrer_by_cardinality
+            .read()
+            .unwrap()
+            .get(cardinality)
+            .filter_map(|id| table.get(id).map(|referrer| referrer.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Referrer`] instances that refer to `conditionality`.
+    ///
+    pub fn exhume_referrer_by_conditionality(
+        &self,
+        conditionality: &Uuid,
+    ) -> Vec<Arc<RwLock<Referrer>>> {
+        let table = self.referrer.read().unwrap();
+        self.referrer_by_conditionality
+            .read()
+            .unwrap()
+            .get(conditionality)
+            .filter_map(|id| table.get(id).map(|referrer| referrer.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Referrer>`.
@@ -1012,10 +2878,27 @@ impl ObjectStore {
     ///
     pub fn inter_relationship(&mut self, relationship: Arc<RwLock<Relationship>>) {
         let read = relationship.read().unwrap();
-        self.relationship
+        let kind = if self.relationship.read().unwrap().contains_key(&read.id()) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self
+            .relationship
             .write()
             .unwrap()
             .insert(read.id(), (relationship.clone(), SystemTime::now()));
+        let id = read.id();
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_relationship(old),
+            None => drop(store.exorcise_relationship(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Relationship,
+            id,
+        });
     }
 
     /// Exhume (get) [`Relationship`] from the store.
@@ -1031,11 +2914,23 @@ impl ObjectStore {
     /// Exorcise (remove) [`Relationship`] from the store.
     ///
     pub fn exorcise_relationship(&mut self, id: &Uuid) -> Option<Arc<RwLock<Relationship>>> {
-        self.relationship
+        let removed = self
+            .relationship
             .write()
             .unwrap()
             .remove(id)
-            .map(|relationship| relationship.0.clone())
+            .map(|relationship| relationship.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_relationship(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Relationship,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Relationship>`.
@@ -1067,10 +2962,31 @@ impl ObjectStore {
     ///
     pub fn inter_state(&mut self, state: Arc<RwLock<State>>) {
         let read = state.read().unwrap();
-        self.state
+        let kind = if self.state.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.state_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        let old = self
+            .state
             .write()
             .unwrap()
             .insert(read.id, (state.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_state(old),
+            None => drop(store.exorcise_state(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::State,
+            id,
+        });
     }
 
     /// Exhume (get) [`State`] from the store.
@@ -1086,11 +3002,35 @@ impl ObjectStore {
     /// Exorcise (remove) [`State`] from the store.
     ///
     pub fn exorcise_state(&mut self, id: &Uuid) -> Option<Arc<RwLock<State>>> {
-        self.state
+        self.state_by_obj_id.write().unwrap().remove(id);
+        let removed = self
+            .state
             .write()
             .unwrap()
             .remove(id)
-            .map(|state| state.0.clone())
+            .map(|state| state.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_state(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::State,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`State`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_state_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<State>>> {
+        let table = self.state.read().unwrap();
+        self.state_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|state| state.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, State>`.
@@ -1122,10 +3062,35 @@ impl ObjectStore {
     ///
     pub fn inter_subtype(&mut self, subtype: Arc<RwLock<Subtype>>) {
         let read = subtype.read().unwrap();
-        self.subtype
+        let kind = if self.subtype.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.subtype_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        self.subtype_by_isa
+            .write()
+            .unwrap()
+            .insert(read.id, read.isa);
+        let old = self
+            .subtype
             .write()
             .unwrap()
             .insert(read.id, (subtype.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_subtype(old),
+            None => drop(store.exorcise_subtype(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Subtype,
+            id,
+        });
     }
 
     /// Exhume (get) [`Subtype`] from the store.
@@ -1141,11 +3106,49 @@ impl ObjectStore {
     /// Exorcise (remove) [`Subtype`] from the store.
     ///
     pub fn exorcise_subtype(&mut self, id: &Uuid) -> Option<Arc<RwLock<Subtype>>> {
-        self.subtype
+        self.subtype_by_obj_id.write().unwrap().remove(id);
+        self.subtype_by_isa.write().unwrap().remove(id);
+        let removed = self
+            .subtype
             .write()
             .unwrap()
             .remove(id)
-            .map(|subtype| subtype.0.clone())
+            .map(|subtype| subtype.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_subtype(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Subtype,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Subtype`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_subtype_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Subtype>>> {
+        let table = self.subtype.read().unwrap();
+        self.subtype_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|subtype| subtype.0.clone()))
+            .collect()
+    }
+
+    /// Exhume the [`Subtype`] instances that refer to `isa`.
+    ///
+    pub fn exhume_subtype_by_isa(&self, isa: &Uuid) -> Vec<Arc<RwLock<Subtype>>> {
+        let table = self.subtype.read().unwrap();
+        self.subtype_by_isa
+            .read()
+            .unwrap()
+            .get(isa)
+            .filter_map(|id| table.get(id).map(|subtype| subtype.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Subtype>`.
@@ -1177,10 +3180,31 @@ impl ObjectStore {
     ///
     pub fn inter_supertype(&mut self, supertype: Arc<RwLock<Supertype>>) {
         let read = supertype.read().unwrap();
-        self.supertype
+        let kind = if self.supertype.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.supertype_by_obj_id
+            .write()
+            .unwrap()
+            .insert(read.id, read.obj_id);
+        let old = self
+            .supertype
             .write()
             .unwrap()
             .insert(read.id, (supertype.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_supertype(old),
+            None => drop(store.exorcise_supertype(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Supertype,
+            id,
+        });
     }
 
     /// Exhume (get) [`Supertype`] from the store.
@@ -1196,11 +3220,36 @@ impl ObjectStore {
     /// Exorcise (remove) [`Supertype`] from the store.
     ///
     pub fn exorcise_supertype(&mut self, id: &Uuid) -> Option<Arc<RwLock<Supertype>>> {
-        self.supertype
+        self.supertype_by_obj_id.write().unwrap().remove(id);
+        let removed = self
+            .supertype
             .write()
             .unwrap()
             .remove(id)
-            .map(|supertype| supertype.0.clone())
+            .map(|supertype| supertype.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_supertype(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Supertype,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Exhume the [`Supertype`] instances that refer to `obj_id`.
+    ///
+    pub fn exhume_supertype_by_obj_id(&self, obj_id: &Uuid) -> Vec<Arc<RwLock<Supertype>>> {
+        let table = self.supertype.read().unwrap();
+        self.supertype_by_obj_id
+            .read()
+            .unwrap()
+            .get(obj_id)
+            .filter_map(|id| table.get(id).map(|supertype| supertype.0.clone()))
+            .collect()
     }
 
     /// Get an iterator over the internal `HashMap<&Uuid, Supertype>`.
@@ -1228,55 +3277,456 @@ impl ObjectStore {
             .unwrap_or(SystemTime::now())
     }
 
-    /// Inter (insert) [`Ty`] into the store.
+    /// Inter (insert) [`Transition`] into the store.
     ///
-    pub fn inter_ty(&mut self, ty: Arc<RwLock<Ty>>) {
-        let read = ty.read().unwrap();
-        self.ty
+    pub fn inter_transition(&mut self, transition: Arc<RwLock<Transition>>) {
+        let read = transition.read().unwrap();
+        let kind = if self.transition.read().unwrap().contains_key(&read.id) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        self.transition_by_from
             .write()
             .unwrap()
-            .insert(read.id(), (ty.clone(), SystemTime::now()));
+            .insert(read.id, read.from);
+        self.transition_by_event
+            .write()
+            .unwrap()
+            .insert(read.id, read.event);
+        let old = self
+            .transition
+            .write()
+            .unwrap()
+            .insert(read.id, (transition.clone(), SystemTime::now()));
+        let id = read.id;
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_transition(old),
+            None => drop(store.exorcise_transition(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Transition,
+            id,
+        });
     }
 
-    /// Exhume (get) [`Ty`] from the store.
+    /// Exhume (get) [`Transition`] from the store.
     ///
-    pub fn exhume_ty(&self, id: &Uuid) -> Option<Arc<RwLock<Ty>>> {
-        self.ty.read().unwrap().get(id).map(|ty| ty.0.clone())
+    pub fn exhume_transition(&self, id: &Uuid) -> Option<Arc<RwLock<Transition>>> {
+        self.transition
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|transition| transition.0.clone())
     }
 
-    /// Exorcise (remove) [`Ty`] from the store.
+    /// Exorcise (remove) [`Transition`] from the store.
     ///
-    pub fn exorcise_ty(&mut self, id: &Uuid) -> Option<Arc<RwLock<Ty>>> {
-        self.ty.write().unwrap().remove(id).map(|ty| ty.0.clone())
+    pub fn exorcise_transition(&mut self, id: &Uuid) -> Option<Arc<RwLock<Transition>>> {
+        self.transition_by_from.write().unwrap().remove(id);
+        self.transition_by_event.write().unwrap().remove(id);
+        let removed = self
+            .transition
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|transition| transition.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal
+                .record(move |store| store.inter_transition(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Transition,
+                id: *id,
+            });
+        }
+
+        removed
     }
 
-    /// Get an iterator over the internal `HashMap<&Uuid, Ty>`.
+    /// Exhume the [`Transition`] instances that refer to `from`.
     ///
-    pub fn iter_ty(&self) -> impl Iterator<Item = Arc<RwLock<Ty>>> + '_ {
-        let values: Vec<Arc<RwLock<Ty>>> = self
-            .ty
+    pub fn exhume_transition_by_from(&self, from: &Uuid) -> Vec<Arc<RwLock<Transition>>> {
+        let table = self.transition.read().unwrap();
+        self.transition_by_from
             .read()
             .unwrap()
-            .values()
-            .map(|ty| ty.0.clone())
-            .collect();
-        let len = values.len();
-        (0..len).map(move |i| values[i].clone())
+            .get(from)
+            .filter_map(|id| table.get(id).map(|transition| transition.0.clone()))
+            .collect()
     }
 
-    /// Get the timestamp for Ty.
+    /// Exhume the [`Transition`] instances that refer to `event`.
     ///
-    pub fn ty_timestamp(&self, ty: &Ty) -> SystemTime {
-        self.ty
+    pub fn exhume_transition_by_event(&self, event: &Uuid) -> Vec<Arc<RwLock<Transition>>> {
+        let table = self.transition.read().unwrap();
+        self.transition_by_event
             .read()
             .unwrap()
-            .get(&ty.id())
-            .map(|ty| ty.1)
-            .unwrap_or(SystemTime::now())
+            .get(event)
+            .filter_map(|id| table.get(id).map(|transition| transition.0.clone()))
+            .collect()
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Transition>`.
+    ///
+    pub fn iter_transition(&self) -> impl Iterator<Item = Arc<RwLock<Transition>>> + '_ {
+        let values: Vec<Arc<RwLock<Transition>>> = self
+            .transition
+            .read()
+            .unwrap()
+            .values()
+            .map(|transition| transition.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Transition.
+    ///
+    pub fn transition_timestamp(&self, transition: &Transition) -> SystemTime {
+        self.transition
+            .read()
+            .unwrap()
+            .get(&transition.id)
+            .map(|transition| transition.1)
+            .unwrap_or(SystemTime::now())
+    }
+
+    /// Inter (insert) [`Ty`] into the store.
+    ///
+    pub fn inter_ty(&mut self, ty: Arc<RwLock<Ty>>) {
+        let read = ty.read().unwrap();
+        let kind = if self.ty.read().unwrap().contains_key(&read.id()) {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Inserted
+        };
+        let old = self
+            .ty
+            .write()
+            .unwrap()
+            .insert(read.id(), (ty.clone(), SystemTime::now()));
+        let id = read.id();
+        drop(read);
+        self.journal.record(move |store| match old {
+            Some((old, _)) => store.inter_ty(old),
+            None => drop(store.exorcise_ty(&id)),
+        });
+        self.changed(Change {
+            kind,
+            table: Table::Ty,
+            id,
+        });
+    }
+
+    /// Exhume (get) [`Ty`] from the store.
+    ///
+    pub fn exhume_ty(&self, id: &Uuid) -> Option<Arc<RwLock<Ty>>> {
+        self.ty.read().unwrap().get(id).map(|ty| ty.0.clone())
+    }
+
+    /// Exorcise (remove) [`Ty`] from the store.
+    ///
+    pub fn exorcise_ty(&mut self, id: &Uuid) -> Option<Arc<RwLock<Ty>>> {
+        let removed = self.ty.write().unwrap().remove(id).map(|ty| ty.0.clone());
+        if let Some(removed) = removed.clone() {
+            self.journal.record(move |store| store.inter_ty(removed));
+            self.changed(Change {
+                kind: ChangeKind::Removed,
+                table: Table::Ty,
+                id: *id,
+            });
+        }
+
+        removed
+    }
+
+    /// Get an iterator over the internal `HashMap<&Uuid, Ty>`.
+    ///
+    pub fn iter_ty(&self) -> impl Iterator<Item = Arc<RwLock<Ty>>> + '_ {
+        let values: Vec<Arc<RwLock<Ty>>> = self
+            .ty
+            .read()
+            .unwrap()
+            .values()
+            .map(|ty| ty.0.clone())
+            .collect();
+        let len = values.len();
+        (0..len).map(move |i| values[i].clone())
+    }
+
+    /// Get the timestamp for Ty.
+    ///
+    pub fn ty_timestamp(&self, ty: &Ty) -> SystemTime {
+        self.ty
+            .read()
+            .unwrap()
+            .get(&ty.id())
+            .map(|ty| ty.1)
+            .unwrap_or(SystemTime::now())
+    }
+
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+    /// Rebuild the relationship indexes from the tables
+    ///
+    fn reindex(&self) {
+        self.acknowledged_event_by_event_id.write().unwrap().clear();
+        self.acknowledged_event_by_state_id.write().unwrap().clear();
+        for acknowledged_event in self.acknowledged_event.read().unwrap().values() {
+            let read = acknowledged_event.0.read().unwrap();
+            self.acknowledged_event_by_event_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.event_id);
+            self.acknowledged_event_by_state_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.state_id);
+        }
+        self.an_associative_referent_by_associative
+            .write()
+            .unwrap()
+            .clear();
+        self.an_associative_referent_by_referent
+            .write()
+            .unwrap()
+            .clear();
+        for an_associative_referent in self.an_associative_referent.read().unwrap().values() {
+            let read = an_associative_referent.0.read().unwrap();
+            self.an_associative_referent_by_associative
+                .write()
+                .unwrap()
+                .insert(read.id, read.associative);
+            self.an_associative_referent_by_referent
+                .write()
+                .unwrap()
+                .insert(read.id, read.referent);
+        }
+        self.argument_by_operation.write().unwrap().clear();
+        self.argument_by_ty.write().unwrap().clear();
+        for argument in self.argument.read().unwrap().values() {
+            let read = argument.0.read().unwrap();
+            self.argument_by_operation
+                .write()
+                .unwrap()
+                .insert(read.id, read.operation);
+            self.argument_by_ty
+                .write()
+                .unwrap()
+                .insert(read.id, read.ty);
+        }
+        self.associative_by_from.write().unwrap().clear();
+        for associative in self.associative.read().unwrap().values() {
+            let read = associative.0.read().unwrap();
+            self.associative_by_from
+                .write()
+                .unwrap()
+                .insert(read.id, read.from);
+        }
+        self.associative_referent_by_obj_id.write().unwrap().clear();
+        self.associative_referent_by_cardinality
+            .write()
+            .unwrap()
+            .clear();
+        self.associative_referent_by_conditionality
+            .write()
+            .unwrap()
+            .clear();
+        for associative_referent in self.associative_referent.read().unwrap().values() {
+            let read = associative_referent.0.read().unwrap();
+            self.associative_referent_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            self.associative_referent_by_cardinality
+                .write()
+                .unwrap()
+                .insert(read.id, read.cardinality);
+            self.associative_referent_by_conditionality
+                .write()
+                .unwrap()
+                .insert(read.id, read.conditionality);
+        }
+        self.associative_referrer_by_obj_id.write().unwrap().clear();
+        self.associative_referrer_by_cardinality
+            .write()
+            .unwrap()
+            .clear();
+        for associative_referrer in self.associative_referrer.read().unwrap().values() {
+            let read = associative_referrer.0.read().unwrap();
+            self.associative_referrer_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            self.associative_referrer_by_cardinality
+                .write()
+                .unwrap()
+                .insert(read.id, read.cardinality);
+        }
+        self.attribute_by_obj_id.write().unwrap().clear();
+        self.attribute_by_ty.write().unwrap().clear();
+        for attribute in self.attribute.read().unwrap().values() {
+            let read = attribute.0.read().unwrap();
+            self.attribute_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            self.attribute_by_ty
+                .write()
+                .unwrap()
+                .insert(read.id, read.ty);
+        }
+        self.binary_by_from.write().unwrap().clear();
+        self.binary_by_to.write().unwrap().clear();
+        for binary in self.binary.read().unwrap().values() {
+            let read = binary.0.read().unwrap();
+            self.binary_by_from
+                .write()
+                .unwrap()
+                .insert(read.id, read.from);
+            self.binary_by_to.write().unwrap().insert(read.id, read.to);
+        }
+        self.enumerator_by_enumeration.write().unwrap().clear();
+        for enumerator in self.enumerator.read().unwrap().values() {
+            let read = enumerator.0.read().unwrap();
+            self.enumerator_by_enumeration
+                .write()
+                .unwrap()
+                .insert(read.id, read.enumeration);
+        }
+        self.event_by_obj_id.write().unwrap().clear();
+        for event in self.event.read().unwrap().values() {
+            let read = event.0.read().unwrap();
+            self.event_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+        }
+        self.isa_by_supertype.write().unwrap().clear();
+        for isa in self.isa.read().unwrap().values() {
+            let read = isa.0.read().unwrap();
+            self.isa_by_supertype
+                .write()
+                .unwrap()
+                .insert(read.id, read.supertype);
+        }
+        self.next_state_by_to.write().unwrap().clear();
+        for next_state in self.next_state.read().unwrap().values() {
+            let read = next_state.0.read().unwrap();
+            self.next_state_by_to
+                .write()
+                .unwrap()
+                .insert(read.id, read.to);
+        }
+        self.operation_by_obj_id.write().unwrap().clear();
+        self.operation_by_ret.write().unwrap().clear();
+        for operation in self.operation.read().unwrap().values() {
+            let read = operation.0.read().unwrap();
+            self.operation_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            if let Some(ret) = read.ret {
+                self.operation_by_ret.write().unwrap().insert(read.id, ret);
+            }
+        }
+        self.parameter_by_event.write().unwrap().clear();
+        self.parameter_by_ty.write().unwrap().clear();
+        for parameter in self.parameter.read().unwrap().values() {
+            let read = parameter.0.read().unwrap();
+            self.parameter_by_event
+                .write()
+                .unwrap()
+                .insert(read.id, read.event);
+            self.parameter_by_ty
+                .write()
+                .unwrap()
+                .insert(read.id, read.ty);
+        }
+        self.referent_by_obj_id.write().unwrap().clear();
+        self.referent_by_cardinality.write().unwrap().clear();
+        self.referent_by_conditionality.write().unwrap().clear();
+        for referent in self.referent.read().unwrap().values() {
+            let read = referent.0.read().unwrap();
+            self.referent_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            self.referent_by_cardinality
+                .write()
+                .unwrap()
+                .insert(read.id, read.cardinality);
+            self.referent_by_conditionality
+                .write()
+                .unwrap()
+                .insert(read.id, read.conditionality);
+        }
+        self.referrer_by_obj_id.write().unwrap().clear();
+        self.referrer_by_cardinality.write().unwrap().clear();
+        self.referrer_by_conditionality.write().unwrap().clear();
+        for referrer in self.referrer.read().unwrap().values() {
+            let read = referrer.0.read().unwrap();
+            self.referrer_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            self.referrer_by_cardinality
+                .write()
+                .unwrap()
+                .insert(read.id, read.cardinality);
+            self.referrer_by_conditionality
+                .write()
+                .unwrap()
+                .insert(read.id, read.conditionality);
+        }
+        self.state_by_obj_id.write().unwrap().clear();
+        for state in self.state.read().unwrap().values() {
+            let read = state.0.read().unwrap();
+            self.state_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+        }
+        self.subtype_by_obj_id.write().unwrap().clear();
+        self.subtype_by_isa.write().unwrap().clear();
+        for subtype in self.subtype.read().unwrap().values() {
+            let read = subtype.0.read().unwrap();
+            self.subtype_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+            self.subtype_by_isa
+                .write()
+                .unwrap()
+                .insert(read.id, read.isa);
+        }
+        self.supertype_by_obj_id.write().unwrap().clear();
+        for supertype in self.supertype.read().unwrap().values() {
+            let read = supertype.0.read().unwrap();
+            self.supertype_by_obj_id
+                .write()
+                .unwrap()
+                .insert(read.id, read.obj_id);
+        }
+        self.transition_by_from.write().unwrap().clear();
+        self.transition_by_event.write().unwrap().clear();
+        for transition in self.transition.read().unwrap().values() {
+            let read = transition.0.read().unwrap();
+            self.transition_by_from
+                .write()
+                .unwrap()
+                .insert(read.id, read.from);
+            self.transition_by_event
+                .write()
+                .unwrap()
+                .insert(read.id, read.event);
+        }
     }
 
-    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
-
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"v2::sarzak-object-store-persistence"}}}
     /// Persist the store.
     ///
@@ -1293,9 +3743,179 @@ impl ObjectStore {
     ///
     /// The store is persisted as a directory of JSON files. The intention
     /// is that this directory can be checked into version control.
-    /// In fact, I intend to add automagic git integration as an option.
+    /// [`Domain::persist_git`](crate::v2::domain::Domain::persist_git) will
+    /// also stage and commit the changes.
+    ///
+    /// If the store was loaded from, or last persisted to, `path`, then only
+    /// the instances that were inter'd or exorcised since then are written.
+    /// Changes made through an instance's lock aren't seen, so inter the
+    /// instance again after editing it. Use [`ObjectStore::persist_all`] to
+    /// compare everything with what's on disk instead.
     pub fn persist<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
         let path = path.as_ref();
+        let in_sync = self.persisted.read().unwrap().as_deref() == Some(path)
+            && path.join("sarzak.json").is_dir();
+
+        if in_sync {
+            self.persist_changes(path)
+        } else {
+            self.persist_all(path)
+        }
+    }
+
+    /// Write the instances that have changed since the last persist
+    ///
+    fn persist_changes(&self, path: &Path) -> io::Result<()> {
+        let path = path.join("sarzak.json");
+        let mut dirty = self.dirty.write().unwrap();
+
+        for (table, id) in dirty.drain() {
+            match table {
+                Table::AcknowledgedEvent => write_instance(
+                    &path.join("acknowledged_event"),
+                    &id,
+                    self.acknowledged_event.read().unwrap().get(&id),
+                )?,
+                Table::AnAssociativeReferent => write_instance(
+                    &path.join("an_associative_referent"),
+                    &id,
+                    self.an_associative_referent.read().unwrap().get(&id),
+                )?,
+                Table::Argument => write_instance(
+                    &path.join("argument"),
+                    &id,
+                    self.argument.read().unwrap().get(&id),
+                )?,
+                Table::Associative => write_instance(
+                    &path.join("associative"),
+                    &id,
+                    self.associative.read().unwrap().get(&id),
+                )?,
+                Table::AssociativeReferent => write_instance(
+                    &path.join("associative_referent"),
+                    &id,
+                    self.associative_referent.read().unwrap().get(&id),
+                )?,
+                Table::AssociativeReferrer => write_instance(
+                    &path.join("associative_referrer"),
+                    &id,
+                    self.associative_referrer.read().unwrap().get(&id),
+                )?,
+                Table::Attribute => write_instance(
+                    &path.join("attribute"),
+                    &id,
+                    self.attribute.read().unwrap().get(&id),
+                )?,
+                Table::Binary => write_instance(
+                    &path.join("binary"),
+                    &id,
+                    self.binary.read().unwrap().get(&id),
+                )?,
+                Table::Cardinality => write_instance(
+                    &path.join("cardinality"),
+                    &id,
+                    self.cardinality.read().unwrap().get(&id),
+                )?,
+                Table::Conditionality => write_instance(
+                    &path.join("conditionality"),
+                    &id,
+                    self.conditionality.read().unwrap().get(&id),
+                )?,
+                Table::Enumeration => write_instance(
+                    &path.join("enumeration"),
+                    &id,
+                    self.enumeration.read().unwrap().get(&id),
+                )?,
+                Table::Enumerator => write_instance(
+                    &path.join("enumerator"),
+                    &id,
+                    self.enumerator.read().unwrap().get(&id),
+                )?,
+                Table::Event => write_instance(
+                    &path.join("event"),
+                    &id,
+                    self.event.read().unwrap().get(&id),
+                )?,
+                Table::External => write_instance(
+                    &path.join("external"),
+                    &id,
+                    self.external.read().unwrap().get(&id),
+                )?,
+                Table::Isa => {
+                    write_instance(&path.join("isa"), &id, self.isa.read().unwrap().get(&id))?
+                }
+                Table::NextState => write_instance(
+                    &path.join("next_state"),
+                    &id,
+                    self.next_state.read().unwrap().get(&id),
+                )?,
+                Table::Object => write_instance(
+                    &path.join("object"),
+                    &id,
+                    self.object.read().unwrap().get(&id),
+                )?,
+                Table::Operation => write_instance(
+                    &path.join("operation"),
+                    &id,
+                    self.operation.read().unwrap().get(&id),
+                )?,
+                Table::Parameter => write_instance(
+                    &path.join("parameter"),
+                    &id,
+                    self.parameter.read().unwrap().get(&id),
+                )?,
+                Table::Referent => write_instance(
+                    &path.join("referent"),
+                    &id,
+                    self.referent.read().unwrap().get(&id),
+                )?,
+                Table::Referrer => write_instance(
+                    &path.join("referrer"),
+                    &id,
+                    self.referrer.read().unwrap().get(&id),
+                )?,
+                Table::Relationship => write_instance(
+                    &path.join("relationship"),
+                    &id,
+                    self.relationship.read().unwrap().get(&id),
+                )?,
+                Table::State => write_instance(
+                    &path.join("state"),
+                    &id,
+                    self.state.read().unwrap().get(&id),
+                )?,
+                Table::Subtype => write_instance(
+                    &path.join("subtype"),
+                    &id,
+                    self.subtype.read().unwrap().get(&id),
+                )?,
+                Table::Supertype => write_instance(
+                    &path.join("supertype"),
+                    &id,
+                    self.supertype.read().unwrap().get(&id),
+                )?,
+                Table::Transition => write_instance(
+                    &path.join("transition"),
+                    &id,
+                    self.transition.read().unwrap().get(&id),
+                )?,
+                Table::Ty => {
+                    write_instance(&path.join("ty"), &id, self.ty.read().unwrap().get(&id))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Persist the whole store.
+    ///
+    /// Every instance is compared with what's on disk, and written if it's
+    /// different. Files for instances that aren't in the store are removed.
+    pub fn persist_all<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        self.dirty.write().unwrap().clear();
+        *self.persisted.write().unwrap() = None;
         fs::create_dir_all(path)?;
 
         let path = path.join("sarzak.json");
@@ -1388,6 +4008,43 @@ impl ObjectStore {
             }
         }
 
+        // Persist Argument.
+        {
+            let path = path.join("argument");
+            fs::create_dir_all(&path)?;
+            for argument_tuple in self.argument.read().unwrap().values() {
+                let path = path.join(format!("{}.json", argument_tuple.0.read().unwrap().id));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Argument>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != argument_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &argument_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &argument_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.argument.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
         // Persist Associative.
         {
             let path = path.join("associative");
@@ -1477,19 +4134,207 @@ impl ObjectStore {
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<AssociativeReferrer>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<AssociativeReferrer>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != associative_referrer_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &associative_referrer_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &associative_referrer_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.associative_referrer.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
+        // Persist Attribute.
+        {
+            let path = path.join("attribute");
+            fs::create_dir_all(&path)?;
+            for attribute_tuple in self.attribute.read().unwrap().values() {
+                let path = path.join(format!("{}.json", attribute_tuple.0.read().unwrap().id));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Attribute>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != attribute_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &attribute_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &attribute_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.attribute.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
+        // Persist Binary.
+        {
+            let path = path.join("binary");
+            fs::create_dir_all(&path)?;
+            for binary_tuple in self.binary.read().unwrap().values() {
+                let path = path.join(format!("{}.json", binary_tuple.0.read().unwrap().id));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Binary>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != binary_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &binary_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &binary_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.binary.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
+        // Persist Cardinality.
+        {
+            let path = path.join("cardinality");
+            fs::create_dir_all(&path)?;
+            for cardinality_tuple in self.cardinality.read().unwrap().values() {
+                let path = path.join(format!("{}.json", cardinality_tuple.0.read().unwrap().id()));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Cardinality>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != cardinality_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &cardinality_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &cardinality_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.cardinality.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
+        // Persist Conditionality.
+        {
+            let path = path.join("conditionality");
+            fs::create_dir_all(&path)?;
+            for conditionality_tuple in self.conditionality.read().unwrap().values() {
+                let path = path.join(format!(
+                    "{}.json",
+                    conditionality_tuple.0.read().unwrap().id()
+                ));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Conditionality>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != conditionality_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &conditionality_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &conditionality_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.conditionality.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
+        // Persist Enumeration.
+        {
+            let path = path.join("enumeration");
+            fs::create_dir_all(&path)?;
+            for enumeration_tuple in self.enumeration.read().unwrap().values() {
+                let path = path.join(format!("{}.json", enumeration_tuple.0.read().unwrap().id));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Enumeration>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != associative_referrer_tuple.0.read().unwrap().to_owned()
+                        != enumeration_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &associative_referrer_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &enumeration_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &associative_referrer_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &enumeration_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1498,35 +4343,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.associative_referrer.read().unwrap().contains_key(&id) {
+                    if !self.enumeration.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Attribute.
+        // Persist Enumerator.
         {
-            let path = path.join("attribute");
+            let path = path.join("enumerator");
             fs::create_dir_all(&path)?;
-            for attribute_tuple in self.attribute.read().unwrap().values() {
-                let path = path.join(format!("{}.json", attribute_tuple.0.read().unwrap().id));
+            for enumerator_tuple in self.enumerator.read().unwrap().values() {
+                let path = path.join(format!("{}.json", enumerator_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Attribute>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<Enumerator>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != attribute_tuple.0.read().unwrap().to_owned()
+                        != enumerator_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &attribute_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &enumerator_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &attribute_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &enumerator_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1535,35 +4380,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.attribute.read().unwrap().contains_key(&id) {
+                    if !self.enumerator.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Binary.
+        // Persist Event.
         {
-            let path = path.join("binary");
+            let path = path.join("event");
             fs::create_dir_all(&path)?;
-            for binary_tuple in self.binary.read().unwrap().values() {
-                let path = path.join(format!("{}.json", binary_tuple.0.read().unwrap().id));
+            for event_tuple in self.event.read().unwrap().values() {
+                let path = path.join(format!("{}.json", event_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Binary>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<Event>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != binary_tuple.0.read().unwrap().to_owned()
+                        != event_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &binary_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &event_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &binary_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &event_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1572,35 +4417,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.binary.read().unwrap().contains_key(&id) {
+                    if !self.event.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Cardinality.
+        // Persist External.
         {
-            let path = path.join("cardinality");
+            let path = path.join("external");
             fs::create_dir_all(&path)?;
-            for cardinality_tuple in self.cardinality.read().unwrap().values() {
-                let path = path.join(format!("{}.json", cardinality_tuple.0.read().unwrap().id()));
+            for external_tuple in self.external.read().unwrap().values() {
+                let path = path.join(format!("{}.json", external_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Cardinality>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<External>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != cardinality_tuple.0.read().unwrap().to_owned()
+                        != external_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &cardinality_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &external_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &cardinality_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &external_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1609,38 +4454,34 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.cardinality.read().unwrap().contains_key(&id) {
+                    if !self.external.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Conditionality.
+        // Persist Isa.
         {
-            let path = path.join("conditionality");
+            let path = path.join("isa");
             fs::create_dir_all(&path)?;
-            for conditionality_tuple in self.conditionality.read().unwrap().values() {
-                let path = path.join(format!(
-                    "{}.json",
-                    conditionality_tuple.0.read().unwrap().id()
-                ));
+            for isa_tuple in self.isa.read().unwrap().values() {
+                let path = path.join(format!("{}.json", isa_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Conditionality>>, SystemTime) =
-                        serde_json::from_reader(reader)?;
+                    let on_disk: (Arc<RwLock<Isa>>, SystemTime) = serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != conditionality_tuple.0.read().unwrap().to_owned()
+                        != isa_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &conditionality_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &isa_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &conditionality_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &isa_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1649,35 +4490,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.conditionality.read().unwrap().contains_key(&id) {
+                    if !self.isa.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Event.
+        // Persist NextState.
         {
-            let path = path.join("event");
+            let path = path.join("next_state");
             fs::create_dir_all(&path)?;
-            for event_tuple in self.event.read().unwrap().values() {
-                let path = path.join(format!("{}.json", event_tuple.0.read().unwrap().id));
+            for next_state_tuple in self.next_state.read().unwrap().values() {
+                let path = path.join(format!("{}.json", next_state_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Event>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<NextState>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != event_tuple.0.read().unwrap().to_owned()
+                        != next_state_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &event_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &next_state_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &event_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &next_state_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1686,35 +4527,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.event.read().unwrap().contains_key(&id) {
+                    if !self.next_state.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist External.
+        // Persist Object.
         {
-            let path = path.join("external");
+            let path = path.join("object");
             fs::create_dir_all(&path)?;
-            for external_tuple in self.external.read().unwrap().values() {
-                let path = path.join(format!("{}.json", external_tuple.0.read().unwrap().id));
+            for object_tuple in self.object.read().unwrap().values() {
+                let path = path.join(format!("{}.json", object_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<External>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<Object>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != external_tuple.0.read().unwrap().to_owned()
+                        != object_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &external_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &object_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &external_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &object_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1723,34 +4564,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.external.read().unwrap().contains_key(&id) {
+                    if !self.object.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Isa.
+        // Persist Operation.
         {
-            let path = path.join("isa");
+            let path = path.join("operation");
             fs::create_dir_all(&path)?;
-            for isa_tuple in self.isa.read().unwrap().values() {
-                let path = path.join(format!("{}.json", isa_tuple.0.read().unwrap().id));
+            for operation_tuple in self.operation.read().unwrap().values() {
+                let path = path.join(format!("{}.json", operation_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Isa>>, SystemTime) = serde_json::from_reader(reader)?;
+                    let on_disk: (Arc<RwLock<Operation>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != isa_tuple.0.read().unwrap().to_owned()
+                        != operation_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &isa_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &operation_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &isa_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &operation_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1759,35 +4601,35 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.isa.read().unwrap().contains_key(&id) {
+                    if !self.operation.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
             }
         }
 
-        // Persist Object.
+        // Persist Parameter.
         {
-            let path = path.join("object");
+            let path = path.join("parameter");
             fs::create_dir_all(&path)?;
-            for object_tuple in self.object.read().unwrap().values() {
-                let path = path.join(format!("{}.json", object_tuple.0.read().unwrap().id));
+            for parameter_tuple in self.parameter.read().unwrap().values() {
+                let path = path.join(format!("{}.json", parameter_tuple.0.read().unwrap().id));
                 if path.exists() {
                     let file = fs::File::open(&path)?;
                     let reader = io::BufReader::new(file);
-                    let on_disk: (Arc<RwLock<Object>>, SystemTime) =
+                    let on_disk: (Arc<RwLock<Parameter>>, SystemTime) =
                         serde_json::from_reader(reader)?;
                     if on_disk.0.read().unwrap().to_owned()
-                        != object_tuple.0.read().unwrap().to_owned()
+                        != parameter_tuple.0.read().unwrap().to_owned()
                     {
                         let file = fs::File::create(path)?;
                         let mut writer = io::BufWriter::new(file);
-                        serde_json::to_writer_pretty(&mut writer, &object_tuple)?;
+                        serde_json::to_writer_pretty(&mut writer, &parameter_tuple)?;
                     }
                 } else {
                     let file = fs::File::create(&path)?;
                     let mut writer = io::BufWriter::new(file);
-                    serde_json::to_writer_pretty(&mut writer, &object_tuple)?;
+                    serde_json::to_writer_pretty(&mut writer, &parameter_tuple)?;
                 }
             }
             for file in fs::read_dir(&path)? {
@@ -1796,7 +4638,7 @@ impl ObjectStore {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
                 let id = file_name.split('.').next().unwrap();
                 if let Ok(id) = Uuid::parse_str(id) {
-                    if !self.object.read().unwrap().contains_key(&id) {
+                    if !self.parameter.read().unwrap().contains_key(&id) {
                         fs::remove_file(path)?;
                     }
                 }
@@ -2028,6 +4870,43 @@ impl ObjectStore {
             }
         }
 
+        // Persist Transition.
+        {
+            let path = path.join("transition");
+            fs::create_dir_all(&path)?;
+            for transition_tuple in self.transition.read().unwrap().values() {
+                let path = path.join(format!("{}.json", transition_tuple.0.read().unwrap().id));
+                if path.exists() {
+                    let file = fs::File::open(&path)?;
+                    let reader = io::BufReader::new(file);
+                    let on_disk: (Arc<RwLock<Transition>>, SystemTime) =
+                        serde_json::from_reader(reader)?;
+                    if on_disk.0.read().unwrap().to_owned()
+                        != transition_tuple.0.read().unwrap().to_owned()
+                    {
+                        let file = fs::File::create(path)?;
+                        let mut writer = io::BufWriter::new(file);
+                        serde_json::to_writer_pretty(&mut writer, &transition_tuple)?;
+                    }
+                } else {
+                    let file = fs::File::create(&path)?;
+                    let mut writer = io::BufWriter::new(file);
+                    serde_json::to_writer_pretty(&mut writer, &transition_tuple)?;
+                }
+            }
+            for file in fs::read_dir(&path)? {
+                let file = file?;
+                let path = file.path();
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                let id = file_name.split('.').next().unwrap();
+                if let Ok(id) = Uuid::parse_str(id) {
+                    if !self.transition.read().unwrap().contains_key(&id) {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+
         // Persist Type.
         {
             let path = path.join("ty");
@@ -2063,27 +4942,32 @@ impl ObjectStore {
             }
         }
 
+        self.dirty.write().unwrap().clear();
+        *self.persisted.write().unwrap() = Some(path.parent().unwrap().to_path_buf());
+
         Ok(())
     }
 
     /// Load the store.
     ///
     pub fn from_bincode(code: &[u8]) -> io::Result<Self> {
-        Ok(bincode::deserialize(code).unwrap())
+        bincode::deserialize(code).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
     /// The store is as a bincode file.
     pub fn load_bincode<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let path = path.as_ref();
         let bin_file = fs::File::open(path)?;
-        Ok(bincode::deserialize_from(bin_file).unwrap())
+        bincode::deserialize_from(bin_file)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
     /// Load the store.
     ///
     /// The store is persisted as a directory of JSON files. The intention
     /// is that this directory can be checked into version control.
-    /// In fact, I intend to add automagic git integration as an option.
+    /// [`Domain::load_at_commit`](crate::v2::domain::Domain::load_at_commit)
+    /// loads a domain from a local commit.
     pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let path = path.as_ref();
         let path = path.join("sarzak.json");
@@ -2093,7 +4977,7 @@ impl ObjectStore {
         // Load Acknowledged Event.
         {
             let path = path.join("acknowledged_event");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2111,7 +4995,7 @@ impl ObjectStore {
         // Load An Associative Referent.
         {
             let path = path.join("an_associative_referent");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2126,10 +5010,29 @@ impl ObjectStore {
             }
         }
 
+        // Load Argument.
+        {
+            let path = path.join("argument");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let argument: (Arc<RwLock<Argument>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .argument
+                    .write()
+                    .unwrap()
+                    .insert(argument.0.read().unwrap().id, argument.clone());
+            }
+        }
+
         // Load Associative.
         {
             let path = path.join("associative");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2148,7 +5051,7 @@ impl ObjectStore {
         // Load Associative Referent.
         {
             let path = path.join("associative_referent");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2166,7 +5069,7 @@ impl ObjectStore {
         // Load Associative Referrer.
         {
             let path = path.join("associative_referrer");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2184,7 +5087,7 @@ impl ObjectStore {
         // Load Attribute.
         {
             let path = path.join("attribute");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2203,7 +5106,7 @@ impl ObjectStore {
         // Load Binary.
         {
             let path = path.join("binary");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2221,7 +5124,7 @@ impl ObjectStore {
         // Load Cardinality.
         {
             let path = path.join("cardinality");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2240,7 +5143,7 @@ impl ObjectStore {
         // Load Conditionality.
         {
             let path = path.join("conditionality");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2255,10 +5158,48 @@ impl ObjectStore {
             }
         }
 
+        // Load Enumeration.
+        {
+            let path = path.join("enumeration");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let enumeration: (Arc<RwLock<Enumeration>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .enumeration
+                    .write()
+                    .unwrap()
+                    .insert(enumeration.0.read().unwrap().id, enumeration.clone());
+            }
+        }
+
+        // Load Enumerator.
+        {
+            let path = path.join("enumerator");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let enumerator: (Arc<RwLock<Enumerator>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .enumerator
+                    .write()
+                    .unwrap()
+                    .insert(enumerator.0.read().unwrap().id, enumerator.clone());
+            }
+        }
+
         // Load Event.
         {
             let path = path.join("event");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2276,7 +5217,7 @@ impl ObjectStore {
         // Load External.
         {
             let path = path.join("external");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2295,7 +5236,7 @@ impl ObjectStore {
         // Load Isa.
         {
             let path = path.join("isa");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2310,10 +5251,29 @@ impl ObjectStore {
             }
         }
 
+        // Load NextState.
+        {
+            let path = path.join("next_state");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let next_state: (Arc<RwLock<NextState>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .next_state
+                    .write()
+                    .unwrap()
+                    .insert(next_state.0.read().unwrap().id, next_state.clone());
+            }
+        }
+
         // Load Object.
         {
             let path = path.join("object");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2332,10 +5292,48 @@ impl ObjectStore {
             }
         }
 
+        // Load Operation.
+        {
+            let path = path.join("operation");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let operation: (Arc<RwLock<Operation>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .operation
+                    .write()
+                    .unwrap()
+                    .insert(operation.0.read().unwrap().id, operation.clone());
+            }
+        }
+
+        // Load Parameter.
+        {
+            let path = path.join("parameter");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let parameter: (Arc<RwLock<Parameter>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .parameter
+                    .write()
+                    .unwrap()
+                    .insert(parameter.0.read().unwrap().id, parameter.clone());
+            }
+        }
+
         // Load Referent.
         {
             let path = path.join("referent");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2354,7 +5352,7 @@ impl ObjectStore {
         // Load Referrer.
         {
             let path = path.join("referrer");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2373,7 +5371,7 @@ impl ObjectStore {
         // Load Relationship.
         {
             let path = path.join("relationship");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2392,7 +5390,7 @@ impl ObjectStore {
         // Load State.
         {
             let path = path.join("state");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2410,7 +5408,7 @@ impl ObjectStore {
         // Load Subtype.
         {
             let path = path.join("subtype");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2428,7 +5426,7 @@ impl ObjectStore {
         // Load Supertype.
         {
             let path = path.join("supertype");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2444,10 +5442,29 @@ impl ObjectStore {
             }
         }
 
+        // Load Transition.
+        {
+            let path = path.join("transition");
+            let entries = read_table(&path)?;
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                let file = fs::File::open(path)?;
+                let reader = io::BufReader::new(file);
+                let transition: (Arc<RwLock<Transition>>, SystemTime) =
+                    serde_json::from_reader(reader)?;
+                store
+                    .transition
+                    .write()
+                    .unwrap()
+                    .insert(transition.0.read().unwrap().id, transition.clone());
+            }
+        }
+
         // Load Type.
         {
             let path = path.join("ty");
-            let entries = fs::read_dir(path)?;
+            let entries = read_table(&path)?;
             for entry in entries {
                 let entry = entry?;
                 let path = entry.path();
@@ -2462,9 +5479,63 @@ impl ObjectStore {
             }
         }
 
+        store.reindex();
+        store.dirty.write().unwrap().clear();
+        *store.persisted.write().unwrap() = Some(path.parent().unwrap().to_path_buf());
+
         Ok(store)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
+
+impl Transactional for ObjectStore {
+    fn begin(&mut self) {
+        self.journal.begin();
+    }
+
+    fn commit(&mut self) {
+        for change in self.journal.commit() {
+            self.subscribers.notify(change);
+        }
+    }
+
+    fn rollback(&mut self) {
+        let journal = self.journal.clone();
+        journal.rollback(self);
+    }
+}
+
+/// Write an instance to its file, or remove the file if there's no instance
+fn write_instance<T: Serialize>(path: &Path, id: &Uuid, instance: Option<&T>) -> io::Result<()> {
+    let path = path.join(format!("{id}.json"));
+
+    match instance {
+        Some(instance) => {
+            fs::create_dir_all(path.parent().unwrap())?;
+            let file = fs::File::create(path)?;
+            let mut writer = io::BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, instance)?;
+            writer.flush()
+        }
+        None => match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Read a table's directory
+///
+/// A table with no instances may not have a directory at all, since git
+/// doesn't keep empty directories.
+fn read_table(path: &Path) -> io::Result<impl Iterator<Item = io::Result<fs::DirEntry>>> {
+    let entries = if path.is_dir() {
+        Some(fs::read_dir(path)?)
+    } else {
+        None
+    };
+
+    Ok(entries.into_iter().flatten())
+}
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
@@ -5,29 +5,38 @@
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"v2::sarzak-module-definition"}}}
 pub mod acknowledged_event;
 pub mod an_associative_referent;
+pub mod argument;
 pub mod associative;
 pub mod associative_referent;
 pub mod associative_referrer;
 pub mod attribute;
 pub mod binary;
 pub mod boolean;
+pub mod cant_happen;
 pub mod cardinality;
 pub mod conditional;
 pub mod conditionality;
+pub mod enumeration;
+pub mod enumerator;
 pub mod event;
 pub mod external;
 pub mod float;
+pub mod ignored;
 pub mod integer;
 pub mod isa;
 pub mod many;
+pub mod next_state;
 pub mod object;
 pub mod one;
+pub mod operation;
+pub mod parameter;
 pub mod referent;
 pub mod referrer;
 pub mod relationship;
 pub mod state;
 pub mod subtype;
 pub mod supertype;
+pub mod transition;
 pub mod ty;
 pub mod unconditional;
 pub mod z_string;
@@ -35,6 +44,7 @@ pub mod z_uuid;
 
 pub use crate::v2::sarzak::acknowledged_event::AcknowledgedEvent;
 pub use crate::v2::sarzak::an_associative_referent::AnAssociativeReferent;
+pub use crate::v2::sarzak::argument::Argument;
 pub use crate::v2::sarzak::associative::Associative;
 pub use crate::v2::sarzak::associative_referent::AssociativeReferent;
 pub use crate::v2::sarzak::associative_referrer::AssociativeReferrer;
@@ -42,28 +52,39 @@ pub use crate::v2::sarzak::attribute::Attribute;
 pub use crate::v2::sarzak::binary::Binary;
 pub use crate::v2::sarzak::boolean::Boolean;
 pub use crate::v2::sarzak::boolean::BOOLEAN;
+pub use crate::v2::sarzak::cant_happen::CantHappen;
+pub use crate::v2::sarzak::cant_happen::CANT_HAPPEN;
 pub use crate::v2::sarzak::cardinality::Cardinality;
 pub use crate::v2::sarzak::conditional::Conditional;
 pub use crate::v2::sarzak::conditional::CONDITIONAL;
 pub use crate::v2::sarzak::conditionality::Conditionality;
+pub use crate::v2::sarzak::enumeration::Enumeration;
+pub use crate::v2::sarzak::enumerator::Enumerator;
 pub use crate::v2::sarzak::event::Event;
 pub use crate::v2::sarzak::external::External;
 pub use crate::v2::sarzak::float::Float;
 pub use crate::v2::sarzak::float::FLOAT;
+pub use crate::v2::sarzak::ignored::Ignored;
+pub use crate::v2::sarzak::ignored::IGNORED;
 pub use crate::v2::sarzak::integer::Integer;
 pub use crate::v2::sarzak::integer::INTEGER;
 pub use crate::v2::sarzak::isa::Isa;
 pub use crate::v2::sarzak::many::Many;
 pub use crate::v2::sarzak::many::MANY;
+pub use crate::v2::sarzak::next_state::NextState;
 pub use crate::v2::sarzak::object::Object;
 pub use crate::v2::sarzak::one::One;
 pub use crate::v2::sarzak::one::ONE;
+pub use crate::v2::sarzak::operation::Operation;
+pub use crate::v2::sarzak::parameter::Parameter;
 pub use crate::v2::sarzak::referent::Referent;
 pub use crate::v2::sarzak::referrer::Referrer;
 pub use crate::v2::sarzak::relationship::Relationship;
 pub use crate::v2::sarzak::state::State;
 pub use crate::v2::sarzak::subtype::Subtype;
 pub use crate::v2::sarzak::supertype::Supertype;
+pub use crate::v2::sarzak::transition::Transition;
+pub use crate::v2::sarzak::transition::TransitionEnum;
 pub use crate::v2::sarzak::ty::Ty;
 pub use crate::v2::sarzak::unconditional::Unconditional;
 pub use crate::v2::sarzak::unconditional::UNCONDITIONAL;
@@ -8,7 +8,7 @@ use crate::v2::sarzak::types::event::Event;
 use crate::v2::sarzak::types::state::State;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"acknowledged_event-struct-documentation"}}}
@@ -36,7 +36,14 @@ impl AcknowledgedEvent {
         state_id: &Arc<RwLock<State>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<AcknowledgedEvent>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::AcknowledgedEvent,
+            &format!(
+                "{}::{}",
+                event_id.read().unwrap().id,
+                state_id.read().unwrap().id
+            ),
+        );
         let new = Arc::new(RwLock::new(AcknowledgedEvent {
             id,
             event_id: event_id.read().unwrap().id,
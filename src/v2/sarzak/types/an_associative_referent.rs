@@ -8,7 +8,7 @@ use crate::v2::sarzak::types::associative::Associative;
 use crate::v2::sarzak::types::associative_referent::AssociativeReferent;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"an_associative_referent-struct-definition"}}}
@@ -32,7 +32,13 @@ impl AnAssociativeReferent {
         referent: &Arc<RwLock<AssociativeReferent>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<AnAssociativeReferent>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::AnAssociativeReferent,
+            &format!(
+                "{}::{referential_attribute}",
+                associative.read().unwrap().id
+            ),
+        );
         let new = Arc::new(RwLock::new(AnAssociativeReferent {
             id,
             referential_attribute,
@@ -0,0 +1,72 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"argument-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::operation::Operation;
+use crate::v2::sarzak::types::ty::Ty;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-struct-documentation"}}}
+/// A value passed to an [`Operation`]
+///
+/// An operation's arguments are ordered by `position`, starting at zero.
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Argument {
+    pub id: Uuid,
+    pub name: String,
+    pub position: i64,
+    /// R35: [`Argument`] 'is passed to' [`Operation`]
+    pub operation: Uuid,
+    /// R36: [`Argument`] 'has a' [`Ty`]
+    pub ty: Uuid,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-implementation"}}}
+impl Argument {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-struct-impl-new"}}}
+    /// Inter a new 'Argument' in the store, and return it's `id`.
+    pub fn new(
+        name: String,
+        position: i64,
+        operation: &Arc<RwLock<Operation>>,
+        ty: &Arc<RwLock<Ty>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Argument>> {
+        let id = store.new_id(
+            Table::Argument,
+            &format!("{}::{name}", operation.read().unwrap().name),
+        );
+        let new = Arc::new(RwLock::new(Argument {
+            id,
+            name,
+            position,
+            operation: operation.read().unwrap().id,
+            ty: ty.read().unwrap().id(),
+        }));
+        store.inter_argument(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-struct-impl-nav-forward-to-operation"}}}
+    /// Navigate to [`Operation`] across R35(1-*)
+    pub fn r35_operation<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Operation>>> {
+        vec![store.exhume_operation(&self.operation).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"argument-struct-impl-nav-forward-to-ty"}}}
+    /// Navigate to [`Ty`] across R36(1-*)
+    pub fn r36_ty<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Ty>>> {
+        vec![store.exhume_ty(&self.ty).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -9,7 +9,7 @@ use crate::v2::sarzak::types::associative_referrer::AssociativeReferrer;
 use crate::v2::sarzak::types::relationship::Relationship;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"associative-struct-definition"}}}
@@ -30,7 +30,7 @@ impl Associative {
         from: &Arc<RwLock<AssociativeReferrer>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Associative>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::Associative, &format!("R{number}"));
         let new = Arc::new(RwLock::new(Associative {
             id,
             number,
@@ -55,12 +55,7 @@ impl Associative {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AnAssociativeReferent>>> {
-        store
-            .iter_an_associative_referent()
-            .filter(|an_associative_referent| {
-                an_associative_referent.read().unwrap().associative == self.id
-            })
-            .collect()
+        store.exhume_an_associative_referent_by_associative(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"associative-impl-nav-subtype-to-supertype-relationship"}}}
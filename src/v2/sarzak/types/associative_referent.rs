@@ -10,7 +10,7 @@ use crate::v2::sarzak::types::conditionality::Conditionality;
 use crate::v2::sarzak::types::object::Object;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"associative_referent-struct-documentation"}}}
@@ -44,7 +44,10 @@ impl AssociativeReferent {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<AssociativeReferent>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::AssociativeReferent,
+            &format!("{}::{description}", obj_id.read().unwrap().name),
+        );
         let new = Arc::new(RwLock::new(AssociativeReferent {
             description,
             id,
@@ -90,10 +93,9 @@ impl AssociativeReferent {
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AnAssociativeReferent>>> {
         vec![store
-            .iter_an_associative_referent()
-            .find(|an_associative_referent| {
-                an_associative_referent.read().unwrap().referent == self.id
-            })
+            .exhume_an_associative_referent_by_referent(&self.id)
+            .into_iter()
+            .next()
             .unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
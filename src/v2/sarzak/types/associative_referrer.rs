@@ -9,7 +9,7 @@ use crate::v2::sarzak::types::cardinality::Cardinality;
 use crate::v2::sarzak::types::object::Object;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"associative_referrer-struct-documentation"}}}
@@ -38,7 +38,7 @@ impl AssociativeReferrer {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<AssociativeReferrer>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::AssociativeReferrer, &obj_id.read().unwrap().name);
         let new = Arc::new(RwLock::new(AssociativeReferrer {
             id,
             cardinality: cardinality.read().unwrap().id(),
@@ -66,8 +66,9 @@ impl AssociativeReferrer {
     /// Navigate to [`Associative`] across R21(1-1)
     pub fn r21_associative<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Associative>>> {
         vec![store
-            .iter_associative()
-            .find(|associative| associative.read().unwrap().from == self.id)
+            .exhume_associative_by_from(&self.id)
+            .into_iter()
+            .next()
             .unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
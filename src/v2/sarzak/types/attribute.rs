@@ -8,7 +8,7 @@ use crate::v2::sarzak::types::object::Object;
 use crate::v2::sarzak::types::ty::Ty;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"attribute-struct-documentation"}}}
@@ -38,7 +38,10 @@ impl Attribute {
         ty: &Arc<RwLock<Ty>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Attribute>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::Attribute,
+            &format!("{}::{name}", obj_id.read().unwrap().name),
+        );
         let new = Arc::new(RwLock::new(Attribute {
             id,
             name,
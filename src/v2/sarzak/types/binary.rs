@@ -9,7 +9,7 @@ use crate::v2::sarzak::types::referrer::Referrer;
 use crate::v2::sarzak::types::relationship::Relationship;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"binary-struct-documentation"}}}
@@ -46,7 +46,7 @@ impl Binary {
         to: &Arc<RwLock<Referent>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Binary>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::Binary, &format!("R{number}"));
         let new = Arc::new(RwLock::new(Binary {
             id,
             number,
@@ -0,0 +1,59 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"cant_happen-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-use-statements"}}}
+use serde::{Deserialize, Serialize};
+use uuid::{uuid, Uuid};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-const-documentation"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-documentation"}}}
+/// A [`Transition`](super::transition::Transition) cell for an event that must not arrive in the state.
+///
+/// Receiving it is an error.
+///
+/// ❗️{"singleton_object": true}
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-const-definition"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-definition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-implementation"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-impl-new"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-impl-new_"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-impl-nav-subtype-to-supertype-transition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-impl-new"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-impl-new_"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-impl-nav-subtype-to-supertype-transition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-impl-new"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-struct-impl-new_"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cant_happen-impl-nav-subtype-to-supertype-transition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+pub const CANT_HAPPEN: Uuid = uuid!["9314ea45-0329-5642-a5f0-b0311302990c"];
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CantHappen;
+
+impl CantHappen {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn id(&self) -> Uuid {
+        CANT_HAPPEN
+    }
+}
+
+impl Default for CantHappen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -50,12 +50,7 @@ impl Cardinality {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AssociativeReferent>>> {
-        store
-            .iter_associative_referent()
-            .filter(|associative_referent| {
-                associative_referent.read().unwrap().cardinality == self.id()
-            })
-            .collect()
+        store.exhume_associative_referent_by_cardinality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cardinality-struct-impl-nav-backward-1_M-to-associative_referrer"}}}
@@ -64,30 +59,19 @@ impl Cardinality {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AssociativeReferrer>>> {
-        store
-            .iter_associative_referrer()
-            .filter(|associative_referrer| {
-                associative_referrer.read().unwrap().cardinality == self.id()
-            })
-            .collect()
+        store.exhume_associative_referrer_by_cardinality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cardinality-struct-impl-nav-backward-1_M-to-referent"}}}
     /// Navigate to [`Referent`] across R8(1-M)
     pub fn r8_referent<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Referent>>> {
-        store
-            .iter_referent()
-            .filter(|referent| referent.read().unwrap().cardinality == self.id())
-            .collect()
+        store.exhume_referent_by_cardinality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"cardinality-struct-impl-nav-backward-1_M-to-referrer"}}}
     /// Navigate to [`Referrer`] across R9(1-M)
     pub fn r9_referrer<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Referrer>>> {
-        store
-            .iter_referrer()
-            .filter(|referrer| referrer.read().unwrap().cardinality == self.id())
-            .collect()
+        store.exhume_referrer_by_cardinality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
@@ -49,30 +49,19 @@ impl Conditionality {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AssociativeReferent>>> {
-        store
-            .iter_associative_referent()
-            .filter(|associative_referent| {
-                associative_referent.read().unwrap().conditionality == self.id()
-            })
-            .collect()
+        store.exhume_associative_referent_by_conditionality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"conditionality-struct-impl-nav-backward-1_M-to-referent"}}}
     /// Navigate to [`Referent`] across R12(1-M)
     pub fn r12_referent<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Referent>>> {
-        store
-            .iter_referent()
-            .filter(|referent| referent.read().unwrap().conditionality == self.id())
-            .collect()
+        store.exhume_referent_by_conditionality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"conditionality-struct-impl-nav-backward-1_M-to-referrer"}}}
     /// Navigate to [`Referrer`] across R11(1-M)
     pub fn r11_referrer<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Referrer>>> {
-        store
-            .iter_referrer()
-            .filter(|referrer| referrer.read().unwrap().conditionality == self.id())
-            .collect()
+        store.exhume_referrer_by_conditionality(&self.id())
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
@@ -0,0 +1,74 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"enumeration-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::enumerator::Enumerator;
+use crate::v2::sarzak::types::ty::Ty;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-struct-documentation"}}}
+/// A type with a fixed set of named values
+///
+/// The values are the enumeration's [`Enumerator`]s, ordered by `position`.
+/// Like an [`Object`], an enumeration is a [`Ty`], and it shares its `id` with
+/// the type.
+///
+/// [`Object`]: crate::v2::sarzak::types::Object
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Enumeration {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-implementation"}}}
+impl Enumeration {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-struct-impl-new"}}}
+    /// Inter a new 'Enumeration' in the store, and return it's `id`.
+    pub fn new(
+        name: String,
+        description: String,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Enumeration>> {
+        let id = store.new_id(Table::Enumeration, &name);
+        let new = Arc::new(RwLock::new(Enumeration {
+            id,
+            name,
+            description,
+        }));
+        store.inter_enumeration(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-struct-impl-nav-backward-1_Mc-to-enumerator"}}}
+    /// Navigate to [`Enumerator`] across R37(1-Mc)
+    pub fn r37_enumerator<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Enumerator>>> {
+        store.exhume_enumerator_by_enumeration(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumeration-impl-nav-subtype-to-supertype-ty"}}}
+    // Navigate to [`Ty`] across R3(isa)
+    pub fn r3_ty<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Ty>>> {
+        vec![store.exhume_ty(&self.id).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+impl Enumeration {
+    /// The enumeration's enumerators, in order
+    pub fn enumerators(&self, store: &SarzakStore) -> Vec<Arc<RwLock<Enumerator>>> {
+        let mut enumerators = self.r37_enumerator(store);
+        enumerators.sort_by_key(|e| e.read().unwrap().position);
+        enumerators
+    }
+}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
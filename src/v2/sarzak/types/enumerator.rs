@@ -0,0 +1,59 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"enumerator-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumerator-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::enumeration::Enumeration;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumerator-struct-documentation"}}}
+/// One of the values of an [`Enumeration`]
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumerator-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Enumerator {
+    pub id: Uuid,
+    pub name: String,
+    pub position: i64,
+    /// R37: [`Enumerator`] 'is a value of' [`Enumeration`]
+    pub enumeration: Uuid,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumerator-implementation"}}}
+impl Enumerator {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumerator-struct-impl-new"}}}
+    /// Inter a new 'Enumerator' in the store, and return it's `id`.
+    pub fn new(
+        name: String,
+        position: i64,
+        enumeration: &Arc<RwLock<Enumeration>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Enumerator>> {
+        let id = store.new_id(
+            Table::Enumerator,
+            &format!("{}::{name}", enumeration.read().unwrap().name),
+        );
+        let new = Arc::new(RwLock::new(Enumerator {
+            id,
+            name,
+            position,
+            enumeration: enumeration.read().unwrap().id,
+        }));
+        store.inter_enumerator(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"enumerator-struct-impl-nav-forward-to-enumeration"}}}
+    /// Navigate to [`Enumeration`] across R37(1-*)
+    pub fn r37_enumeration<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Enumeration>>> {
+        vec![store.exhume_enumeration(&self.enumeration).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
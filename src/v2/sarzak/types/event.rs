@@ -6,9 +6,11 @@ use uuid::Uuid;
 
 use crate::v2::sarzak::types::acknowledged_event::AcknowledgedEvent;
 use crate::v2::sarzak::types::object::Object;
+use crate::v2::sarzak::types::parameter::Parameter;
+use crate::v2::sarzak::types::transition::Transition;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"event-struct-documentation"}}}
@@ -34,7 +36,10 @@ impl Event {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Event>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::Event,
+            &format!("{}::{name}", obj_id.read().unwrap().name),
+        );
         let new = Arc::new(RwLock::new(Event {
             id,
             name,
@@ -56,12 +61,30 @@ impl Event {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AcknowledgedEvent>>> {
-        store
-            .iter_acknowledged_event()
-            .filter(|acknowledged_event| acknowledged_event.read().unwrap().event_id == self.id)
-            .collect()
+        store.exhume_acknowledged_event_by_event_id(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"event-struct-impl-nav-backward-1_Mc-to-transition"}}}
+    /// Navigate to [`Transition`] across R29(1-Mc)
+    pub fn r29_transition<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Transition>>> {
+        store.exhume_transition_by_event(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"event-struct-impl-nav-backward-1_Mc-to-parameter"}}}
+    /// Navigate to [`Parameter`] across R31(1-Mc)
+    pub fn r31_parameter<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Parameter>>> {
+        store.exhume_parameter_by_event(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+impl Event {
+    /// The event's parameters, in order
+    pub fn parameters(&self, store: &SarzakStore) -> Vec<Arc<RwLock<Parameter>>> {
+        let mut parameters = self.r31_parameter(store);
+        parameters.sort_by_key(|p| p.read().unwrap().position);
+        parameters
+    }
+}
 // {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
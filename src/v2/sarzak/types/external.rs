@@ -7,7 +7,7 @@ use uuid::Uuid;
 use crate::v2::sarzak::types::ty::Ty;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"external-struct-documentation"}}}
@@ -50,7 +50,7 @@ impl External {
         x_path: String,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<External>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::External, &name);
         let new = Arc::new(RwLock::new(External {
             ctor,
             id,
@@ -0,0 +1,59 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"ignored-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-use-statements"}}}
+use serde::{Deserialize, Serialize};
+use uuid::{uuid, Uuid};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-const-documentation"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-documentation"}}}
+/// A [`Transition`](super::transition::Transition) cell for an event that is dropped in the state.
+///
+/// The instance stays where it is.
+///
+/// ❗️{"singleton_object": true}
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-const-definition"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-definition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-implementation"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-impl-new"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-impl-new_"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-impl-nav-subtype-to-supertype-transition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-impl-new"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-impl-new_"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-impl-nav-subtype-to-supertype-transition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-impl-new"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-struct-impl-new_"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ignored-impl-nav-subtype-to-supertype-transition"}}}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+pub const IGNORED: Uuid = uuid!["c7982b8a-3be3-54e6-8a7c-28c768b2d401"];
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Ignored;
+
+impl Ignored {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn id(&self) -> Uuid {
+        IGNORED
+    }
+}
+
+impl Default for Ignored {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -9,7 +9,7 @@ use crate::v2::sarzak::types::subtype::Subtype;
 use crate::v2::sarzak::types::supertype::Supertype;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"isa-struct-definition"}}}
@@ -30,7 +30,7 @@ impl Isa {
         supertype: &Arc<RwLock<Supertype>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Isa>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::Isa, &format!("R{number}"));
         let new = Arc::new(RwLock::new(Isa {
             id,
             number,
@@ -51,10 +51,7 @@ impl Isa {
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"isa-struct-impl-nav-backward-1_M-to-subtype"}}}
     /// Navigate to [`Subtype`] across R27(1-M)
     pub fn r27_subtype<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Subtype>>> {
-        store
-            .iter_subtype()
-            .filter(|subtype| subtype.read().unwrap().isa == self.id)
-            .collect()
+        store.exhume_subtype_by_isa(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"isa-impl-nav-subtype-to-supertype-relationship"}}}
@@ -0,0 +1,66 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"next_state-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::state::State;
+use crate::v2::sarzak::types::transition::Transition;
+use crate::v2::sarzak::types::transition::TransitionEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-struct-documentation"}}}
+/// A [`Transition`] cell that moves the instance to another [`State`]
+///
+/// The state may be the one the instance is already in.
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NextState {
+    pub id: Uuid,
+    /// R38: [`NextState`] 'enters' [`State`]
+    pub to: Uuid,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-implementation"}}}
+impl NextState {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-struct-impl-new"}}}
+    /// Inter a new 'Next State' in the store, and return it's `id`.
+    pub fn new(to: &Arc<RwLock<State>>, store: &mut SarzakStore) -> Arc<RwLock<NextState>> {
+        let id = store.new_id(Table::NextState, &to.read().unwrap().name);
+        let new = Arc::new(RwLock::new(NextState {
+            id,
+            to: to.read().unwrap().id,
+        }));
+        store.inter_next_state(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-struct-impl-nav-forward-to-to"}}}
+    /// Navigate to [`State`] across R38(1-*)
+    pub fn r38_state<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<State>>> {
+        vec![store.exhume_state(&self.to).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"next_state-impl-nav-subtype-to-supertype-transition"}}}
+    // Navigate to [`Transition`] across R30(isa)
+    pub fn r30_transition<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Transition>>> {
+        vec![store
+            .iter_transition()
+            .find(|transition| {
+                if let TransitionEnum::NextState(id) = transition.read().unwrap().subtype {
+                    id == self.id
+                } else {
+                    false
+                }
+            })
+            .unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
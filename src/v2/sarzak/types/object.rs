@@ -8,6 +8,7 @@ use crate::v2::sarzak::types::associative_referent::AssociativeReferent;
 use crate::v2::sarzak::types::associative_referrer::AssociativeReferrer;
 use crate::v2::sarzak::types::attribute::Attribute;
 use crate::v2::sarzak::types::event::Event;
+use crate::v2::sarzak::types::operation::Operation;
 use crate::v2::sarzak::types::referent::Referent;
 use crate::v2::sarzak::types::referrer::Referrer;
 use crate::v2::sarzak::types::state::State;
@@ -16,7 +17,7 @@ use crate::v2::sarzak::types::supertype::Supertype;
 use crate::v2::sarzak::types::ty::Ty;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-documentation"}}}
@@ -49,7 +50,7 @@ impl Object {
         name: String,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Object>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::Object, &name);
         let new = Arc::new(RwLock::new(Object {
             description,
             id,
@@ -68,10 +69,7 @@ impl Object {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AssociativeReferent>>> {
-        store
-            .iter_associative_referent()
-            .filter(|associative_referent| associative_referent.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_associative_referent_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-associative_referrer"}}}
@@ -80,75 +78,51 @@ impl Object {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AssociativeReferrer>>> {
-        store
-            .iter_associative_referrer()
-            .filter(|associative_referrer| associative_referrer.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_associative_referrer_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_Mc-to-attribute"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-attribute"}}}
     /// Navigate to [`Attribute`] across R1(1-M)
     pub fn r1_attribute<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Attribute>>> {
-        store
-            .iter_attribute()
-            .filter(|attribute| attribute.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_attribute_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-event"}}}
     /// Navigate to [`Event`] across R19(1-M)
     pub fn r19_event<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Event>>> {
-        store
-            .iter_event()
-            .filter(|event| event.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_event_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-referent"}}}
     /// Navigate to [`Referent`] across R16(1-M)
     pub fn r16_referent<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Referent>>> {
-        store
-            .iter_referent()
-            .filter(|referent| referent.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_referent_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-referrer"}}}
     /// Navigate to [`Referrer`] across R17(1-M)
     pub fn r17_referrer<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Referrer>>> {
-        store
-            .iter_referrer()
-            .filter(|referrer| referrer.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_referrer_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-state"}}}
     /// Navigate to [`State`] across R18(1-M)
     pub fn r18_state<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<State>>> {
-        store
-            .iter_state()
-            .filter(|state| state.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_state_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-cond-to-subtype"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-subtype"}}}
     /// Navigate to [`Subtype`] across R15(1-M)
     pub fn r15_subtype<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Subtype>>> {
-        store
-            .iter_subtype()
-            .filter(|subtype| subtype.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_subtype_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_M-to-supertype"}}}
     /// Navigate to [`Supertype`] across R14(1-M)
     pub fn r14_supertype<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Supertype>>> {
-        store
-            .iter_supertype()
-            .filter(|supertype| supertype.read().unwrap().obj_id == self.id)
-            .collect()
+        store.exhume_supertype_by_obj_id(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-impl-nav-subtype-to-supertype-ty"}}}
@@ -157,6 +131,12 @@ impl Object {
         vec![store.exhume_ty(&self.id).unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"object-struct-impl-nav-backward-1_Mc-to-operation"}}}
+    /// Navigate to [`Operation`] across R33(1-Mc)
+    pub fn r33_operation<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Operation>>> {
+        store.exhume_operation_by_obj_id(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 // {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -0,0 +1,107 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"operation-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::argument::Argument;
+use crate::v2::sarzak::types::object::Object;
+use crate::v2::sarzak::types::ty::Ty;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-struct-documentation"}}}
+/// A method on an [`Object`]
+///
+/// An operation takes [`Argument`]s, in order, and may return a value. It's
+/// called on an instance of the object, unless it's class-based, in which case
+/// it's called on the object itself.
+///
+/// There's no `Option<String>` in the metamodel, so `has_body` says whether
+/// `body` means anything.
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Operation {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    /// A class-based operation doesn't have an instance, i.e., no `self`.
+    pub class_based: bool,
+    /// Where the operation's implementation is, e.g., a file of `dwarf` code
+    pub body: String,
+    pub has_body: bool,
+    /// R33: [`Operation`] 'belongs to' [`Object`]
+    pub obj_id: Uuid,
+    /// R34: [`Operation`] 'returns' [`Ty`]
+    pub ret: Option<Uuid>,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-implementation"}}}
+impl Operation {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-struct-impl-new"}}}
+    /// Inter a new 'Operation' in the store, and return it's `id`.
+    pub fn new(
+        name: String,
+        description: String,
+        class_based: bool,
+        body: String,
+        has_body: bool,
+        obj_id: &Arc<RwLock<Object>>,
+        ret: Option<&Arc<RwLock<Ty>>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Operation>> {
+        let id = store.new_id(
+            Table::Operation,
+            &format!("{}::{name}", obj_id.read().unwrap().name),
+        );
+        let new = Arc::new(RwLock::new(Operation {
+            id,
+            name,
+            description,
+            class_based,
+            body,
+            has_body,
+            obj_id: obj_id.read().unwrap().id,
+            ret: ret.map(|ty| ty.read().unwrap().id()),
+        }));
+        store.inter_operation(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-struct-impl-nav-forward-to-obj_id"}}}
+    /// Navigate to [`Object`] across R33(1-*)
+    pub fn r33_object<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Object>>> {
+        vec![store.exhume_object(&self.obj_id).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-struct-impl-nav-forward-cond-to-ret"}}}
+    /// Navigate to [`Ty`] across R34(1-*c)
+    pub fn r34_ty<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Ty>>> {
+        match self.ret {
+            Some(ref ret) => vec![store.exhume_ty(ret).unwrap()],
+            None => Vec::new(),
+        }
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"operation-struct-impl-nav-backward-1_Mc-to-argument"}}}
+    /// Navigate to [`Argument`] across R35(1-Mc)
+    pub fn r35_argument<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Argument>>> {
+        store.exhume_argument_by_operation(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+impl Operation {
+    /// The operation's arguments, in order
+    pub fn arguments(&self, store: &SarzakStore) -> Vec<Arc<RwLock<Argument>>> {
+        let mut arguments = self.r35_argument(store);
+        arguments.sort_by_key(|a| a.read().unwrap().position);
+        arguments
+    }
+}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -0,0 +1,73 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"parameter-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::event::Event;
+use crate::v2::sarzak::types::ty::Ty;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-struct-documentation"}}}
+/// Data carried by an [`Event`]
+///
+/// An event's parameters are ordered by `position`, starting at zero. They are
+/// the arguments of the state that the event moves an instance to.
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Parameter {
+    pub id: Uuid,
+    pub name: String,
+    pub position: i64,
+    /// R31: [`Parameter`] 'is carried by' [`Event`]
+    pub event: Uuid,
+    /// R32: [`Parameter`] 'has a' [`Ty`]
+    pub ty: Uuid,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-implementation"}}}
+impl Parameter {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-struct-impl-new"}}}
+    /// Inter a new 'Parameter' in the store, and return it's `id`.
+    pub fn new(
+        name: String,
+        position: i64,
+        event: &Arc<RwLock<Event>>,
+        ty: &Arc<RwLock<Ty>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Parameter>> {
+        let id = store.new_id(
+            Table::Parameter,
+            &format!("{}::{name}", event.read().unwrap().name),
+        );
+        let new = Arc::new(RwLock::new(Parameter {
+            id,
+            name,
+            position,
+            event: event.read().unwrap().id,
+            ty: ty.read().unwrap().id(),
+        }));
+        store.inter_parameter(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-struct-impl-nav-forward-to-event"}}}
+    /// Navigate to [`Event`] across R31(1-*)
+    pub fn r31_event<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Event>>> {
+        vec![store.exhume_event(&self.event).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"parameter-struct-impl-nav-forward-to-ty"}}}
+    /// Navigate to [`Ty`] across R32(1-*)
+    pub fn r32_ty<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Ty>>> {
+        vec![store.exhume_ty(&self.ty).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
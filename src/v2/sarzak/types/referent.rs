@@ -10,7 +10,7 @@ use crate::v2::sarzak::types::conditionality::Conditionality;
 use crate::v2::sarzak::types::object::Object;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"referent-struct-documentation"}}}
@@ -41,7 +41,10 @@ impl Referent {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Referent>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::Referent,
+            &format!("{}::{description}", obj_id.read().unwrap().name),
+        );
         let new = Arc::new(RwLock::new(Referent {
             description,
             id,
@@ -80,8 +83,9 @@ impl Referent {
     /// Navigate to [`Binary`] across R5(1-1)
     pub fn r5_binary<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Binary>>> {
         vec![store
-            .iter_binary()
-            .find(|binary| binary.read().unwrap().to == self.id)
+            .exhume_binary_by_to(&self.id)
+            .into_iter()
+            .next()
             .unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
@@ -10,7 +10,7 @@ use crate::v2::sarzak::types::conditionality::Conditionality;
 use crate::v2::sarzak::types::object::Object;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"referrer-struct-documentation"}}}
@@ -44,7 +44,10 @@ impl Referrer {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Referrer>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::Referrer,
+            &format!("{}::{referential_attribute}", obj_id.read().unwrap().name),
+        );
         let new = Arc::new(RwLock::new(Referrer {
             description,
             id,
@@ -84,8 +87,9 @@ impl Referrer {
     /// Navigate to [`Binary`] across R6(1-1)
     pub fn r6_binary<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Binary>>> {
         vec![store
-            .iter_binary()
-            .find(|binary| binary.read().unwrap().from == self.id)
+            .exhume_binary_by_from(&self.id)
+            .into_iter()
+            .next()
             .unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
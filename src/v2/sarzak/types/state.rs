@@ -5,10 +5,12 @@ use std::sync::RwLock;
 use uuid::Uuid;
 
 use crate::v2::sarzak::types::acknowledged_event::AcknowledgedEvent;
+use crate::v2::sarzak::types::next_state::NextState;
 use crate::v2::sarzak::types::object::Object;
+use crate::v2::sarzak::types::transition::Transition;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"state-struct-documentation"}}}
@@ -33,7 +35,10 @@ impl State {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<State>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::State,
+            &format!("{}::{name}", obj_id.read().unwrap().name),
+        );
         let new = Arc::new(RwLock::new(State {
             id,
             name,
@@ -58,10 +63,19 @@ impl State {
         &'a self,
         store: &'a SarzakStore,
     ) -> Vec<Arc<RwLock<AcknowledgedEvent>>> {
-        store
-            .iter_acknowledged_event()
-            .filter(|acknowledged_event| acknowledged_event.read().unwrap().state_id == self.id)
-            .collect()
+        store.exhume_acknowledged_event_by_state_id(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"state-struct-impl-nav-backward-1_Mc-to-transition"}}}
+    /// Navigate to [`Transition`] across R28(1-Mc)
+    pub fn r28_transition<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Transition>>> {
+        store.exhume_transition_by_from(&self.id)
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"state-struct-impl-nav-backward-1_Mc-to-next_state"}}}
+    /// Navigate to [`NextState`] across R38(1-Mc)
+    pub fn r38_next_state<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<NextState>>> {
+        store.exhume_next_state_by_to(&self.id)
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
@@ -8,7 +8,7 @@ use crate::v2::sarzak::types::isa::Isa;
 use crate::v2::sarzak::types::object::Object;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"subtype-struct-documentation"}}}
@@ -34,7 +34,14 @@ impl Subtype {
         obj_id: &Arc<RwLock<Object>>,
         store: &mut SarzakStore,
     ) -> Arc<RwLock<Subtype>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(
+            Table::Subtype,
+            &format!(
+                "{}::{}",
+                isa.read().unwrap().id,
+                obj_id.read().unwrap().name
+            ),
+        );
         let new = Arc::new(RwLock::new(Subtype {
             id,
             isa: isa.read().unwrap().id,
@@ -8,7 +8,7 @@ use crate::v2::sarzak::types::isa::Isa;
 use crate::v2::sarzak::types::object::Object;
 use serde::{Deserialize, Serialize};
 
-use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"supertype-struct-documentation"}}}
@@ -29,7 +29,7 @@ impl Supertype {
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"supertype-struct-impl-new"}}}
     /// Inter a new 'Supertype' in the store, and return it's `id`.
     pub fn new(obj_id: &Arc<RwLock<Object>>, store: &mut SarzakStore) -> Arc<RwLock<Supertype>> {
-        let id = Uuid::new_v4();
+        let id = store.new_id(Table::Supertype, &obj_id.read().unwrap().name);
         let new = Arc::new(RwLock::new(Supertype {
             id,
             obj_id: obj_id.read().unwrap().id,
@@ -50,8 +50,9 @@ impl Supertype {
     /// Navigate to [`Isa`] across R13(1-1)
     pub fn r13_isa<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Isa>>> {
         vec![store
-            .iter_isa()
-            .find(|isa| isa.read().unwrap().supertype == self.id)
+            .exhume_isa_by_supertype(&self.id)
+            .into_iter()
+            .next()
             .unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
@@ -0,0 +1,139 @@
+// {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"transition-struct-definition-file"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-use-statements"}}}
+use std::sync::Arc;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::v2::sarzak::types::cant_happen::CANT_HAPPEN;
+use crate::v2::sarzak::types::event::Event;
+use crate::v2::sarzak::types::ignored::IGNORED;
+use crate::v2::sarzak::types::next_state::NextState;
+use crate::v2::sarzak::types::state::State;
+use serde::{Deserialize, Serialize};
+
+use crate::v2::sarzak::store::{ObjectStore as SarzakStore, Table};
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-hybrid-documentation"}}}
+/// A cell in an [`Object`]'s state transition table
+///
+/// It says what happens when [`Event`] arrives while an instance is in the
+/// _from_ [`State`]: the instance moves to its [`NextState`], the event is
+/// ignored, or the event can't happen, and arriving is an error. A missing
+/// cell is treated as can't happen.
+///
+/// [`Object`]: crate::v2::sarzak::types::object::Object
+///
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-hybrid-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Transition {
+    pub subtype: TransitionEnum,
+    pub id: Uuid,
+    /// R29: [`Transition`] 'is triggered by' [`Event`]
+    pub event: Uuid,
+    /// R28: [`Transition`] 'leaves' [`State`]
+    pub from: Uuid,
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-hybrid-enum-definition"}}}
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum TransitionEnum {
+    CantHappen(Uuid),
+    Ignored(Uuid),
+    NextState(Uuid),
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-implementation"}}}
+impl Transition {
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-struct-impl-new_cant_happen"}}}
+    /// Inter a new Transition in the store, and return it's `id`.
+    pub fn new_cant_happen(
+        event: &Arc<RwLock<Event>>,
+        from: &Arc<RwLock<State>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Transition>> {
+        let id = store.new_id(
+            Table::Transition,
+            &format!(
+                "{}::{}",
+                from.read().unwrap().name,
+                event.read().unwrap().name
+            ),
+        );
+        let new = Arc::new(RwLock::new(Transition {
+            event: event.read().unwrap().id,
+            from: from.read().unwrap().id,
+            subtype: TransitionEnum::CantHappen(CANT_HAPPEN),
+            id,
+        }));
+        store.inter_transition(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-struct-impl-new_ignored"}}}
+    /// Inter a new Transition in the store, and return it's `id`.
+    pub fn new_ignored(
+        event: &Arc<RwLock<Event>>,
+        from: &Arc<RwLock<State>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Transition>> {
+        let id = store.new_id(
+            Table::Transition,
+            &format!(
+                "{}::{}",
+                from.read().unwrap().name,
+                event.read().unwrap().name
+            ),
+        );
+        let new = Arc::new(RwLock::new(Transition {
+            event: event.read().unwrap().id,
+            from: from.read().unwrap().id,
+            subtype: TransitionEnum::Ignored(IGNORED),
+            id,
+        }));
+        store.inter_transition(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-struct-impl-new_next_state"}}}
+    /// Inter a new Transition in the store, and return it's `id`.
+    pub fn new_next_state(
+        subtype: &Arc<RwLock<NextState>>,
+        event: &Arc<RwLock<Event>>,
+        from: &Arc<RwLock<State>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Transition>> {
+        let id = store.new_id(
+            Table::Transition,
+            &format!(
+                "{}::{}",
+                from.read().unwrap().name,
+                event.read().unwrap().name
+            ),
+        );
+        let new = Arc::new(RwLock::new(Transition {
+            event: event.read().unwrap().id,
+            from: from.read().unwrap().id,
+            subtype: TransitionEnum::NextState(subtype.read().unwrap().id),
+            id,
+        }));
+        store.inter_transition(new.clone());
+        new
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-struct-impl-nav-forward-to-event"}}}
+    /// Navigate to [`Event`] across R29(1-*)
+    pub fn r29_event<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Event>>> {
+        vec![store.exhume_event(&self.event).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"transition-struct-impl-nav-forward-to-from"}}}
+    /// Navigate to [`State`] across R28(1-*)
+    pub fn r28_state<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<State>>> {
+        vec![store.exhume_state(&self.from).unwrap()]
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+}
+// {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+// {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -1,12 +1,16 @@
 // {"magic":"","directive":{"Start":{"directive":"allow-editing","tag":"ty-struct-definition-file"}}}
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-use-statements"}}}
 use crate::v2::sarzak::store::ObjectStore as SarzakStore;
+use crate::v2::sarzak::types::argument::Argument;
 use crate::v2::sarzak::types::attribute::Attribute;
 use crate::v2::sarzak::types::boolean::BOOLEAN;
+use crate::v2::sarzak::types::enumeration::Enumeration;
 use crate::v2::sarzak::types::external::External;
 use crate::v2::sarzak::types::float::FLOAT;
 use crate::v2::sarzak::types::integer::INTEGER;
 use crate::v2::sarzak::types::object::Object;
+use crate::v2::sarzak::types::operation::Operation;
+use crate::v2::sarzak::types::parameter::Parameter;
 use crate::v2::sarzak::types::z_string::Z_STRING;
 use crate::v2::sarzak::types::z_uuid::Z_UUID;
 use serde::{Deserialize, Serialize};
@@ -21,6 +25,12 @@ use uuid::Uuid;
 /// There are several values available: [Integer], [Boolean], [Float], [String], and [UUID]
 /// .
 ///
+/// An [`Object`], an [`External`] and an [`Enumeration`] are types too. They
+/// share their `id` with the type.
+///
+/// `Enumeration` comes last, rather than in order, so that adding it didn't
+/// change the bincode of the variants after it.
+///
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-enum-definition"}}}
 #[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -32,6 +42,7 @@ pub enum Ty {
     Object(Uuid),
     ZString(Uuid),
     ZUuid(Uuid),
+    Enumeration(Uuid),
 }
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-implementation"}}}
@@ -94,6 +105,21 @@ impl Ty {
         store.exhume_ty(&Z_UUID).unwrap()
     }
 
+    /// Create a new instance of Ty::Enumeration
+    pub fn new_enumeration(
+        enumeration: &Arc<RwLock<Enumeration>>,
+        store: &mut SarzakStore,
+    ) -> Arc<RwLock<Self>> {
+        let id = enumeration.read().unwrap().id;
+        if let Some(enumeration) = store.exhume_ty(&id) {
+            enumeration
+        } else {
+            let new = Arc::new(RwLock::new(Self::Enumeration(id)));
+            store.inter_ty(new.clone());
+            new
+        }
+    }
+
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
     // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-get-id-impl"}}}
     pub fn id(&self) -> Uuid {
@@ -105,6 +131,7 @@ impl Ty {
             Self::Object(id) => *id,
             Self::ZString(id) => *id,
             Self::ZUuid(id) => *id,
+            Self::Enumeration(id) => *id,
         }
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
@@ -112,11 +139,30 @@ impl Ty {
     /// Navigate to [`Attribute`] across R2(1-1)
     pub fn r2_attribute<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Attribute>>> {
         vec![store
-            .iter_attribute()
-            .find(|attribute| attribute.read().unwrap().ty == self.id())
+            .exhume_attribute_by_ty(&self.id())
+            .into_iter()
+            .next()
             .unwrap()]
     }
     // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-struct-impl-nav-backward-1_Mc-to-parameter"}}}
+    /// Navigate to [`Parameter`] across R32(1-Mc)
+    pub fn r32_parameter<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Parameter>>> {
+        store.exhume_parameter_by_ty(&self.id())
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-struct-impl-nav-backward-1_Mc-to-operation"}}}
+    /// Navigate to [`Operation`] across R34(1-Mc)
+    pub fn r34_operation<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Operation>>> {
+        store.exhume_operation_by_ret(&self.id())
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
+    // {"magic":"","directive":{"Start":{"directive":"ignore-orig","tag":"ty-struct-impl-nav-backward-1_Mc-to-argument"}}}
+    /// Navigate to [`Argument`] across R36(1-Mc)
+    pub fn r36_argument<'a>(&'a self, store: &'a SarzakStore) -> Vec<Arc<RwLock<Argument>>> {
+        store.exhume_argument_by_ty(&self.id())
+    }
+    // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 }
 // {"magic":"","directive":{"End":{"directive":"ignore-orig"}}}
 // {"magic":"","directive":{"End":{"directive":"allow-editing"}}}
@@ -0,0 +1,351 @@
+//! Store Transactions
+//!
+//! Editing a model usually means touching several tables at once. A new
+//! binary relationship is a [`Binary`](crate::v2::sarzak::types::Binary), a
+//! `Referrer`, a `Referent` and a `Relationship`. If something goes wrong half
+//! way through, we want the store back the way it was.
+//!
+//! A [`Transaction`] keeps an undo log of everything that is inter'd and
+//! exorcised while it's open. It derefs to the store, so `inter_*`,
+//! `exorcise_*` and everything else work as usual, and reads see the
+//! transaction's own changes. Call [`Transaction::commit`] to keep the
+//! changes. Dropping the transaction without committing, or calling
+//! [`Transaction::rollback`], plays the log backwards.
+//!
+//! Subscribers hear about the changes when the transaction commits, one
+//! [`Change`] per instance, and not at all if it's rolled back. Transactions
+//! nest: an inner transaction's changes become part of the outer one when it
+//! commits.
+//!
+//! Only the tables are rolled back. The instances are shared with the store,
+//! so changes made through an instance's lock stick. Within a transaction,
+//! inter a modified copy instead.
+//!
+//! ```ignore
+//! let mut tx = store.transaction();
+//! let object = Object::new(description, key_letters, name, &mut tx);
+//! add_attributes(&object, &mut tx)?;
+//! tx.commit();
+//! ```
+use std::{
+    fmt,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashMap as HashMap;
+use uuid::Uuid;
+
+use crate::v2::notify::{Change, ChangeKind};
+
+/// A store that can take part in a [`Transaction`]
+pub trait Transactional: Sized {
+    /// Start logging changes
+    fn begin(&mut self);
+
+    /// Keep the changes since the matching `begin`
+    fn commit(&mut self);
+
+    /// Undo the changes since the matching `begin`
+    fn rollback(&mut self);
+}
+
+/// A set of changes to a store that are kept, or discarded, together
+pub struct Transaction<'a, S: Transactional> {
+    store: &'a mut S,
+    done: bool,
+}
+
+impl<'a, S: Transactional> Transaction<'a, S> {
+    pub fn new(store: &'a mut S) -> Self {
+        store.begin();
+        Self { store, done: false }
+    }
+
+    /// Keep the changes
+    pub fn commit(mut self) {
+        self.done = true;
+        self.store.commit();
+    }
+
+    /// Discard the changes
+    ///
+    /// This is what happens when the transaction is dropped, and is here so
+    /// that it can be said out loud.
+    pub fn rollback(self) {}
+}
+
+impl<'a, S: Transactional> Deref for Transaction<'a, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        self.store
+    }
+}
+
+impl<'a, S: Transactional> DerefMut for Transaction<'a, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        self.store
+    }
+}
+
+impl<'a, S: Transactional> Drop for Transaction<'a, S> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.store.rollback();
+        }
+    }
+}
+
+type Undo<S> = Box<dyn FnOnce(&mut S) + Send + Sync>;
+
+/// The undo logs of a store's open transactions
+///
+/// `S` is the store, and `T` is its `Table` enum. Like the subscribers, the
+/// journal is shared between clones of a store.
+pub(crate) struct Journal<S, T> {
+    inner: Arc<Mutex<Logs<S, T>>>,
+}
+
+struct Logs<S, T> {
+    open: Vec<Log<S, T>>,
+    // Set while a log is played backwards, so that the undo isn't itself
+    // logged or announced.
+    replaying: bool,
+}
+
+struct Log<S, T> {
+    undo: Vec<Undo<S>>,
+    changes: Vec<Change<T>>,
+}
+
+impl<S, T> Default for Journal<S, T> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Logs {
+                open: Vec::new(),
+                replaying: false,
+            })),
+        }
+    }
+}
+
+impl<S, T> Clone for Journal<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S, T> fmt::Debug for Journal<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Journal({})", self.inner.lock().unwrap().open.len())
+    }
+}
+
+impl<S, T: Copy + Eq + Hash> Journal<S, T> {
+    pub(crate) fn begin(&self) {
+        self.inner.lock().unwrap().open.push(Log {
+            undo: Vec::new(),
+            changes: Vec::new(),
+        });
+    }
+
+    /// Remember how to undo a change, if there is a transaction open
+    pub(crate) fn record<F>(&self, undo: F)
+    where
+        F: FnOnce(&mut S) + Send + Sync + 'static,
+    {
+        let mut logs = self.inner.lock().unwrap();
+        if !logs.replaying {
+            if let Some(log) = logs.open.last_mut() {
+                log.undo.push(Box::new(undo));
+            }
+        }
+    }
+
+    /// Hold on to `change` until the transaction commits
+    ///
+    /// Returns the change if there is no transaction open, and it should be
+    /// announced right away.
+    pub(crate) fn defer(&self, change: Change<T>) -> Option<Change<T>> {
+        let mut logs = self.inner.lock().unwrap();
+        if logs.replaying {
+            return None;
+        }
+        match logs.open.last_mut() {
+            Some(log) => {
+                log.changes.push(change);
+                None
+            }
+            None => Some(change),
+        }
+    }
+
+    /// Close the innermost transaction, and keep its changes
+    ///
+    /// Returns the changes to announce, which is nothing until the outermost
+    /// transaction commits.
+    pub(crate) fn commit(&self) -> Vec<Change<T>> {
+        let mut logs = self.inner.lock().unwrap();
+        let Some(log) = logs.open.pop() else {
+            return Vec::new();
+        };
+        match logs.open.last_mut() {
+            Some(outer) => {
+                outer.undo.extend(log.undo);
+                outer.changes.extend(log.changes);
+                Vec::new()
+            }
+            None => coalesce(log.changes),
+        }
+    }
+
+    /// Close the innermost transaction, and undo its changes
+    pub(crate) fn rollback(&self, store: &mut S) {
+        // The undo goes back through the store, so let go of the lock.
+        let log = {
+            let mut logs = self.inner.lock().unwrap();
+            logs.replaying = true;
+            logs.open.pop()
+        };
+        for undo in log.into_iter().flat_map(|log| log.undo).rev() {
+            undo(store);
+        }
+        self.inner.lock().unwrap().replaying = false;
+    }
+}
+
+/// Boil the changes down to one per instance
+///
+/// An instance that was inserted and then removed was never there, as far as
+/// anyone outside the transaction is concerned.
+fn coalesce<T: Copy + Eq + Hash>(changes: Vec<Change<T>>) -> Vec<Change<T>> {
+    let mut order = Vec::new();
+    let mut kinds: HashMap<(T, Uuid), Option<ChangeKind>> = HashMap::default();
+
+    for change in changes {
+        let key = (change.table, change.id);
+        let kind = match kinds.get(&key) {
+            None => {
+                order.push(key);
+                Some(change.kind)
+            }
+            Some(first) => match (*first, change.kind) {
+                (Some(ChangeKind::Inserted), ChangeKind::Updated) => Some(ChangeKind::Inserted),
+                (Some(ChangeKind::Inserted), ChangeKind::Removed) => None,
+                (Some(ChangeKind::Removed), ChangeKind::Inserted) => Some(ChangeKind::Updated),
+                (_, kind) => Some(kind),
+            },
+        };
+        kinds.insert(key, kind);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| {
+            kinds[&key].map(|kind| Change {
+                kind,
+                table: key.0,
+                id: key.1,
+            })
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "sarzak-rwlock"))]
+mod tests {
+    use crate::v2::{domain::Domain, sarzak::types::Object};
+
+    #[test]
+    fn test_rollback() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let objects = sarzak.iter_object().count();
+        let state = sarzak.exhume_object_id_by_name("State").unwrap();
+        let attributes = sarzak.exhume_attribute_by_obj_id(&state).len();
+
+        {
+            let mut tx = sarzak.transaction();
+            Object::new("".to_owned(), "NEW".to_owned(), "New".to_owned(), &mut tx);
+            for attribute in tx.exhume_attribute_by_obj_id(&state) {
+                let id = attribute.read().unwrap().id;
+                tx.exorcise_attribute(&id);
+            }
+            tx.exorcise_object(&state);
+
+            // The transaction sees its own changes
+            assert!(tx.exhume_object_id_by_name("New").is_some());
+            assert!(tx.exhume_object(&state).is_none());
+        }
+
+        assert_eq!(sarzak.iter_object().count(), objects);
+        assert!(sarzak.exhume_object_id_by_name("New").is_none());
+        assert_eq!(sarzak.exhume_object_id_by_name("State"), Some(state));
+        assert_eq!(sarzak.exhume_attribute_by_obj_id(&state).len(), attributes);
+
+        let mut tx = sarzak.transaction();
+        Object::new("".to_owned(), "NEW".to_owned(), "New".to_owned(), &mut tx);
+        tx.commit();
+
+        assert_eq!(sarzak.iter_object().count(), objects + 1);
+        assert!(sarzak.exhume_object_id_by_name("New").is_some());
+    }
+
+    #[test]
+    fn test_merlin_rollback() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let merlin = domain.merlin_mut();
+        let boxes = merlin.iter_x_box().count();
+        let id = merlin.iter_x_box().next().unwrap().read().unwrap().id;
+
+        let mut tx = merlin.transaction();
+        tx.exorcise_x_box(&id);
+        assert_eq!(tx.iter_x_box().count(), boxes - 1);
+        tx.rollback();
+
+        assert_eq!(merlin.iter_x_box().count(), boxes);
+    }
+
+    #[test]
+    fn test_nested() {
+        let mut domain = Domain::from_cuckoo("models/sarzak.json").unwrap();
+        let sarzak = domain.sarzak_mut();
+        let objects = sarzak.iter_object().count();
+        let state = sarzak.exhume_object_id_by_name("State").unwrap();
+
+        let mut outer = sarzak.transaction();
+        Object::new(
+            "".to_owned(),
+            "OUT".to_owned(),
+            "Outer".to_owned(),
+            &mut outer,
+        );
+        {
+            let mut inner = outer.transaction();
+            Object::new(
+                "".to_owned(),
+                "IN".to_owned(),
+                "Inner".to_owned(),
+                &mut inner,
+            );
+            inner.exorcise_object(&state);
+        }
+        // Only the inner transaction was rolled back
+        assert!(outer.exhume_object_id_by_name("Outer").is_some());
+        assert!(outer.exhume_object_id_by_name("Inner").is_none());
+        assert_eq!(outer.exhume_object_id_by_name("State"), Some(state));
+
+        let mut inner = outer.transaction();
+        inner.exorcise_object(&state);
+        inner.commit();
+        outer.rollback();
+
+        // Committing the inner transaction made it part of the outer one
+        assert_eq!(sarzak.iter_object().count(), objects);
+        assert_eq!(sarzak.exhume_object_id_by_name("State"), Some(state));
+        assert!(sarzak.exhume_object_id_by_name("Outer").is_none());
+    }
+}